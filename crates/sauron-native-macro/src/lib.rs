@@ -0,0 +1,35 @@
+//! Provides the `view!` macro, which allows writing the widget tree
+//! in a jsx like syntax.
+//!
+//! ```rust,ignore
+//! view! {
+//!     <column>
+//!         <button label="Hi" on_click=|_| Msg::Click/>
+//!         <paragraph>{ self.text }</paragraph>
+//!         { for self.items.iter().map(|item| item.view()) }
+//!     </column>
+//! }
+//! ```
+//! The widgets are expanded into the builder functions in `sauron_native::widget`
+//! and the attributes into the attribute and event functions,
+//! so `<button label="Hi"/>` is the same as `button(vec![label("Hi")])`.
+//!
+//! - Attribute values are literals, closures or expressions. Expressions which
+//!   contains `>` or `/` needs to be wrapped in braces, ie: `checked={count > 0}`
+//! - `{ expr }` as a child is a single node, `{ for expr }` is an iterator of nodes
+//! - `<paragraph>` accepts a single string literal or `{ expr }` as its text
+//!
+//! Unknown widgets and attributes are reported as compile errors.
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+mod node;
+mod widget_def;
+
+/// build a widget tree using a jsx like syntax
+#[proc_macro]
+pub fn view(input: TokenStream) -> TokenStream {
+    let element = syn::parse_macro_input!(input as node::Element);
+    quote::quote!(#element).into()
+}
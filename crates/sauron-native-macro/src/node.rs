@@ -0,0 +1,351 @@
+//! Parsing of the markup and the expansion into the builder functions
+use crate::widget_def::{self, WidgetKind};
+use proc_macro2::{Spacing, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{
+    braced,
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    token::Brace,
+    Error, Expr, Ident, LitStr, Result, Stmt, Token,
+};
+
+/// A widget element, ie: `<button label="Hi"/>`
+pub(crate) struct Element {
+    name: Ident,
+    kind: WidgetKind,
+    attrs: Vec<Attribute>,
+    children: Vec<Child>,
+}
+
+/// An attribute or an event of a widget, ie: `label="Hi"` or `on_click=|_| Msg::Click`
+struct Attribute {
+    name: Ident,
+    value: Expr,
+}
+
+enum Child {
+    Element(Element),
+    /// `{ expr }`, a single node
+    Node(Expr),
+    /// `{ for expr }`, an iterator of nodes
+    Nodes(Expr),
+    /// a string literal, which is only allowed in paragraph
+    Text(LitStr),
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Token![<]>()?;
+        let name = Ident::parse_any(input)?;
+        let kind =
+            widget_def::widget_kind(&name.to_string()).ok_or_else(|| {
+                Error::new(name.span(), format!("unknown widget `{}`", name))
+            })?;
+
+        let mut attrs = vec![];
+        let mut self_closing = false;
+        loop {
+            if input.peek(Token![/]) && input.peek2(Token![>]) {
+                input.parse::<Token![/]>()?;
+                input.parse::<Token![>]>()?;
+                self_closing = true;
+                break;
+            }
+            if input.peek(Token![>]) {
+                input.parse::<Token![>]>()?;
+                break;
+            }
+            if input.is_empty() {
+                return Err(Error::new(
+                    name.span(),
+                    format!("`<{}` is not closed with `>` or `/>`", name),
+                ));
+            }
+            attrs.push(input.parse()?);
+        }
+
+        let mut children = vec![];
+        if !self_closing {
+            loop {
+                if input.peek(Token![<]) && input.peek2(Token![/]) {
+                    input.parse::<Token![<]>()?;
+                    input.parse::<Token![/]>()?;
+                    let closing = Ident::parse_any(input)?;
+                    if closing != name {
+                        return Err(Error::new(
+                            closing.span(),
+                            format!("expected `</{}>`", name),
+                        ));
+                    }
+                    input.parse::<Token![>]>()?;
+                    break;
+                }
+                if input.is_empty() {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("missing the closing `</{}>`", name),
+                    ));
+                }
+                children.push(input.parse()?);
+            }
+        }
+
+        let element = Element {
+            name,
+            kind,
+            attrs,
+            children,
+        };
+        element.validate()?;
+        Ok(element)
+    }
+}
+
+impl Element {
+    /// check that the children fits in the builder function of the widget
+    fn validate(&self) -> Result<()> {
+        let name = &self.name;
+        match self.kind {
+            WidgetKind::Container => {
+                for child in self.children.iter() {
+                    if let Child::Text(lit) = child {
+                        return Err(Error::new(
+                            lit.span(),
+                            "text needs to be wrapped in a `<paragraph>`",
+                        ));
+                    }
+                }
+            }
+            WidgetKind::Leaf => {
+                if !self.children.is_empty() {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("`<{}>` can not have children", name),
+                    ));
+                }
+            }
+            WidgetKind::Text => {
+                if let Some(attr) = self.attrs.first() {
+                    return Err(Error::new(
+                        attr.name.span(),
+                        format!("`<{}>` has no attributes", name),
+                    ));
+                }
+                match self.children.as_slice() {
+                    [Child::Text(_)] | [Child::Node(_)] => (),
+                    _ => {
+                        return Err(Error::new(
+                            name.span(),
+                            format!(
+                                "`<{}>` expects a string literal or a `{{ expr }}` as its text",
+                                name
+                            ),
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Parse for Attribute {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = Ident::parse_any(input)?;
+        let attr_name = name.to_string();
+        if !widget_def::is_attribute(&attr_name)
+            && !widget_def::is_event(&attr_name)
+        {
+            return Err(Error::new(
+                name.span(),
+                format!("unknown attribute `{}`", name),
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        let tokens = input.step(|cursor| {
+            let mut rest = *cursor;
+            let mut tokens = TokenStream::new();
+            let mut prev_joint = false;
+            while let Some((tt, next)) = rest.token_tree() {
+                let lookahead: Vec<TokenTree> = next
+                    .token_tree()
+                    .map(|(next_tt, next2)| {
+                        let mut lookahead = vec![next_tt];
+                        lookahead.extend(next2.token_tree().map(|(tt, _)| tt));
+                        lookahead
+                    })
+                    .unwrap_or_default();
+                if is_value_end(&tt, &lookahead, prev_joint) {
+                    break;
+                }
+                prev_joint = match &tt {
+                    TokenTree::Punct(punct) => {
+                        punct.spacing() == Spacing::Joint
+                    }
+                    _ => false,
+                };
+                tokens.extend(Some(tt));
+                rest = next;
+            }
+            Ok((tokens, rest))
+        })?;
+        if tokens.is_empty() {
+            return Err(Error::new(
+                name.span(),
+                format!("expecting a value for `{}`", name),
+            ));
+        }
+        let value = unwrap_braces(syn::parse2(tokens)?);
+        Ok(Attribute { name, value })
+    }
+}
+
+/// `checked={count > 0}` is expanded as `checked(count > 0)`,
+/// to avoid the unnecessary braces warning
+fn unwrap_braces(value: Expr) -> Expr {
+    if let Expr::Block(block) = &value {
+        if let (None, [Stmt::Expr(expr)]) =
+            (&block.label, block.block.stmts.as_slice())
+        {
+            return expr.clone();
+        }
+    }
+    value
+}
+
+/// the attribute value ends at `>`, `/>` or when the next attribute `name=` starts.
+/// `->`, `=>`, `>=` and `==` are part of the value.
+fn is_value_end(
+    tt: &TokenTree,
+    lookahead: &[TokenTree],
+    prev_joint: bool,
+) -> bool {
+    let punct_at = |i: usize| match lookahead.get(i) {
+        Some(TokenTree::Punct(punct)) => Some(punct),
+        _ => None,
+    };
+    let next_punct = punct_at(0);
+    match tt {
+        TokenTree::Punct(punct) if punct.as_char() == '>' => {
+            let is_ge = punct.spacing() == Spacing::Joint
+                && next_punct.map(|p| p.as_char() == '=').unwrap_or(false);
+            !prev_joint && !is_ge
+        }
+        TokenTree::Punct(punct) if punct.as_char() == '/' => {
+            next_punct.map(|p| p.as_char() == '>').unwrap_or(false)
+        }
+        TokenTree::Ident(_) => match next_punct {
+            Some(p) if p.as_char() == '=' => {
+                p.spacing() == Spacing::Alone
+                    || punct_at(1)
+                        .map(|p| p.as_char() != '=' && p.as_char() != '>')
+                        .unwrap_or(true)
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+impl Parse for Child {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![<]) {
+            Ok(Child::Element(input.parse()?))
+        } else if input.peek(LitStr) {
+            Ok(Child::Text(input.parse()?))
+        } else if input.peek(Brace) {
+            let content;
+            braced!(content in input);
+            if content.peek(Token![for]) {
+                content.parse::<Token![for]>()?;
+                Ok(Child::Nodes(content.parse()?))
+            } else {
+                Ok(Child::Node(content.parse()?))
+            }
+        } else {
+            Err(input.error(
+                "expecting a widget, a string literal or a `{ expr }` block",
+            ))
+        }
+    }
+}
+
+impl ToTokens for Attribute {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = self.name.to_string();
+        let value = &self.value;
+        let expanded = if widget_def::is_event(&name) {
+            let func = &self.name;
+            quote_spanned!(func.span()=> ::sauron_native::widget::event::#func(#value))
+        } else {
+            let func =
+                Ident::new(widget_def::attribute_fn(&name), self.name.span());
+            quote_spanned!(func.span()=> ::sauron_native::widget::attribute::#func(#value))
+        };
+        tokens.extend(expanded);
+    }
+}
+
+impl ToTokens for Element {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = &self.name;
+        let attrs = &self.attrs;
+        let expanded = match self.kind {
+            WidgetKind::Container => {
+                let children = children_to_tokens(&self.children);
+                quote_spanned!(name.span()=>
+                    ::sauron_native::widget::#name(vec![#(#attrs),*], #children)
+                )
+            }
+            WidgetKind::Leaf => {
+                quote_spanned!(name.span()=>
+                    ::sauron_native::widget::#name(vec![#(#attrs),*])
+                )
+            }
+            WidgetKind::Text => {
+                let txt = match &self.children[0] {
+                    Child::Text(lit) => quote!(#lit),
+                    Child::Node(expr) => {
+                        quote!(::std::convert::AsRef::<str>::as_ref(&(#expr)))
+                    }
+                    _ => unreachable!("checked in validate"),
+                };
+                quote_spanned!(name.span()=> ::sauron_native::widget::#name(#txt))
+            }
+        };
+        tokens.extend(expanded);
+    }
+}
+
+/// the children is a `vec![]`, unless there are iterators to be spliced in
+fn children_to_tokens(children: &[Child]) -> TokenStream {
+    let has_nodes = children
+        .iter()
+        .any(|child| matches!(child, Child::Nodes(_)));
+    if has_nodes {
+        // the local is not visible to the expressions of the user,
+        // which may use a variable with the same name
+        let local = Ident::new("children", Span::mixed_site());
+        let stmts = children.iter().map(|child| match child {
+            Child::Element(element) => quote!(#local.push(#element);),
+            Child::Node(expr) => quote!(#local.push(#expr);),
+            Child::Nodes(expr) => quote!(#local.extend(#expr);),
+            Child::Text(_) => unreachable!("checked in validate"),
+        });
+        quote!({
+            let mut #local = ::std::vec::Vec::new();
+            #(#stmts)*
+            #local
+        })
+    } else {
+        let nodes = children.iter().map(|child| match child {
+            Child::Element(element) => quote!(#element),
+            Child::Node(expr) => quote!(#expr),
+            Child::Nodes(_) | Child::Text(_) => {
+                unreachable!("checked in validate")
+            }
+        });
+        quote!(vec![#(#nodes),*])
+    }
+}
@@ -0,0 +1,264 @@
+//! The widgets and attributes known to the `view!` macro.
+//! These needs to be kept in sync with the builder functions, the `declare_attr!`
+//! attribute functions and the event functions in sauron-widget.
+
+/// How the widget builder function is called
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum WidgetKind {
+    /// `column(attrs, children)`
+    Container,
+    /// `button(attrs)`
+    Leaf,
+    /// `paragraph(txt)`
+    Text,
+}
+
+const CONTAINERS: &[&str] = &[
+    "column",
+    "row",
+    "vpane",
+    "hpane",
+    "overlay",
+    "groupbox",
+    "header_bar",
+    "menu_bar",
+    "menu",
+    "menu_item",
+    "dialog",
+    "tab_box",
+];
+
+const LEAVES: &[&str] = &[
+    "button",
+    "text_input",
+    "checkbox",
+    "radio",
+    "image",
+    "svg",
+    "textarea",
+    "text_label",
+    "search_input",
+    "link",
+    "slider",
+    "progress_bar",
+    "spinner",
+    "combo_box",
+    "table",
+    "tree_view",
+    "canvas",
+];
+
+const ATTRIBUTES: &[&str] = &[
+    "value",
+    "data",
+    "label",
+    "svg_image",
+    "editable",
+    "scrollable",
+    "width",
+    "height",
+    "resizable",
+    "monospace",
+    "selectable",
+    "preformatted",
+    "name",
+    "uri",
+    "checked",
+    "placeholder",
+    "selected",
+    "min",
+    "max",
+    "step",
+    "orientation",
+    "fraction",
+    "indeterminate",
+    "options",
+    "columns",
+    "rows",
+    "sort_column",
+    "sort_descending",
+    "nodes",
+    "multi_select",
+    "scroll_top",
+    "overscan",
+    "commands",
+    "key",
+    "id",
+    "for_",
+];
+
+const EVENTS: &[&str] = &[
+    "on_click",
+    "on_mousedown",
+    "on_mouseup",
+    "on_mousemove",
+    "on_doubleclick",
+    "on_blur",
+    "on_input",
+    "on_keypress",
+    "on_enter",
+    "on_select",
+    "on_sort",
+    "on_cell_edit",
+    "on_expand",
+    "on_node_select",
+    "on_scroll",
+];
+
+/// returns how the widget is built, `None` if the widget is unknown
+pub(crate) fn widget_kind(name: &str) -> Option<WidgetKind> {
+    if CONTAINERS.contains(&name) {
+        Some(WidgetKind::Container)
+    } else if LEAVES.contains(&name) {
+        Some(WidgetKind::Leaf)
+    } else if name == "paragraph" {
+        Some(WidgetKind::Text)
+    } else {
+        None
+    }
+}
+
+/// returns the function name of the attribute, since `for` is a keyword,
+/// it is written as `for_`
+pub(crate) fn attribute_fn(name: &str) -> &str {
+    match name {
+        "for" => "for_",
+        _ => name,
+    }
+}
+
+pub(crate) fn is_attribute(name: &str) -> bool {
+    ATTRIBUTES.contains(&attribute_fn(name))
+}
+
+pub(crate) fn is_event(name: &str) -> bool {
+    EVENTS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proc_macro2::TokenTree;
+    use syn::{Item, Visibility};
+
+    const BUILDER: &str =
+        include_str!("../../sauron-widget/src/widget/builder.rs");
+    const ATTRIBUTE: &str =
+        include_str!("../../sauron-widget/src/widget/attribute.rs");
+    const EVENT: &str = include_str!("../../sauron-widget/src/widget/event.rs");
+
+    /// builder functions which are not called as `name(attrs)` or `name(attrs, children)`
+    const NOT_IN_MACRO: &[&str] = &[
+        "widget",
+        "virtual_list",
+        "custom",
+        "alert",
+        "confirm",
+        "prompt",
+    ];
+
+    /// the names of the `pub fn` in the source file
+    fn pub_fns(source: &str) -> Vec<String> {
+        let file = syn::parse_file(source).expect("must parse the source");
+        file.items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Fn(item_fn) => match item_fn.vis {
+                    Visibility::Public(_) => {
+                        Some(item_fn.sig.ident.to_string())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// the function names declared in the `declare_attr!` invocations,
+    /// which are the identifiers followed by `=>`
+    fn declared_attrs(source: &str) -> Vec<String> {
+        let file = syn::parse_file(source).expect("must parse the source");
+        let mut names = vec![];
+        for item in file.items.iter() {
+            if let Item::Macro(item_macro) = item {
+                if !item_macro.mac.path.is_ident("declare_attr") {
+                    continue;
+                }
+                let tokens: Vec<TokenTree> =
+                    item_macro.mac.tokens.clone().into_iter().collect();
+                for pair in tokens.windows(2) {
+                    if let (TokenTree::Ident(ident), TokenTree::Punct(punct)) =
+                        (&pair[0], &pair[1])
+                    {
+                        if punct.as_char() == '=' {
+                            names.push(ident.to_string());
+                        }
+                    }
+                }
+            }
+        }
+        names
+    }
+
+    #[test]
+    fn widgets_are_in_sync_with_the_builder() {
+        let builders = pub_fns(BUILDER);
+        for widget in CONTAINERS.iter().chain(LEAVES).chain(&["paragraph"]) {
+            assert!(
+                builders.iter().any(|name| name == widget),
+                "`{}` has no builder function in sauron-widget",
+                widget
+            );
+        }
+        for name in builders.iter() {
+            assert!(
+                widget_kind(name).is_some()
+                    || NOT_IN_MACRO.contains(&name.as_str()),
+                "the builder `{}` is missing in the view! macro",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn attributes_are_in_sync_with_declare_attr() {
+        let attrs = declared_attrs(ATTRIBUTE);
+        assert!(!attrs.is_empty(), "must find the declare_attr! entries");
+        for attr in ATTRIBUTES {
+            assert!(
+                attrs.iter().any(|name| name == attr),
+                "`{}` is not declared in sauron-widget",
+                attr
+            );
+        }
+        for name in attrs.iter() {
+            assert!(
+                is_attribute(name),
+                "the attribute `{}` is missing in the view! macro",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn events_are_in_sync_with_the_event_functions() {
+        let events: Vec<String> = pub_fns(EVENT)
+            .into_iter()
+            .filter(|name| name.starts_with("on_"))
+            .collect();
+        for event in EVENTS {
+            assert!(
+                events.iter().any(|name| name == event),
+                "`{}` has no event function in sauron-widget",
+                event
+            );
+        }
+        for name in events.iter() {
+            assert!(
+                is_event(name),
+                "the event `{}` is missing in the view! macro",
+                name
+            );
+        }
+    }
+}
@@ -0,0 +1,6 @@
+//! The unknown widgets and attributes are reported at the misspelled name
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
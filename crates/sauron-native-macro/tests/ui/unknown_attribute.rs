@@ -0,0 +1,7 @@
+use sauron_native_macro::view;
+
+fn main() {
+    let _ = view! {
+        <button lable="Hi"/>
+    };
+}
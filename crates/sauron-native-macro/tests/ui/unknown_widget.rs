@@ -0,0 +1,9 @@
+use sauron_native_macro::view;
+
+fn main() {
+    let _ = view! {
+        <column>
+            <buton label="Hi"/>
+        </column>
+    };
+}
@@ -17,6 +17,10 @@ pub mod attribute;
 mod builder;
 pub mod event;
 pub mod layout;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod snapshot;
+pub mod template;
 
 /// TODO: Each widget variant will need to have more details
 ///  such as attributes, that will be converted to their
@@ -26,6 +30,7 @@ pub mod layout;
 /// This will have a counterparts for each of the supported
 /// different platforms
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Widget {
     /// vertical flexbox
     Vbox,
@@ -58,6 +63,8 @@ pub enum Widget {
     Overlay,
     /// groupbox
     GroupBox,
+    /// displays one of its children at a time, each child is a tab titled with its label
+    TabBox,
     /// headerbar, such as in gtk where it can contains
     /// menu buttons
     HeaderBar,
@@ -71,6 +78,27 @@ pub enum Widget {
     SearchInput,
     /// Link button
     Link,
+    /// picks a number from a range of values
+    Slider,
+    /// displays the progress of an operation
+    ProgressBar,
+    /// an animation which shows that an operation is running
+    Spinner,
+    /// chooses one of its options, the chosen value can also be typed in when it is editable
+    ComboBox,
+    /// displays the rows of values in columns, the rows can be selected and sorted
+    Table,
+    /// displays nodes in a hierarchy, the nodes can be expanded and selected
+    TreeView,
+    /// a scrollable list of rows, where only the rows around the visible rows are its children
+    VirtualList,
+    /// draws its drawing commands, its mouse events are at the coordinates in the canvas
+    Canvas,
+    /// a modal dialog, the buttons in its children are the buttons of the dialog
+    Dialog,
+    /// a widget which is not part of this enum, identified by its name.
+    /// Each backend creates it using the custom widget registered with the same name
+    Custom(String),
 }
 
 impl Widget {
@@ -83,10 +111,14 @@ impl Widget {
             | Widget::Hpane
             | Widget::Overlay
             | Widget::GroupBox
+            | Widget::TabBox
             | Widget::HeaderBar
             | Widget::MenuBar
             | Widget::Menu
-            | Widget::MenuItem => true,
+            | Widget::MenuItem
+            | Widget::Dialog
+            | Widget::VirtualList
+            | Widget::Custom(_) => true,
 
             Widget::SearchInput
             | Widget::Button
@@ -98,6 +130,13 @@ impl Widget {
             | Widget::Image
             | Widget::Svg
             | Widget::Link
+            | Widget::Slider
+            | Widget::ProgressBar
+            | Widget::Spinner
+            | Widget::ComboBox
+            | Widget::Table
+            | Widget::TreeView
+            | Widget::Canvas
             | Widget::TextArea => false,
         }
     }
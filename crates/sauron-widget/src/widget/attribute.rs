@@ -2,12 +2,18 @@
 //!
 pub use super::event::Event;
 use crate::Attribute;
+pub use canvas::{Color, DrawCommand};
 use mt_dom::attr;
 use std::fmt;
 use stretch::style::Style;
+pub use table::{CellKind, TableColumn};
+pub use tree::{selected_ids, TreeNode};
 pub use util::{find_callback, find_value, get_style};
-pub use value::Value;
+pub use value::{Orientation, SelectOption, Value};
 
+mod canvas;
+mod table;
+mod tree;
 pub mod util;
 mod value;
 
@@ -35,6 +41,7 @@ macro_rules! declare_attr {
 /// These are attribute keys used in sauron-native, which will be translated to their
 /// corresponding backends
 #[derive(Clone, PartialEq, PartialOrd, Debug, Eq, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AttribKey {
     /// String, used in text_input
     Value,
@@ -80,6 +87,51 @@ pub enum AttribKey {
     Uri,
     /// Placeholder is used in text input, search input, and text_area
     Placeholder,
+    /// the index of the selected item, used in tab_box
+    Selected,
+    /// the lowest value, used in slider
+    Min,
+    /// the highest value, used in slider
+    Max,
+    /// the increment between the values, used in slider
+    Step,
+    /// Orientation enum, whether the widget is laid out horizontally or vertically,
+    /// used in slider
+    Orientation,
+    /// the completed fraction from 0.0 to 1.0, used in progress_bar
+    Fraction,
+    /// whether the progress is unknown, so the progress_bar is animated instead
+    Indeterminate,
+    /// the options to choose from, used in combo_box
+    Options,
+    /// the columns of a table
+    Columns,
+    /// the values of the cells in each row of a table
+    Rows,
+    /// the index of the column which the rows of a table are sorted by
+    SortColumn,
+    /// whether the rows of a table are sorted from the highest value
+    SortDescending,
+    /// the nodes at the top of a tree_view
+    Nodes,
+    /// whether more than one node can be selected, used in tree_view
+    MultiSelect,
+    /// the number of rows in a virtual_list
+    RowCount,
+    /// the height of each row in a virtual_list
+    RowHeight,
+    /// the offset of the visible rows from the top of the content,
+    /// used in virtual_list
+    ScrollTop,
+    /// the number of rows built before and after the visible rows of a virtual_list
+    Overscan,
+    /// the height of all the rows of a virtual_list, set by the virtual_list builder
+    ContentHeight,
+    /// the offset of the first built row from the top of the content,
+    /// set by the virtual_list builder
+    RowsOffset,
+    /// the drawing commands of a canvas
+    Commands,
 
     /// Events
     ClickEvent,
@@ -99,6 +151,16 @@ pub enum AttribKey {
     BlurEvent,
     /// activate event, used in on_enter
     Activate,
+    /// sort event, used in table
+    SortEvent,
+    /// cell edit event, used in table
+    CellEditEvent,
+    /// expand event, used in tree_view
+    ExpandEvent,
+    /// node select event, used in tree_view
+    NodeSelectEvent,
+    /// scroll event, used in virtual_list
+    ScrollEvent,
     /// For
     For,
 }
@@ -137,6 +199,40 @@ declare_attr! {
     checked => Checked;
     /// placeholder
     placeholder => Placeholder;
+    /// selected
+    selected => Selected;
+    /// min
+    min => Min;
+    /// max
+    max => Max;
+    /// step
+    step => Step;
+    /// orientation
+    orientation => Orientation;
+    /// fraction
+    fraction => Fraction;
+    /// indeterminate
+    indeterminate => Indeterminate;
+    /// options
+    options => Options;
+    /// columns
+    columns => Columns;
+    /// rows
+    rows => Rows;
+    /// sort_column
+    sort_column => SortColumn;
+    /// sort_descending
+    sort_descending => SortDescending;
+    /// nodes
+    nodes => Nodes;
+    /// multi_select
+    multi_select => MultiSelect;
+    /// scroll_top
+    scroll_top => ScrollTop;
+    /// overscan
+    overscan => Overscan;
+    /// commands
+    commands => Commands;
     /// key
     key => Key;
     /// id
@@ -0,0 +1,201 @@
+use std::fmt;
+
+/// A color, the red, green and blue are from 0 to 255 and the alpha is from 0.0 to 1.0
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Color {
+    /// red
+    pub r: u8,
+    /// green
+    pub g: u8,
+    /// blue
+    pub b: u8,
+    /// alpha, 0.0 is transparent
+    pub a: f64,
+}
+
+impl Color {
+    /// an opaque color
+    pub fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b, a: 1.0 }
+    }
+
+    /// a color with an alpha
+    pub fn rgba(r: u8, g: u8, b: u8, a: f64) -> Self {
+        Color { r, g, b, a }
+    }
+
+    /// the color in css notation, ie: `rgba(255,0,0,1)`
+    pub fn to_css(&self) -> String {
+        format!("rgba({},{},{},{})", self.r, self.g, self.b, self.a)
+    }
+}
+
+/// A command which draws in a canvas, the commands are drawn in order.
+/// The coordinates are from the top left of the canvas, the angles are in radians
+/// and increase clockwise
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DrawCommand {
+    /// discard the current path and start a new one
+    BeginPath,
+    /// start a new sub path at the point
+    MoveTo {
+        /// x
+        x: f64,
+        /// y
+        y: f64,
+    },
+    /// add a line from the current point to the point
+    LineTo {
+        /// x
+        x: f64,
+        /// y
+        y: f64,
+    },
+    /// add a cubic bezier curve from the current point to the point,
+    /// with the two control points
+    CurveTo {
+        /// x of the first control point
+        x1: f64,
+        /// y of the first control point
+        y1: f64,
+        /// x of the second control point
+        x2: f64,
+        /// y of the second control point
+        y2: f64,
+        /// x of the end point
+        x: f64,
+        /// y of the end point
+        y: f64,
+    },
+    /// add an arc around the center, from the start angle to the end angle.
+    /// A line is added from the current point to the start of the arc
+    Arc {
+        /// x of the center
+        x: f64,
+        /// y of the center
+        y: f64,
+        /// radius
+        radius: f64,
+        /// start angle
+        start_angle: f64,
+        /// end angle
+        end_angle: f64,
+    },
+    /// add a rectangle as a closed sub path
+    Rect {
+        /// x of the top left
+        x: f64,
+        /// y of the top left
+        y: f64,
+        /// width
+        width: f64,
+        /// height
+        height: f64,
+    },
+    /// add a line to the start of the current sub path
+    ClosePath,
+    /// set the color which fills the paths and the text
+    FillColor(Color),
+    /// set the color which strokes the paths
+    StrokeColor(Color),
+    /// set the width of the stroked lines
+    LineWidth(f64),
+    /// fill the current path with the fill color
+    Fill,
+    /// stroke the current path with the stroke color
+    Stroke,
+    /// draw the text filled with the fill color, the baseline of the text starts at the point
+    Text {
+        /// x
+        x: f64,
+        /// y of the baseline
+        y: f64,
+        /// the text
+        text: String,
+        /// the font size
+        size: f64,
+    },
+    /// draw the image from its bytes, scaled into the rectangle
+    Image {
+        /// x of the top left
+        x: f64,
+        /// y of the top left
+        y: f64,
+        /// width
+        width: f64,
+        /// height
+        height: f64,
+        /// the bytes of the image
+        data: Vec<u8>,
+    },
+}
+
+/// the command as it is written in a snapshot, ie: `move_to(10, 20)`
+impl fmt::Display for DrawCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DrawCommand::BeginPath => write!(f, "begin_path"),
+            DrawCommand::MoveTo { x, y } => write!(f, "move_to({}, {})", x, y),
+            DrawCommand::LineTo { x, y } => write!(f, "line_to({}, {})", x, y),
+            DrawCommand::CurveTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+            } => write!(
+                f,
+                "curve_to({}, {}, {}, {}, {}, {})",
+                x1, y1, x2, y2, x, y
+            ),
+            DrawCommand::Arc {
+                x,
+                y,
+                radius,
+                start_angle,
+                end_angle,
+            } => write!(
+                f,
+                "arc({}, {}, {}, {}, {})",
+                x, y, radius, start_angle, end_angle
+            ),
+            DrawCommand::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => write!(f, "rect({}, {}, {}, {})", x, y, width, height),
+            DrawCommand::ClosePath => write!(f, "close_path"),
+            DrawCommand::FillColor(color) => {
+                write!(f, "fill_color({})", color.to_css())
+            }
+            DrawCommand::StrokeColor(color) => {
+                write!(f, "stroke_color({})", color.to_css())
+            }
+            DrawCommand::LineWidth(width) => write!(f, "line_width({})", width),
+            DrawCommand::Fill => write!(f, "fill"),
+            DrawCommand::Stroke => write!(f, "stroke"),
+            DrawCommand::Text { x, y, text, size } => {
+                write!(f, "text({}, {}, {:?}, {})", x, y, text, size)
+            }
+            DrawCommand::Image {
+                x,
+                y,
+                width,
+                height,
+                data,
+            } => write!(
+                f,
+                "image({}, {}, {}, {}, <{} bytes>)",
+                x,
+                y,
+                width,
+                height,
+                data.len()
+            ),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+/// How the cells of a table column are displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CellKind {
+    /// the value of the cell is displayed as text
+    #[default]
+    Text,
+    /// a checkbox, which is checked with a bool value
+    Toggle,
+    /// a progress bar, which is filled with the fraction from 0.0 to 1.0
+    Progress,
+    /// an image, from the bytes of the value
+    Image,
+}
+
+/// Describes a column of a table
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableColumn {
+    /// the title in the header of the column
+    pub title: String,
+    /// the width of the column, the column fits its cells when there is none
+    pub width: Option<f64>,
+    /// whether pressing the header of the column emits the sort event
+    pub sortable: bool,
+    /// whether the cells of the column can be edited, which emits the cell edit event
+    pub editable: bool,
+    /// how the cells of the column are displayed
+    pub kind: CellKind,
+}
+
+impl TableColumn {
+    /// create a column of text cells with this title
+    pub fn new(title: &str) -> Self {
+        TableColumn {
+            title: title.to_string(),
+            width: None,
+            sortable: false,
+            editable: false,
+            kind: CellKind::Text,
+        }
+    }
+
+    /// set the width of the column
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// set whether the table can be sorted by this column
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+
+    /// set whether the cells of the column can be edited
+    pub fn editable(mut self, editable: bool) -> Self {
+        self.editable = editable;
+        self
+    }
+
+    /// set how the cells of the column are displayed
+    pub fn kind(mut self, kind: CellKind) -> Self {
+        self.kind = kind;
+        self
+    }
+}
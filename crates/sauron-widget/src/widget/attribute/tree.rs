@@ -0,0 +1,79 @@
+/// A node of a tree_view, which may have children nodes
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreeNode {
+    /// identifies the node in the events of the tree_view
+    pub id: String,
+    /// the text displayed for the node
+    pub label: String,
+    /// the bytes of the image displayed before the label
+    pub icon: Option<Vec<u8>>,
+    /// whether the children of the node are displayed
+    pub expanded: bool,
+    /// whether the node is selected
+    pub selected: bool,
+    /// whether the node can be expanded, the node has children which
+    /// may not be loaded yet
+    pub has_children: bool,
+    /// the loaded children of the node
+    pub children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    /// create a collapsed node without children
+    pub fn new(id: impl ToString, label: impl ToString) -> Self {
+        TreeNode {
+            id: id.to_string(),
+            label: label.to_string(),
+            icon: None,
+            expanded: false,
+            selected: false,
+            has_children: false,
+            children: vec![],
+        }
+    }
+
+    /// set the icon of the node from the bytes of an image
+    pub fn icon(mut self, bytes: Vec<u8>) -> Self {
+        self.icon = Some(bytes);
+        self
+    }
+
+    /// set whether the children of the node are displayed
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+
+    /// set whether the node is selected
+    pub fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// set whether the node can be expanded before its children are loaded.
+    /// Expanding the node emits the expand event, then the app loads the children
+    pub fn has_children(mut self, has_children: bool) -> Self {
+        self.has_children = has_children;
+        self
+    }
+
+    /// set the children of the node
+    pub fn children(mut self, children: Vec<TreeNode>) -> Self {
+        self.has_children = self.has_children || !children.is_empty();
+        self.children = children;
+        self
+    }
+}
+
+/// the ids of the selected nodes, in the order they are displayed
+pub fn selected_ids(nodes: &[TreeNode]) -> Vec<String> {
+    let mut ids = vec![];
+    for node in nodes {
+        if node.selected {
+            ids.push(node.id.clone());
+        }
+        ids.extend(selected_ids(&node.children));
+    }
+    ids
+}
@@ -1,11 +1,23 @@
 //! utility functions for manipulating attributes
 //!
 use crate::{
-    widget::attribute::AttribKey, Attribute, Callback, Element, Node, Value,
+    widget::attribute::{
+        AttribKey, DrawCommand, Orientation, SelectOption, TableColumn,
+        TreeNode,
+    },
+    Attribute, Callback, Element, Node, Value,
 };
+use std::ops::Range;
 use stretch::result::Layout;
 use stretch::style::Style;
 
+/// the number of visible rows of a virtual_list without a height
+const DEFAULT_VISIBLE_ROWS: usize = 20;
+
+/// the number of rows built before and after the visible rows of a virtual_list
+/// without an overscan
+const DEFAULT_OVERSCAN: usize = 3;
+
 /// find the value of the attribute key from a Vec of attributes
 pub fn find_value<MSG>(
     key: AttribKey,
@@ -17,8 +29,7 @@ where
     attrs
         .iter()
         .find(|att| *att.name() == key)
-        .map(|att| att.get_plain().first().copied())
-        .flatten()
+        .and_then(|att| att.get_plain().first().copied())
 }
 
 /// find the callback of the attribute key from a Vec of attributes
@@ -42,33 +53,254 @@ pub fn is_scrollable<MSG: 'static>(attrs: &[Attribute<MSG>]) -> bool {
         .unwrap_or(false)
 }
 
+/// find the min, max and step of the range, which is from 0 to 100 with a step of 1 by default
+pub fn find_range<MSG: 'static>(attrs: &[Attribute<MSG>]) -> (f64, f64, f64) {
+    let find_f64 = |key, default| {
+        find_value(key, attrs)
+            .and_then(|v| v.as_f64())
+            .unwrap_or(default)
+    };
+    (
+        find_f64(AttribKey::Min, 0.0),
+        find_f64(AttribKey::Max, 100.0),
+        find_f64(AttribKey::Step, 1.0),
+    )
+}
+
+/// find the RowCount and the RowHeight of a virtual_list,
+/// default is no rows with a height of 1
+pub fn find_row_geometry<MSG: 'static>(
+    attrs: &[Attribute<MSG>],
+) -> (usize, f64) {
+    let row_count = find_value(AttribKey::RowCount, attrs)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0);
+    let row_height = find_value(AttribKey::RowHeight, attrs)
+        .and_then(|v| v.as_f64())
+        .filter(|row_height| *row_height > 0.0)
+        .unwrap_or(1.0);
+    (row_count as usize, row_height)
+}
+
+/// find the ScrollTop attribute value, default is 0.0
+pub fn find_scroll_top<MSG: 'static>(attrs: &[Attribute<MSG>]) -> f64 {
+    find_value(AttribKey::ScrollTop, attrs)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0)
+        .max(0.0)
+}
+
+/// the rows of a virtual_list which are visible at its ScrollTop,
+/// as many rows as fit in its Height are visible
+pub fn find_visible_rows<MSG: 'static>(
+    attrs: &[Attribute<MSG>],
+) -> Range<usize> {
+    let (row_count, row_height) = find_row_geometry(attrs);
+    let visible_count = find_value(AttribKey::Height, attrs)
+        .and_then(|v| v.as_f64())
+        .map(|height| (height / row_height).ceil() as usize)
+        .unwrap_or(DEFAULT_VISIBLE_ROWS);
+    let first = ((find_scroll_top(attrs) / row_height).floor() as usize)
+        .min(row_count.saturating_sub(visible_count));
+    first..(first + visible_count).min(row_count)
+}
+
+/// the rows of a virtual_list which are built, these are the visible rows
+/// and the Overscan rows before and after them
+pub fn find_materialized_rows<MSG: 'static>(
+    attrs: &[Attribute<MSG>],
+) -> Range<usize> {
+    let (row_count, _) = find_row_geometry(attrs);
+    let overscan = find_value(AttribKey::Overscan, attrs)
+        .and_then(|v| v.as_f64())
+        .map(|overscan| overscan as usize)
+        .unwrap_or(DEFAULT_OVERSCAN);
+    let visible = find_visible_rows(attrs);
+    visible.start.saturating_sub(overscan)
+        ..(visible.end + overscan).min(row_count)
+}
+
+/// find the Orientation attribute value, default is horizontal
+pub fn find_orientation<MSG: 'static>(attrs: &[Attribute<MSG>]) -> Orientation {
+    find_value(AttribKey::Orientation, attrs)
+        .and_then(|v| v.as_orientation())
+        .unwrap_or_default()
+}
+
+/// find the Fraction attribute value clamped from 0.0 to 1.0, default is 0.0
+pub fn find_fraction<MSG: 'static>(attrs: &[Attribute<MSG>]) -> f64 {
+    find_value(AttribKey::Fraction, attrs)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0)
+        .clamp(0.0, 1.0)
+}
+
+/// find the options of the Options attribute, default is no options
+pub fn find_options<MSG: 'static>(attrs: &[Attribute<MSG>]) -> &[SelectOption] {
+    find_value(AttribKey::Options, attrs)
+        .and_then(|v| v.as_options())
+        .unwrap_or(&[])
+}
+
+/// find the columns of the Columns attribute, default is no columns
+pub fn find_columns<MSG: 'static>(attrs: &[Attribute<MSG>]) -> &[TableColumn] {
+    find_value(AttribKey::Columns, attrs)
+        .and_then(|v| v.as_columns())
+        .unwrap_or(&[])
+}
+
+/// find the rows of the Rows attribute, default is no rows
+pub fn find_rows<MSG: 'static>(attrs: &[Attribute<MSG>]) -> &[Vec<Value>] {
+    find_value(AttribKey::Rows, attrs)
+        .and_then(|v| v.as_rows())
+        .unwrap_or(&[])
+}
+
+/// find the nodes of the Nodes attribute, default is no nodes
+pub fn find_nodes<MSG: 'static>(attrs: &[Attribute<MSG>]) -> &[TreeNode] {
+    find_value(AttribKey::Nodes, attrs)
+        .and_then(|v| v.as_nodes())
+        .unwrap_or(&[])
+}
+
+/// find the drawing commands of the Commands attribute, default is no commands
+pub fn find_commands<MSG: 'static>(attrs: &[Attribute<MSG>]) -> &[DrawCommand] {
+    find_value(AttribKey::Commands, attrs)
+        .and_then(|v| v.as_commands())
+        .unwrap_or(&[])
+}
+
+/// find the MultiSelect attribute boolean value, default is false
+pub fn is_multi_select<MSG: 'static>(attrs: &[Attribute<MSG>]) -> bool {
+    find_value(AttribKey::MultiSelect, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// find the index of the SortColumn attribute and whether it is SortDescending
+pub fn find_sort<MSG: 'static>(
+    attrs: &[Attribute<MSG>],
+) -> Option<(usize, bool)> {
+    let sort_column =
+        find_value(AttribKey::SortColumn, attrs).and_then(|v| v.as_f64())?;
+    let descending = find_value(AttribKey::SortDescending, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(false);
+    Some((sort_column as usize, descending))
+}
+
+/// find the index of the Selected attribute
+pub fn find_selected<MSG: 'static>(attrs: &[Attribute<MSG>]) -> Option<usize> {
+    find_value(AttribKey::Selected, attrs)
+        .and_then(|v| v.as_f64())
+        .map(|selected| selected as usize)
+}
+
+/// the text displayed for the value of a table cell,
+/// values which are not text, number or bool are displayed as empty
+pub fn cell_text(value: &Value) -> String {
+    match value {
+        Value::String(_) | Value::Str(_) | Value::Bool(_) | Value::F64(_) => {
+            value.to_string()
+        }
+        _ => String::new(),
+    }
+}
+
+/// find the Indeterminate attribute boolean value, default is false
+pub fn is_indeterminate<MSG: 'static>(attrs: &[Attribute<MSG>]) -> bool {
+    find_value(AttribKey::Indeterminate, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
 /// return the first style attribute of this node
 pub fn get_style<MSG>(node: &Node<MSG>) -> Option<&Style> {
     node.get_attribute_value(&AttribKey::Style)
-        .map(|values| values.first().map(|value| value.as_style()))
-        .flatten()
-        .flatten()
+        .and_then(|values| values.first().and_then(|value| value.as_style()))
 }
 
 /// return the calculated layout of this element
 pub fn get_layout<MSG>(element: &Element<MSG>) -> Option<&Layout> {
     element
         .get_attribute_value(&AttribKey::Layout)
-        .map(|values| values.first().map(|value| value.as_layout()))
-        .flatten()
-        .flatten()
+        .and_then(|values| values.first().and_then(|value| value.as_layout()))
 }
 
 pub fn get_key<MSG>(element: &Element<MSG>) -> Option<&Value> {
     element
         .get_attribute_value(&AttribKey::Key)
-        .map(|values| values.first().map(|v| *v))
-        .flatten()
+        .and_then(|values| values.first().copied())
 }
 
 pub fn get_id<MSG>(element: &Element<MSG>) -> Option<&Value> {
     element
         .get_attribute_value(&AttribKey::Id)
-        .map(|values| values.first().map(|v| *v))
-        .flatten()
+        .and_then(|values| values.first().copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::attribute::{height, overscan, scroll_top};
+
+    fn list_attrs(
+        count: usize,
+        top: f64,
+        visible_height: Option<f64>,
+        rows_overscan: usize,
+    ) -> Vec<Attribute<()>> {
+        let mut attrs = vec![
+            mt_dom::attr(AttribKey::RowCount, Value::from(count as f64)),
+            mt_dom::attr(AttribKey::RowHeight, Value::from(10.0)),
+            scroll_top(top),
+            overscan(rows_overscan as f64),
+        ];
+        if let Some(visible_height) = visible_height {
+            attrs.push(height(visible_height));
+        }
+        attrs
+    }
+
+    #[test]
+    fn no_rows_are_visible_without_rows() {
+        let attrs = list_attrs(0, 50.0, Some(100.0), 3);
+        assert_eq!(find_visible_rows(&attrs), 0..0);
+        assert_eq!(find_materialized_rows(&attrs), 0..0);
+    }
+
+    #[test]
+    fn rows_fit_in_the_height() {
+        let attrs = list_attrs(100, 0.0, Some(45.0), 3);
+        assert_eq!(find_visible_rows(&attrs), 0..5);
+    }
+
+    #[test]
+    fn scroll_top_past_the_end_shows_the_last_page() {
+        let attrs = list_attrs(100, 5000.0, Some(100.0), 3);
+        assert_eq!(find_visible_rows(&attrs), 90..100);
+        assert_eq!(find_materialized_rows(&attrs), 87..100);
+    }
+
+    #[test]
+    fn overscan_is_clamped_at_both_ends() {
+        let attrs = list_attrs(100, 0.0, Some(100.0), 3);
+        assert_eq!(find_materialized_rows(&attrs), 0..13);
+
+        let attrs = list_attrs(100, 500.0, Some(100.0), 3);
+        assert_eq!(find_visible_rows(&attrs), 50..60);
+        assert_eq!(find_materialized_rows(&attrs), 47..63);
+
+        let attrs = list_attrs(12, 0.0, Some(100.0), 3);
+        assert_eq!(find_materialized_rows(&attrs), 0..12);
+    }
+
+    #[test]
+    fn default_visible_rows_without_a_height() {
+        let attrs = list_attrs(100, 0.0, None, 0);
+        assert_eq!(find_visible_rows(&attrs), 0..DEFAULT_VISIBLE_ROWS);
+
+        let attrs = list_attrs(5, 0.0, None, 0);
+        assert_eq!(find_visible_rows(&attrs), 0..5);
+    }
 }
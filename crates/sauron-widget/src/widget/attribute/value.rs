@@ -1,3 +1,4 @@
+use super::{DrawCommand, TableColumn, TreeNode};
 use std::fmt;
 use stretch::result::Layout;
 use stretch::style::PositionType;
@@ -20,10 +21,58 @@ pub enum Value {
     Layout(Layout),
     /// Position type of a widget
     PositionType(PositionType),
+    /// Orientation of a widget
+    Orientation(Orientation),
+    /// the options to choose from, used in combo_box
+    Options(Vec<SelectOption>),
+    /// the columns of a table
+    Columns(Vec<TableColumn>),
+    /// the values of the cells in each row of a table
+    Rows(Vec<Vec<Value>>),
+    /// the nodes of a tree_view
+    Nodes(Vec<TreeNode>),
+    /// the drawing commands of a canvas
+    Commands(Vec<DrawCommand>),
     /// float values
     F64(f64),
 }
 
+/// Whether the widget is laid out horizontally or vertically
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Orientation {
+    /// from left to right
+    Horizontal,
+    /// from top to bottom
+    Vertical,
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        Orientation::Horizontal
+    }
+}
+
+/// An option which can be chosen in a combo_box
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SelectOption {
+    /// the value of the input event when this option is chosen
+    pub value: String,
+    /// the text displayed for this option
+    pub label: String,
+}
+
+impl SelectOption {
+    /// create an option with the value and the label displayed for it
+    pub fn new(value: impl ToString, label: impl ToString) -> Self {
+        SelectOption {
+            value: value.to_string(),
+            label: label.to_string(),
+        }
+    }
+}
+
 impl Value {
     /// return the boolean value if it is a Bool variant
     pub fn as_bool(&self) -> bool {
@@ -81,6 +130,54 @@ impl Value {
             _ => None,
         }
     }
+
+    /// return the orientation if it is an Orientation variant
+    pub fn as_orientation(&self) -> Option<Orientation> {
+        match self {
+            Value::Orientation(orientation) => Some(*orientation),
+            _ => None,
+        }
+    }
+
+    /// return the options if it is an Options variant
+    pub fn as_options(&self) -> Option<&[SelectOption]> {
+        match self {
+            Value::Options(options) => Some(&options),
+            _ => None,
+        }
+    }
+
+    /// return the columns if it is a Columns variant
+    pub fn as_columns(&self) -> Option<&[TableColumn]> {
+        match self {
+            Value::Columns(columns) => Some(&columns),
+            _ => None,
+        }
+    }
+
+    /// return the rows if it is a Rows variant
+    pub fn as_rows(&self) -> Option<&[Vec<Value>]> {
+        match self {
+            Value::Rows(rows) => Some(&rows),
+            _ => None,
+        }
+    }
+
+    /// return the nodes if it is a Nodes variant
+    pub fn as_nodes(&self) -> Option<&[TreeNode]> {
+        match self {
+            Value::Nodes(nodes) => Some(&nodes),
+            _ => None,
+        }
+    }
+
+    /// return the drawing commands if it is a Commands variant
+    pub fn as_commands(&self) -> Option<&[DrawCommand]> {
+        match self {
+            Value::Commands(commands) => Some(&commands),
+            _ => None,
+        }
+    }
 }
 
 impl From<String> for Value {
@@ -125,6 +222,12 @@ impl From<u64> for Value {
     }
 }
 
+impl From<usize> for Value {
+    fn from(v: usize) -> Self {
+        Value::F64(v as f64)
+    }
+}
+
 impl From<i8> for Value {
     fn from(v: i8) -> Self {
         Value::F64(v as f64)
@@ -185,6 +288,42 @@ impl From<PositionType> for Value {
     }
 }
 
+impl From<Orientation> for Value {
+    fn from(v: Orientation) -> Self {
+        Value::Orientation(v)
+    }
+}
+
+impl From<Vec<SelectOption>> for Value {
+    fn from(v: Vec<SelectOption>) -> Self {
+        Value::Options(v)
+    }
+}
+
+impl From<Vec<TableColumn>> for Value {
+    fn from(v: Vec<TableColumn>) -> Self {
+        Value::Columns(v)
+    }
+}
+
+impl From<Vec<Vec<Value>>> for Value {
+    fn from(v: Vec<Vec<Value>>) -> Self {
+        Value::Rows(v)
+    }
+}
+
+impl From<Vec<TreeNode>> for Value {
+    fn from(v: Vec<TreeNode>) -> Self {
+        Value::Nodes(v)
+    }
+}
+
+impl From<Vec<DrawCommand>> for Value {
+    fn from(v: Vec<DrawCommand>) -> Self {
+        Value::Commands(v)
+    }
+}
+
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -192,7 +331,104 @@ impl fmt::Display for Value {
             Value::Str(s) => write!(f, "{}", s),
             Value::Bool(b) => write!(f, "{}", b),
             Value::F64(v) => write!(f, "{}", v),
-            _ => todo!(),
+            Value::Bytes(v) => write!(f, "<{} bytes>", v.len()),
+            Value::Style(v) => write!(f, "{:?}", v),
+            Value::Layout(v) => write!(f, "{:?}", v),
+            Value::PositionType(v) => write!(f, "{:?}", v),
+            Value::Orientation(v) => write!(f, "{:?}", v),
+            Value::Options(options) => {
+                let labels: Vec<&str> =
+                    options.iter().map(|opt| opt.label.as_str()).collect();
+                write!(f, "{}", labels.join(", "))
+            }
+            Value::Columns(columns) => {
+                let titles: Vec<&str> =
+                    columns.iter().map(|col| col.title.as_str()).collect();
+                write!(f, "{}", titles.join(", "))
+            }
+            Value::Rows(rows) => {
+                for (i, row) in rows.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    let cells: Vec<String> =
+                        row.iter().map(|cell| cell.to_string()).collect();
+                    write!(f, "[{}]", cells.join(", "))?;
+                }
+                Ok(())
+            }
+            Value::Nodes(nodes) => write_tree_nodes(f, nodes),
+            Value::Commands(commands) => {
+                let commands: Vec<String> =
+                    commands.iter().map(|cmd| cmd.to_string()).collect();
+                write!(f, "{}", commands.join(", "))
+            }
+        }
+    }
+}
+
+/// write the labels of the nodes, the loaded children follow their parent in brackets
+fn write_tree_nodes(f: &mut fmt::Formatter, nodes: &[TreeNode]) -> fmt::Result {
+    for (i, node) in nodes.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
         }
+        write!(f, "{}", node.label)?;
+        if !node.children.is_empty() {
+            write!(f, " [")?;
+            write_tree_nodes(f, &node.children)?;
+            write!(f, "]")?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attribute::Color;
+
+    #[test]
+    fn display_options_and_columns() {
+        let options = Value::from(vec![
+            SelectOption::new("r", "Red"),
+            SelectOption::new("g", "Green"),
+        ]);
+        assert_eq!(options.to_string(), "Red, Green");
+        let columns =
+            Value::from(vec![TableColumn::new("Id"), TableColumn::new("Name")]);
+        assert_eq!(columns.to_string(), "Id, Name");
+    }
+
+    #[test]
+    fn display_rows() {
+        let rows = Value::from(vec![
+            vec![Value::from(1), Value::from("Alice")],
+            vec![Value::from(2), Value::from("Bob")],
+        ]);
+        assert_eq!(rows.to_string(), "[1, Alice], [2, Bob]");
+    }
+
+    #[test]
+    fn display_nodes() {
+        let mut root = TreeNode::new("root", "Root");
+        root.children =
+            vec![TreeNode::new("a", "Child A"), TreeNode::new("b", "Child B")];
+        let nodes = Value::from(vec![root, TreeNode::new("other", "Other")]);
+        assert_eq!(nodes.to_string(), "Root [Child A, Child B], Other");
+    }
+
+    #[test]
+    fn display_commands() {
+        let commands = Value::from(vec![
+            DrawCommand::BeginPath,
+            DrawCommand::MoveTo { x: 1.0, y: 2.5 },
+            DrawCommand::FillColor(Color::rgb(255, 0, 0)),
+            DrawCommand::Fill,
+        ]);
+        assert_eq!(
+            commands.to_string(),
+            "begin_path, move_to(1, 2.5), fill_color(rgba(255,0,0,1)), fill"
+        );
     }
 }
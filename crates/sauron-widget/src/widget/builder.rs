@@ -1,6 +1,10 @@
 use crate::widget::attribute;
 use crate::widget::attribute::style;
-use crate::widget::attribute::util::find_value;
+use crate::widget::attribute::util::{
+    find_materialized_rows, find_orientation, find_value,
+};
+use crate::widget::attribute::Orientation;
+use crate::widget::event;
 use crate::Widget;
 use crate::{AttribKey, Attribute, Node, Value};
 use mt_dom::{attr, element};
@@ -276,6 +280,58 @@ where
     widget(Widget::GroupBox, attrs, children)
 }
 
+/// tab_box displays one of its children at a time, the `label` of each child is the title
+/// of its tab. The children are stacked on top of each other, so each of them takes the whole size
+/// of the tab_box. Use `selected` to set the active tab and `on_select` to be notified when
+/// the user switches to another tab
+pub fn tab_box<MSG>(
+    mut attrs: Vec<Attribute<MSG>>,
+    mut children: Vec<Node<MSG>>,
+) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width = find_value(AttribKey::Width, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+    let spec_height = find_value(AttribKey::Height, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .map(|w| w.as_position_type())
+        .flatten();
+
+    children.iter_mut().for_each(|child| {
+        child.add_attributes_ref_mut(vec![attr(
+            AttribKey::PositionType,
+            Value::from(PositionType::Absolute),
+        )]);
+    });
+
+    attrs.push(style(Style {
+        position_type: if let Some(spec_position) = spec_position {
+            spec_position
+        } else {
+            Default::default()
+        },
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::TabBox, attrs, children)
+}
+
 /// create a button
 pub fn button<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
 where
@@ -708,3 +764,465 @@ where
     }));
     widget(Widget::Link, attrs, vec![])
 }
+
+/// create a slider, which picks a number from `min` to `max` in increments of `step`.
+/// The slider fills the width of its parent, or the height when it is vertical
+pub fn slider<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width = find_value(AttribKey::Width, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+    let spec_height = find_value(AttribKey::Height, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .map(|w| w.as_position_type())
+        .flatten();
+
+    let orientation = find_orientation(&attrs);
+
+    attrs.push(style(Style {
+        position_type: if let Some(spec_position) = spec_position {
+            spec_position
+        } else {
+            Default::default()
+        },
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else if orientation == Orientation::Horizontal {
+                Dimension::Percent(1.0)
+            } else {
+                Dimension::Auto
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else if orientation == Orientation::Vertical {
+                Dimension::Percent(1.0)
+            } else {
+                Dimension::Auto
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::Slider, attrs, vec![])
+}
+
+/// create a progress bar, which is filled with the `fraction` and displays the `label`
+/// as its text. An `indeterminate` progress bar is animated by the backend
+pub fn progress_bar<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width = find_value(AttribKey::Width, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+    let spec_height = find_value(AttribKey::Height, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .map(|w| w.as_position_type())
+        .flatten();
+
+    attrs.push(style(Style {
+        position_type: if let Some(spec_position) = spec_position {
+            spec_position
+        } else {
+            Default::default()
+        },
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Auto
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::ProgressBar, attrs, vec![])
+}
+
+/// create a spinner, which is animated by the backend for as long as it is in the view
+pub fn spinner<MSG>(attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    widget(Widget::Spinner, attrs, vec![])
+}
+
+/// create a combo box, which chooses the `value` from its `options`.
+/// The value can also be typed in when the combo box is `editable`
+pub fn combo_box<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width = find_value(AttribKey::Width, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+    let spec_height = find_value(AttribKey::Height, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .map(|w| w.as_position_type())
+        .flatten();
+
+    attrs.push(style(Style {
+        position_type: if let Some(spec_position) = spec_position {
+            spec_position
+        } else {
+            Default::default()
+        },
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Auto
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::ComboBox, attrs, vec![])
+}
+
+/// create a table, which displays the `rows` in the `columns`.
+/// The app sorts the rows when a sortable column is pressed, the table only displays them
+pub fn table<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width = find_value(AttribKey::Width, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+    let spec_height = find_value(AttribKey::Height, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .map(|w| w.as_position_type())
+        .flatten();
+
+    attrs.push(style(Style {
+        position_type: if let Some(spec_position) = spec_position {
+            spec_position
+        } else {
+            Default::default()
+        },
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::Table, attrs, vec![])
+}
+
+/// create a tree view, which displays the `nodes` and the children of the expanded nodes.
+/// The app sets the expanded and selected nodes from the expand and node select events
+pub fn tree_view<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width = find_value(AttribKey::Width, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+    let spec_height = find_value(AttribKey::Height, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .map(|w| w.as_position_type())
+        .flatten();
+
+    attrs.push(style(Style {
+        position_type: if let Some(spec_position) = spec_position {
+            spec_position
+        } else {
+            Default::default()
+        },
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::TreeView, attrs, vec![])
+}
+
+/// create a virtual list of `row_count` rows, which are each `row_height` tall.
+/// Only the rows visible at its `scroll_top` and the `overscan` rows around them
+/// are built with `row_builder`, these are the children of the list.
+/// The list emits the scroll event when it is scrolled, then the app sets its `scroll_top`
+pub fn virtual_list<MSG, F>(
+    mut attrs: Vec<Attribute<MSG>>,
+    row_count: usize,
+    row_height: f64,
+    row_builder: F,
+) -> Node<MSG>
+where
+    MSG: 'static,
+    F: Fn(usize) -> Node<MSG>,
+{
+    attrs.push(attr(AttribKey::RowCount, Value::from(row_count)));
+    attrs.push(attr(AttribKey::RowHeight, Value::from(row_height)));
+    let rows = find_materialized_rows(&attrs);
+    attrs.push(attr(
+        AttribKey::ContentHeight,
+        Value::from(row_count as f64 * row_height),
+    ));
+    attrs.push(attr(
+        AttribKey::RowsOffset,
+        Value::from(rows.start as f64 * row_height),
+    ));
+
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .and_then(|w| w.as_position_type());
+
+    attrs.push(style(Style {
+        position_type: if let Some(spec_position) = spec_position {
+            spec_position
+        } else {
+            Default::default()
+        },
+        flex_direction: FlexDirection::Column,
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    let children = rows.map(row_builder).collect();
+    widget(Widget::VirtualList, attrs, children)
+}
+
+/// create a canvas, which draws its `commands` in order.
+/// The mouse events of the canvas are at the coordinates from its top left
+pub fn canvas<MSG>(mut attrs: Vec<Attribute<MSG>>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width =
+        find_value(AttribKey::Width, &attrs).and_then(|w| w.as_f64());
+    let spec_height =
+        find_value(AttribKey::Height, &attrs).and_then(|w| w.as_f64());
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .and_then(|w| w.as_position_type());
+
+    attrs.push(style(Style {
+        position_type: if let Some(spec_position) = spec_position {
+            spec_position
+        } else {
+            Default::default()
+        },
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::Canvas, attrs, vec![])
+}
+
+/// create a custom widget, which is created by the custom widget
+/// registered with the same name in the backend
+pub fn custom<MSG>(
+    name: &str,
+    mut attrs: Vec<Attribute<MSG>>,
+    children: Vec<Node<MSG>>,
+) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width = find_value(AttribKey::Width, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+    let spec_height = find_value(AttribKey::Height, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+
+    let spec_position = find_value(AttribKey::PositionType, &attrs)
+        .map(|w| w.as_position_type())
+        .flatten();
+
+    attrs.push(style(Style {
+        position_type: if let Some(spec_position) = spec_position {
+            spec_position
+        } else {
+            Default::default()
+        },
+        size: Size {
+            width: if let Some(width) = spec_width {
+                Dimension::Points(width as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+            height: if let Some(height) = spec_height {
+                Dimension::Points(height as f32)
+            } else {
+                Dimension::Percent(1.0)
+            },
+        },
+        ..Default::default()
+    }));
+    widget(Widget::Custom(name.to_string()), attrs, children)
+}
+
+/// create a modal dialog, the `label` attribute is used as the title.
+/// The buttons in the children are the buttons of the dialog,
+/// while the rest of the children are the body of the dialog
+pub fn dialog<MSG>(
+    mut attrs: Vec<Attribute<MSG>>,
+    children: Vec<Node<MSG>>,
+) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let spec_width = find_value(AttribKey::Width, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+    let spec_height = find_value(AttribKey::Height, &attrs)
+        .map(|w| w.as_f64())
+        .flatten();
+
+    // the dialog is on top of the other widgets, so it takes no space in its parent
+    attrs.push(style(Style {
+        flex_direction: FlexDirection::Column,
+        position_type: PositionType::Absolute,
+        size: Size {
+            width: Dimension::Points(spec_width.unwrap_or(400.0) as f32),
+            height: Dimension::Points(spec_height.unwrap_or(200.0) as f32),
+        },
+        ..Default::default()
+    }));
+    widget(Widget::Dialog, attrs, children)
+}
+
+/// a dialog which displays a message, `on_ok` is emitted when the ok button is clicked
+pub fn alert<MSG>(title: &str, message: &str, on_ok: MSG) -> Node<MSG>
+where
+    MSG: Clone + 'static,
+{
+    dialog(
+        vec![attribute::label(title.to_string())],
+        vec![
+            paragraph(message),
+            button(vec![
+                attribute::label("OK"),
+                event::on_click(move |_| on_ok.clone()),
+            ]),
+        ],
+    )
+}
+
+/// a dialog which asks the user to confirm,
+/// `on_confirm` or `on_cancel` is emitted depending on the button clicked
+pub fn confirm<MSG>(
+    title: &str,
+    message: &str,
+    on_confirm: MSG,
+    on_cancel: MSG,
+) -> Node<MSG>
+where
+    MSG: Clone + 'static,
+{
+    dialog(
+        vec![attribute::label(title.to_string())],
+        vec![
+            paragraph(message),
+            button(vec![
+                attribute::label("Cancel"),
+                event::on_click(move |_| on_cancel.clone()),
+            ]),
+            button(vec![
+                attribute::label("OK"),
+                event::on_click(move |_| on_confirm.clone()),
+            ]),
+        ],
+    )
+}
+
+/// a dialog which asks the user to enter a value, the value is passed to `on_input`
+/// as the user types, then `on_ok` or `on_cancel` is emitted depending on the button clicked
+pub fn prompt<MSG, F>(
+    title: &str,
+    message: &str,
+    value: &str,
+    on_input: F,
+    on_ok: MSG,
+    on_cancel: MSG,
+) -> Node<MSG>
+where
+    MSG: Clone + 'static,
+    F: Fn(String) -> MSG + 'static,
+{
+    dialog(
+        vec![attribute::label(title.to_string())],
+        vec![
+            paragraph(message),
+            text_input(vec![
+                attribute::value(value.to_string()),
+                event::on_input(move |input| on_input(input.value.to_string())),
+            ]),
+            button(vec![
+                attribute::label("Cancel"),
+                event::on_click(move |_| on_cancel.clone()),
+            ]),
+            button(vec![
+                attribute::label("OK"),
+                event::on_click(move |_| on_ok.clone()),
+            ]),
+        ],
+    )
+}
@@ -15,6 +15,16 @@ pub enum Event {
     KeyEvent(KeyEvent),
     /// Events in text_area or text_input
     InputEvent(InputEvent),
+    /// the header of a sortable column in a table is pressed
+    SortEvent(SortEvent),
+    /// a cell of an editable column in a table is edited
+    CellEditEvent(CellEditEvent),
+    /// a node of a tree_view is expanded or collapsed
+    ExpandEvent(ExpandEvent),
+    /// the selected nodes of a tree_view are changed
+    NodeSelectEvent(NodeSelectEvent),
+    /// a virtual_list is scrolled
+    ScrollEvent(ScrollEvent),
     /// resize event
     Resize(f32, f32),
 }
@@ -37,6 +47,36 @@ impl From<KeyEvent> for Event {
     }
 }
 
+impl From<SortEvent> for Event {
+    fn from(se: SortEvent) -> Self {
+        Event::SortEvent(se)
+    }
+}
+
+impl From<CellEditEvent> for Event {
+    fn from(ce: CellEditEvent) -> Self {
+        Event::CellEditEvent(ce)
+    }
+}
+
+impl From<ExpandEvent> for Event {
+    fn from(ee: ExpandEvent) -> Self {
+        Event::ExpandEvent(ee)
+    }
+}
+
+impl From<NodeSelectEvent> for Event {
+    fn from(ne: NodeSelectEvent) -> Self {
+        Event::NodeSelectEvent(ne)
+    }
+}
+
+impl From<ScrollEvent> for Event {
+    fn from(se: ScrollEvent) -> Self {
+        Event::ScrollEvent(se)
+    }
+}
+
 /// A mouse event contains the (x,y) coordinates, buttons and modifier keys
 #[derive(Default, Debug, Copy, Clone, PartialEq)]
 pub struct MouseEvent {
@@ -182,6 +222,123 @@ impl From<Value> for InputEvent {
     }
 }
 
+/// Sort event is triggered by pressing the header of a sortable column in a table.
+/// The app sorts the rows, then sets the `sort_column` and `sort_descending` of the table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortEvent {
+    /// the index of the column to sort the rows by
+    pub column: usize,
+    /// whether the rows are sorted from the highest value
+    pub descending: bool,
+}
+
+impl SortEvent {
+    /// the sort when the header of the column is pressed, the rows are sorted from the
+    /// lowest value first, pressing the header of the sorted column again reverses the order
+    pub fn pressed(column: usize, current: Option<(usize, bool)>) -> Self {
+        let descending = match current {
+            Some((sort_column, descending)) if sort_column == column => {
+                !descending
+            }
+            _ => false,
+        };
+        SortEvent { column, descending }
+    }
+}
+
+/// Cell edit event is triggered when a cell in an editable column of a table is edited
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellEditEvent {
+    /// the index of the row of the cell
+    pub row: usize,
+    /// the index of the column of the cell
+    pub column: usize,
+    /// the new value of the cell
+    pub value: Value,
+}
+
+impl CellEditEvent {
+    /// creates a cell edit event
+    pub fn new<V: Into<Value>>(row: usize, column: usize, value: V) -> Self {
+        CellEditEvent {
+            row,
+            column,
+            value: value.into(),
+        }
+    }
+}
+
+/// Expand event is triggered when a node of a tree_view is expanded or collapsed.
+/// The app sets the `expanded` of the node, and loads its children if they are not loaded yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpandEvent {
+    /// the id of the node
+    pub id: String,
+    /// whether the node is expanded, otherwise it is collapsed
+    pub expanded: bool,
+}
+
+impl ExpandEvent {
+    /// creates an expand event
+    pub fn new(id: impl ToString, expanded: bool) -> Self {
+        ExpandEvent {
+            id: id.to_string(),
+            expanded,
+        }
+    }
+}
+
+/// Node select event is triggered when the selected nodes of a tree_view are changed.
+/// The app sets the `selected` of the nodes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeSelectEvent {
+    /// the ids of all the selected nodes
+    pub ids: Vec<String>,
+}
+
+impl NodeSelectEvent {
+    /// creates a node select event
+    pub fn new(ids: Vec<String>) -> Self {
+        NodeSelectEvent { ids }
+    }
+
+    /// the selection when the node is pressed, the node is toggled in a multi select
+    /// tree_view, otherwise it is the only selected node
+    pub fn pressed(id: &str, current: &[String], multi_select: bool) -> Self {
+        let ids = if !multi_select {
+            vec![id.to_string()]
+        } else if current.iter().any(|selected| selected == id) {
+            current
+                .iter()
+                .filter(|selected| *selected != id)
+                .cloned()
+                .collect()
+        } else {
+            current
+                .iter()
+                .cloned()
+                .chain(Some(id.to_string()))
+                .collect()
+        };
+        NodeSelectEvent { ids }
+    }
+}
+
+/// Scroll event is triggered when a virtual_list is scrolled.
+/// The app sets the `scroll_top` of the list, which builds the rows visible there
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollEvent {
+    /// the offset of the visible rows from the top of the content
+    pub scroll_top: f64,
+}
+
+impl ScrollEvent {
+    /// creates a scroll event
+    pub fn new(scroll_top: f64) -> Self {
+        ScrollEvent { scroll_top }
+    }
+}
+
 /// Which mouse button is used
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MouseButton {
@@ -378,6 +535,78 @@ where
     })
 }
 
+/// create an attribute which attach a callback to the change of the selected item,
+/// such as the active tab of a tab_box
+pub fn on_select<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(usize) -> MSG + 'static,
+{
+    on(AttribKey::InputEvent, move |ev: Event| match ev {
+        Event::InputEvent(input) => {
+            func(input.value.as_f64().unwrap_or(0.0) as usize)
+        }
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the pressed header of a sortable column
+/// in a table
+pub fn on_sort<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(SortEvent) -> MSG + 'static,
+{
+    on(AttribKey::SortEvent, move |ev: Event| match ev {
+        Event::SortEvent(se) => func(se),
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the edited cells of a table
+pub fn on_cell_edit<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(CellEditEvent) -> MSG + 'static,
+{
+    on(AttribKey::CellEditEvent, move |ev: Event| match ev {
+        Event::CellEditEvent(ce) => func(ce),
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the expanded and collapsed nodes
+/// of a tree_view
+pub fn on_expand<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(ExpandEvent) -> MSG + 'static,
+{
+    on(AttribKey::ExpandEvent, move |ev: Event| match ev {
+        Event::ExpandEvent(ee) => func(ee),
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the change of the selected nodes
+/// of a tree_view
+pub fn on_node_select<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(NodeSelectEvent) -> MSG + 'static,
+{
+    on(AttribKey::NodeSelectEvent, move |ev: Event| match ev {
+        Event::NodeSelectEvent(ne) => func(ne),
+        _ => unreachable!(),
+    })
+}
+
+/// create an attribute which attach a callback to the scrolling of a virtual_list
+pub fn on_scroll<F, MSG>(func: F) -> Attribute<MSG>
+where
+    F: Fn(ScrollEvent) -> MSG + 'static,
+{
+    on(AttribKey::ScrollEvent, move |ev: Event| match ev {
+        Event::ScrollEvent(se) => func(se),
+        _ => unreachable!(),
+    })
+}
+
 pub fn on_keypress<F, MSG>(func: F) -> Attribute<MSG>
 where
     F: Fn(KeyEvent) -> MSG + 'static,
@@ -0,0 +1,635 @@
+//! Serialization of widget trees and attribute values, enabled with the `serde` feature.
+//!
+//! `Widget`, `AttribKey`, `Orientation`, `SelectOption`, `TableColumn`, `TreeNode`
+//! and `DrawCommand` derive the serde traits, while `Value` is encoded through
+//! mirror types since `Value::Style` and `Value::Layout` wrap stretch types.
+//! Node trees are converted into a `NodeDef`, which is a copy of the tree
+//! without the callbacks.
+//!
+//! The `Layout` of stretch can only be created by a layout pass, so a layout is
+//! serialized but can not be deserialized. The calculated layouts are left out of
+//! the `NodeDef`, these are calculated again with `compute_node_layout`.
+use crate::{
+    widget::attribute::{
+        DrawCommand, Orientation, SelectOption, TableColumn, TreeNode,
+    },
+    AttribKey, Node, Value, Widget,
+};
+use mt_dom::{attr, element, text};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+use stretch::{
+    geometry::{Rect, Size},
+    number::Number,
+    result::Layout,
+    style::{
+        AlignContent, AlignItems, AlignSelf, Dimension, Direction, Display,
+        FlexDirection, FlexWrap, JustifyContent, Overflow, PositionType, Style,
+    },
+};
+
+/// A serializable copy of a node tree, the callbacks and the calculated layouts are left out
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum NodeDef {
+    /// a widget element
+    Element {
+        /// the widget
+        tag: Widget,
+        /// the plain attributes of the widget
+        attrs: Vec<AttributeDef>,
+        /// the child nodes
+        children: Vec<NodeDef>,
+    },
+    /// a text node
+    Text(String),
+}
+
+/// A serializable copy of an attribute, only the plain values are kept
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AttributeDef {
+    /// the attribute key
+    pub name: AttribKey,
+    /// the plain values of the attribute
+    pub values: Vec<Value>,
+}
+
+impl<MSG> From<&Node<MSG>> for NodeDef {
+    fn from(node: &Node<MSG>) -> Self {
+        match node {
+            Node::Element(element) => NodeDef::Element {
+                tag: element.tag.clone(),
+                attrs: element
+                    .get_attributes()
+                    .iter()
+                    .filter(|att| {
+                        *att.name() != AttribKey::Layout
+                            && !att.get_plain().is_empty()
+                    })
+                    .map(|att| AttributeDef {
+                        name: att.name().clone(),
+                        values: att.get_plain().into_iter().cloned().collect(),
+                    })
+                    .collect(),
+                children: element
+                    .get_children()
+                    .iter()
+                    .map(NodeDef::from)
+                    .collect(),
+            },
+            Node::Text(txt) => NodeDef::Text(txt.text.clone()),
+        }
+    }
+}
+
+impl NodeDef {
+    /// convert back into a node tree, which has no callbacks
+    pub fn into_node<MSG>(self) -> Node<MSG> {
+        match self {
+            NodeDef::Element {
+                tag,
+                attrs,
+                children,
+            } => element(
+                tag,
+                attrs
+                    .into_iter()
+                    .flat_map(|att| {
+                        let name = att.name;
+                        att.values
+                            .into_iter()
+                            .map(move |value| attr(name.clone(), value))
+                    })
+                    .collect(),
+                children.into_iter().map(NodeDef::into_node).collect(),
+            ),
+            NodeDef::Text(txt) => text(txt),
+        }
+    }
+}
+
+/// serialize the node tree, the callbacks are left out
+pub fn serialize_node<MSG, S>(
+    node: &Node<MSG>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    NodeDef::from(node).serialize(serializer)
+}
+
+/// deserialize a node tree
+pub fn deserialize_node<'de, MSG, D>(
+    deserializer: D,
+) -> Result<Node<MSG>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    NodeDef::deserialize(deserializer).map(NodeDef::into_node)
+}
+
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ValueDef::from(self).serialize(serializer)
+    }
+}
+
+/// Note: `Value::Str` is deserialized as `Value::String`,
+/// and a `Value::Layout` can not be deserialized
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ValueDef::deserialize(deserializer)
+            .and_then(|v| Value::try_from(v).map_err(de::Error::custom))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+enum ValueDef {
+    String(String),
+    Bool(bool),
+    Bytes(Vec<u8>),
+    Style(StyleDef),
+    Layout(LayoutDef),
+    PositionType(PositionTypeDef),
+    Orientation(Orientation),
+    Options(Vec<SelectOption>),
+    Columns(Vec<TableColumn>),
+    Rows(Vec<Vec<ValueDef>>),
+    Nodes(Vec<TreeNode>),
+    Commands(Vec<DrawCommand>),
+    F64(f64),
+}
+
+impl From<&Value> for ValueDef {
+    fn from(v: &Value) -> Self {
+        match v {
+            Value::String(v) => ValueDef::String(v.clone()),
+            Value::Str(v) => ValueDef::String(v.to_string()),
+            Value::Bool(v) => ValueDef::Bool(*v),
+            Value::Bytes(v) => ValueDef::Bytes(v.clone()),
+            Value::Style(v) => ValueDef::Style(StyleDef::from(v)),
+            Value::Layout(v) => ValueDef::Layout(LayoutDef::from(v)),
+            Value::PositionType(v) => ValueDef::PositionType((*v).into()),
+            Value::Orientation(v) => ValueDef::Orientation(*v),
+            Value::Options(v) => ValueDef::Options(v.clone()),
+            Value::Columns(v) => ValueDef::Columns(v.clone()),
+            Value::Rows(v) => ValueDef::Rows(
+                v.iter()
+                    .map(|row| row.iter().map(ValueDef::from).collect())
+                    .collect(),
+            ),
+            Value::Nodes(v) => ValueDef::Nodes(v.clone()),
+            Value::Commands(v) => ValueDef::Commands(v.clone()),
+            Value::F64(v) => ValueDef::F64(*v),
+        }
+    }
+}
+
+impl TryFrom<ValueDef> for Value {
+    type Error = &'static str;
+
+    fn try_from(v: ValueDef) -> Result<Self, Self::Error> {
+        Ok(match v {
+            ValueDef::String(v) => Value::String(v),
+            ValueDef::Bool(v) => Value::Bool(v),
+            ValueDef::Bytes(v) => Value::Bytes(v),
+            ValueDef::Style(v) => Value::Style(v.into()),
+            ValueDef::Layout(_) => {
+                return Err("a layout can only be created by a layout pass")
+            }
+            ValueDef::PositionType(v) => Value::PositionType(v.into()),
+            ValueDef::Orientation(v) => Value::Orientation(v),
+            ValueDef::Options(v) => Value::Options(v),
+            ValueDef::Columns(v) => Value::Columns(v),
+            ValueDef::Rows(v) => Value::Rows(
+                v.into_iter()
+                    .map(|row| {
+                        row.into_iter()
+                            .map(Value::try_from)
+                            .collect::<Result<_, _>>()
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            ValueDef::Nodes(v) => Value::Nodes(v),
+            ValueDef::Commands(v) => Value::Commands(v),
+            ValueDef::F64(v) => Value::F64(v),
+        })
+    }
+}
+
+/// declare a serializable mirror of a fieldless stretch enum
+macro_rules! mirror_enum {
+    (
+        $(
+            $def:ident => $remote:ident { $($variant:ident),* $(,)? }
+        )*
+    ) => {
+        $(
+            // the variants have the same names as in stretch
+            #[allow(clippy::upper_case_acronyms)]
+            #[derive(Serialize, Deserialize)]
+            enum $def {
+                $($variant,)*
+            }
+
+            impl From<$remote> for $def {
+                fn from(v: $remote) -> Self {
+                    match v {
+                        $($remote::$variant => $def::$variant,)*
+                    }
+                }
+            }
+
+            impl From<$def> for $remote {
+                fn from(v: $def) -> Self {
+                    match v {
+                        $($def::$variant => $remote::$variant,)*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+mirror_enum! {
+    AlignItemsDef => AlignItems {
+        FlexStart, FlexEnd, Center, Baseline, Stretch,
+    }
+    AlignSelfDef => AlignSelf {
+        Auto, FlexStart, FlexEnd, Center, Baseline, Stretch,
+    }
+    AlignContentDef => AlignContent {
+        FlexStart, FlexEnd, Center, Stretch, SpaceBetween, SpaceAround,
+    }
+    DirectionDef => Direction {
+        Inherit, LTR, RTL,
+    }
+    DisplayDef => Display {
+        Flex, None,
+    }
+    FlexDirectionDef => FlexDirection {
+        Row, Column, RowReverse, ColumnReverse,
+    }
+    JustifyContentDef => JustifyContent {
+        FlexStart, FlexEnd, Center, SpaceBetween, SpaceAround, SpaceEvenly,
+    }
+    OverflowDef => Overflow {
+        Visible, Hidden, Scroll,
+    }
+    PositionTypeDef => PositionType {
+        Relative, Absolute,
+    }
+    FlexWrapDef => FlexWrap {
+        NoWrap, Wrap, WrapReverse,
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+enum DimensionDef {
+    Undefined,
+    Auto,
+    Points(f32),
+    Percent(f32),
+}
+
+impl From<Dimension> for DimensionDef {
+    fn from(v: Dimension) -> Self {
+        match v {
+            Dimension::Undefined => DimensionDef::Undefined,
+            Dimension::Auto => DimensionDef::Auto,
+            Dimension::Points(v) => DimensionDef::Points(v),
+            Dimension::Percent(v) => DimensionDef::Percent(v),
+        }
+    }
+}
+
+impl From<DimensionDef> for Dimension {
+    fn from(v: DimensionDef) -> Self {
+        match v {
+            DimensionDef::Undefined => Dimension::Undefined,
+            DimensionDef::Auto => Dimension::Auto,
+            DimensionDef::Points(v) => Dimension::Points(v),
+            DimensionDef::Percent(v) => Dimension::Percent(v),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+enum NumberDef {
+    Defined(f32),
+    Undefined,
+}
+
+impl From<Number> for NumberDef {
+    fn from(v: Number) -> Self {
+        match v {
+            Number::Defined(v) => NumberDef::Defined(v),
+            Number::Undefined => NumberDef::Undefined,
+        }
+    }
+}
+
+impl From<NumberDef> for Number {
+    fn from(v: NumberDef) -> Self {
+        match v {
+            NumberDef::Defined(v) => Number::Defined(v),
+            NumberDef::Undefined => Number::Undefined,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RectDef {
+    start: DimensionDef,
+    end: DimensionDef,
+    top: DimensionDef,
+    bottom: DimensionDef,
+}
+
+impl From<Rect<Dimension>> for RectDef {
+    fn from(v: Rect<Dimension>) -> Self {
+        RectDef {
+            start: v.start.into(),
+            end: v.end.into(),
+            top: v.top.into(),
+            bottom: v.bottom.into(),
+        }
+    }
+}
+
+impl From<RectDef> for Rect<Dimension> {
+    fn from(v: RectDef) -> Self {
+        Rect {
+            start: v.start.into(),
+            end: v.end.into(),
+            top: v.top.into(),
+            bottom: v.bottom.into(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SizeDef {
+    width: DimensionDef,
+    height: DimensionDef,
+}
+
+impl From<Size<Dimension>> for SizeDef {
+    fn from(v: Size<Dimension>) -> Self {
+        SizeDef {
+            width: v.width.into(),
+            height: v.height.into(),
+        }
+    }
+}
+
+impl From<SizeDef> for Size<Dimension> {
+    fn from(v: SizeDef) -> Self {
+        Size {
+            width: v.width.into(),
+            height: v.height.into(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StyleDef {
+    display: DisplayDef,
+    position_type: PositionTypeDef,
+    direction: DirectionDef,
+    flex_direction: FlexDirectionDef,
+    flex_wrap: FlexWrapDef,
+    overflow: OverflowDef,
+    align_items: AlignItemsDef,
+    align_self: AlignSelfDef,
+    align_content: AlignContentDef,
+    justify_content: JustifyContentDef,
+    position: RectDef,
+    margin: RectDef,
+    padding: RectDef,
+    border: RectDef,
+    flex_grow: f32,
+    flex_shrink: f32,
+    flex_basis: DimensionDef,
+    size: SizeDef,
+    min_size: SizeDef,
+    max_size: SizeDef,
+    aspect_ratio: NumberDef,
+}
+
+impl From<&Style> for StyleDef {
+    fn from(v: &Style) -> Self {
+        StyleDef {
+            display: v.display.into(),
+            position_type: v.position_type.into(),
+            direction: v.direction.into(),
+            flex_direction: v.flex_direction.into(),
+            flex_wrap: v.flex_wrap.into(),
+            overflow: v.overflow.into(),
+            align_items: v.align_items.into(),
+            align_self: v.align_self.into(),
+            align_content: v.align_content.into(),
+            justify_content: v.justify_content.into(),
+            position: v.position.into(),
+            margin: v.margin.into(),
+            padding: v.padding.into(),
+            border: v.border.into(),
+            flex_grow: v.flex_grow,
+            flex_shrink: v.flex_shrink,
+            flex_basis: v.flex_basis.into(),
+            size: v.size.into(),
+            min_size: v.min_size.into(),
+            max_size: v.max_size.into(),
+            aspect_ratio: v.aspect_ratio.into(),
+        }
+    }
+}
+
+impl From<StyleDef> for Style {
+    fn from(v: StyleDef) -> Self {
+        Style {
+            display: v.display.into(),
+            position_type: v.position_type.into(),
+            direction: v.direction.into(),
+            flex_direction: v.flex_direction.into(),
+            flex_wrap: v.flex_wrap.into(),
+            overflow: v.overflow.into(),
+            align_items: v.align_items.into(),
+            align_self: v.align_self.into(),
+            align_content: v.align_content.into(),
+            justify_content: v.justify_content.into(),
+            position: v.position.into(),
+            margin: v.margin.into(),
+            padding: v.padding.into(),
+            border: v.border.into(),
+            flex_grow: v.flex_grow,
+            flex_shrink: v.flex_shrink,
+            flex_basis: v.flex_basis.into(),
+            size: v.size.into(),
+            min_size: v.min_size.into(),
+            max_size: v.max_size.into(),
+            aspect_ratio: v.aspect_ratio.into(),
+        }
+    }
+}
+
+/// the size and location of a layout, the order of the node in its parent is
+/// private to stretch and is left out
+#[derive(Serialize, Deserialize)]
+struct LayoutDef {
+    width: f32,
+    height: f32,
+    x: f32,
+    y: f32,
+}
+
+impl From<&Layout> for LayoutDef {
+    fn from(v: &Layout) -> Self {
+        LayoutDef {
+            width: v.size.width,
+            height: v.size.height,
+            x: v.location.x,
+            y: v.location.y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        attribute::{checked, label, Color},
+        widget::{
+            button, checkbox, column, event::on_click,
+            layout::compute_node_layout, snapshot,
+        },
+    };
+    use stretch::Stretch;
+
+    fn round_trip(v: &Value) -> Value {
+        let json = serde_json::to_string(v).expect("must serialize");
+        serde_json::from_str(&json).expect("must deserialize")
+    }
+
+    #[test]
+    fn node_round_trip() {
+        let view: Node<()> = column(
+            vec![],
+            vec![
+                button(vec![label("+"), on_click(|_| ())]),
+                checkbox(vec![label("done"), checked(true)]),
+            ],
+        );
+        let json = serde_json::to_string(&NodeDef::from(&view))
+            .expect("must serialize");
+        let restored: Node<()> = serde_json::from_str::<NodeDef>(&json)
+            .expect("must deserialize")
+            .into_node();
+        assert_eq!(
+            serde_json::to_string(&NodeDef::from(&restored))
+                .expect("must serialize"),
+            json
+        );
+        // the callbacks are left out
+        let expected = r#"Vbox
+  Button label="+"
+  Checkbox label="done" checked=true
+"#;
+        assert_eq!(snapshot::render(&restored), expected);
+    }
+
+    #[test]
+    fn value_round_trip() {
+        let mut root = TreeNode::new("1", "src");
+        root.expanded = true;
+        root.children = vec![TreeNode::new("2", "main.rs")];
+        let values = vec![
+            Value::from("text".to_string()),
+            Value::from(true),
+            Value::from(4.5),
+            Value::from(vec![1u8, 2, 3]),
+            Value::from(Orientation::Vertical),
+            Value::from(PositionType::Absolute),
+            Value::Style(Style {
+                flex_direction: FlexDirection::Column,
+                size: Size {
+                    width: Dimension::Points(10.0),
+                    height: Dimension::Percent(1.0),
+                },
+                ..Default::default()
+            }),
+            Value::from(vec![SelectOption::new("r", "Red")]),
+            Value::from(vec![TableColumn::new("Name")]),
+            Value::from(vec![vec![
+                Value::from(1),
+                Value::from("a".to_string()),
+            ]]),
+            Value::from(vec![root]),
+            Value::from(vec![
+                DrawCommand::FillColor(Color::rgb(255, 0, 0)),
+                DrawCommand::Rect {
+                    x: 0.0,
+                    y: 0.0,
+                    width: 5.0,
+                    height: 5.0,
+                },
+                DrawCommand::Fill,
+            ]),
+        ];
+        for v in values {
+            assert_eq!(round_trip(&v), v);
+        }
+        // a static str is restored as a String
+        assert_eq!(
+            round_trip(&Value::from("text")),
+            Value::String("text".to_string())
+        );
+    }
+
+    #[test]
+    fn layout_is_serialized_but_not_deserialized() {
+        let mut stretch = Stretch::new();
+        let child = stretch
+            .new_node(
+                Style {
+                    size: Size {
+                        width: Dimension::Points(20.0),
+                        height: Dimension::Points(10.0),
+                    },
+                    ..Default::default()
+                },
+                &[],
+            )
+            .expect("must create a node");
+        let container = stretch
+            .new_node(Style::default(), &[child])
+            .expect("must create a container");
+        stretch
+            .compute_layout(container, Size::undefined())
+            .expect("must compute the layout");
+        let layout = *stretch.layout(child).expect("must have a layout");
+
+        let json = serde_json::to_string(&Value::from(layout))
+            .expect("must serialize");
+        assert_eq!(
+            json,
+            r#"{"Layout":{"width":20.0,"height":10.0,"x":0.0,"y":0.0}}"#
+        );
+        assert!(serde_json::from_str::<Value>(&json).is_err());
+    }
+
+    #[test]
+    fn calculated_layouts_are_left_out_of_the_node() {
+        let mut view: Node<()> = column(vec![], vec![button(vec![label("+")])]);
+        compute_node_layout(&mut view, Size::undefined());
+        let json = serde_json::to_string(&NodeDef::from(&view))
+            .expect("must serialize");
+        assert!(!json.contains("Layout"), "{}", json);
+    }
+}
@@ -0,0 +1,216 @@
+//! Renders a widget node tree into a stable, indented text form.
+//! The output is deterministic, so it can be committed next to the code
+//! and UI changes can be reviewed as text diffs.
+//!
+//! Each widget is written in one line with its plain attributes sorted by `AttribKey`,
+//! followed by the names of the attached events. The children are indented below it.
+//! ```text
+//! Hbox key="todo-1" name="todo"
+//!   Button label="x" name="destroy" events=[click_event]
+//! ```
+use crate::{attribute::TreeNode, AttribKey, Node, Value};
+use std::fmt::Write;
+
+/// Options for rendering a snapshot
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SnapshotOptions {
+    /// include the generated `Style` attribute of the widgets
+    pub include_style: bool,
+    /// include the calculated `Layout` attribute of the widgets
+    pub include_layout: bool,
+}
+
+/// render the node tree with the default options,
+/// where the generated `Style` and `Layout` are left out
+pub fn render<MSG: 'static>(node: &Node<MSG>) -> String {
+    render_with(node, SnapshotOptions::default())
+}
+
+/// render the node tree using the supplied options
+pub fn render_with<MSG: 'static>(
+    node: &Node<MSG>,
+    options: SnapshotOptions,
+) -> String {
+    let mut buffer = String::new();
+    render_node(&mut buffer, node, &options, 0).expect("must write to string");
+    buffer
+}
+
+fn render_node<MSG: 'static>(
+    buffer: &mut String,
+    node: &Node<MSG>,
+    options: &SnapshotOptions,
+    depth: usize,
+) -> std::fmt::Result {
+    let indent = "  ".repeat(depth);
+    match node {
+        Node::Element(element) => {
+            write!(buffer, "{}{:?}", indent, element.tag)?;
+
+            let mut attrs: Vec<_> = element
+                .get_attributes()
+                .iter()
+                .filter(|att| match att.name() {
+                    AttribKey::Style => options.include_style,
+                    AttribKey::Layout => options.include_layout,
+                    _ => true,
+                })
+                .collect();
+            attrs.sort_by(|a, b| a.name().cmp(b.name()));
+
+            let mut events = vec![];
+            for att in attrs {
+                for value in att.get_plain() {
+                    write!(buffer, " {}=", to_snake_case(att.name()))?;
+                    render_value(buffer, value)?;
+                }
+                if !att.get_callback().is_empty() {
+                    events.push(to_snake_case(att.name()));
+                }
+            }
+            if !events.is_empty() {
+                write!(buffer, " events=[{}]", events.join(", "))?;
+            }
+            writeln!(buffer)?;
+
+            for child in element.get_children() {
+                render_node(buffer, child, options, depth + 1)?;
+            }
+        }
+        Node::Text(txt) => {
+            writeln!(buffer, "{}{:?}", indent, txt.text)?;
+        }
+    }
+    Ok(())
+}
+
+fn render_value(buffer: &mut String, value: &Value) -> std::fmt::Result {
+    match value {
+        Value::String(v) => write!(buffer, "{:?}", v),
+        Value::Str(v) => write!(buffer, "{:?}", v),
+        Value::Bool(v) => write!(buffer, "{}", v),
+        Value::F64(v) => write!(buffer, "{}", v),
+        Value::Bytes(v) => write!(buffer, "<{} bytes>", v.len()),
+        Value::PositionType(v) => write!(buffer, "{:?}", v),
+        Value::Orientation(v) => write!(buffer, "{:?}", v),
+        Value::Options(v) => write!(buffer, "{:?}", v),
+        Value::Columns(v) => write!(buffer, "{:?}", v),
+        Value::Rows(rows) => {
+            write!(buffer, "[")?;
+            for (i, row) in rows.iter().enumerate() {
+                if i > 0 {
+                    write!(buffer, ", ")?;
+                }
+                write!(buffer, "[")?;
+                for (j, cell) in row.iter().enumerate() {
+                    if j > 0 {
+                        write!(buffer, ", ")?;
+                    }
+                    render_value(buffer, cell)?;
+                }
+                write!(buffer, "]")?;
+            }
+            write!(buffer, "]")
+        }
+        Value::Nodes(nodes) => render_tree_nodes(buffer, nodes),
+        Value::Commands(commands) => {
+            let commands: Vec<String> =
+                commands.iter().map(|cmd| cmd.to_string()).collect();
+            write!(buffer, "[{}]", commands.join(", "))
+        }
+        Value::Style(v) => write!(buffer, "{:?}", v),
+        Value::Layout(v) => write!(buffer, "{:?}", v),
+    }
+}
+
+/// write each node with its id, label and flags, followed by its loaded children,
+/// ie: `[{id="1" label="src" expanded [{id="2" label="main.rs" selected}]}]`
+fn render_tree_nodes(
+    buffer: &mut String,
+    nodes: &[TreeNode],
+) -> std::fmt::Result {
+    write!(buffer, "[")?;
+    for (i, node) in nodes.iter().enumerate() {
+        if i > 0 {
+            write!(buffer, ", ")?;
+        }
+        write!(buffer, "{{id={:?} label={:?}", node.id, node.label)?;
+        if let Some(icon) = &node.icon {
+            write!(buffer, " icon=<{} bytes>", icon.len())?;
+        }
+        if node.expanded {
+            write!(buffer, " expanded")?;
+        }
+        if node.selected {
+            write!(buffer, " selected")?;
+        }
+        if node.has_children && node.children.is_empty() {
+            write!(buffer, " has_children")?;
+        }
+        if !node.children.is_empty() {
+            write!(buffer, " ")?;
+            render_tree_nodes(buffer, &node.children)?;
+        }
+        write!(buffer, "}}")?;
+    }
+    write!(buffer, "]")
+}
+
+/// the attribute name as it is used in the attribute functions,
+/// ie: `SvgImage` is written as `svg_image`
+fn to_snake_case(key: &AttribKey) -> String {
+    let mut name = String::new();
+    for (i, ch) in key.to_string().chars().enumerate() {
+        if ch.is_uppercase() {
+            if i > 0 {
+                name.push('_');
+            }
+            name.extend(ch.to_lowercase());
+        } else {
+            name.push(ch);
+        }
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        attribute::{commands, nodes, rows, DrawCommand},
+        widget::{canvas, column, table, tree_view},
+    };
+
+    #[test]
+    fn render_the_contents_of_rows_nodes_and_commands() {
+        let mut src = TreeNode::new("1", "src");
+        src.expanded = true;
+        let mut main = TreeNode::new("2", "main.rs");
+        main.selected = true;
+        src.children = vec![main];
+        let mut docs = TreeNode::new("3", "docs");
+        docs.has_children = true;
+
+        let view: Node<()> = column(
+            vec![],
+            vec![
+                table(vec![rows(vec![
+                    vec![Value::from(1), Value::from("Alice")],
+                    vec![Value::from(2), Value::from("Bob")],
+                ])]),
+                tree_view(vec![nodes(vec![src, docs])]),
+                canvas(vec![commands(vec![
+                    DrawCommand::MoveTo { x: 0.0, y: 0.0 },
+                    DrawCommand::LineTo { x: 10.0, y: 5.5 },
+                    DrawCommand::Stroke,
+                ])]),
+            ],
+        );
+        let expected = r#"Vbox
+  Table rows=[[1, "Alice"], [2, "Bob"]]
+  TreeView nodes=[{id="1" label="src" expanded [{id="2" label="main.rs" selected}]}, {id="3" label="docs" has_children}]
+  Canvas commands=[move_to(0, 0), line_to(10, 5.5), stroke]
+"#;
+        assert_eq!(render(&view), expected);
+    }
+}
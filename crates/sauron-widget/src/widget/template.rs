@@ -0,0 +1,938 @@
+//! Loads a widget tree at runtime from an xml like template.
+//!
+//! The element names are the same as the builder functions and
+//! the attribute names are the same as the attribute and event functions.
+//! ```xml
+//! <column>
+//!     <button label="Hi" on_click="increment"/>
+//!     <checkbox label="Done" checked="true"/>
+//!     <paragraph>Some &lt;text&gt;</paragraph>
+//! </column>
+//! ```
+//! The event attributes refers to a handler name, which is looked up in the
+//! supplied `Bindings` and converted into the msg of the app.
+//!
+//! - `checked`, `editable`, `scrollable`, `resizable`, `monospace`, `selectable`,
+//!   `preformatted`, `indeterminate`, `sort_descending` and `multi_select` are booleans,
+//!   written as `true` or `false`
+//! - `width`, `height`, `selected`, `min`, `max`, `step`, `fraction`, `sort_column`,
+//!   `scroll_top` and `overscan` are numbers
+//! - `position_type` is either `relative` or `absolute`
+//! - `orientation` is either `horizontal` or `vertical`
+//! - `options` is a comma separated list of `value:label`, the label is the value
+//!   when it is left out, ie: `options="red:Red,green:Green"`
+//! - `data` and `svg_image` are the bytes of the text, ie: an inline svg
+//!
+//! The `table`, `tree_view` and `canvas` are created in code, since their columns, rows,
+//! nodes and drawing commands can not be written as text. The `virtual_list` is also
+//! created in code, since its rows are built by a closure.
+//!
+//! The names are the same as the ones known to the `view!` macro,
+//! which is checked by the tests against `widget_def.rs` of sauron-native-macro.
+use crate::{
+    widget::{
+        attribute::{Orientation, SelectOption},
+        builder::*,
+        event::Event,
+    },
+    AttribKey, Attribute, Callback, Node, Value,
+};
+use std::{collections::BTreeMap, fmt, rc::Rc};
+use stretch::style::PositionType;
+
+/// Maps the handler names used in the event attributes into msgs
+pub struct Bindings<MSG> {
+    handlers: BTreeMap<String, Rc<dyn Fn(Event) -> MSG>>,
+}
+
+impl<MSG> Bindings<MSG> {
+    /// create an empty bindings
+    pub fn new() -> Self {
+        Bindings {
+            handlers: BTreeMap::new(),
+        }
+    }
+
+    /// bind the handler name to the function which converts the event into a msg
+    pub fn on<F>(mut self, handler: &str, func: F) -> Self
+    where
+        F: Fn(Event) -> MSG + 'static,
+    {
+        self.handlers.insert(handler.to_string(), Rc::new(func));
+        self
+    }
+}
+
+impl<MSG> Default for Bindings<MSG> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The location in the template, both line and column starts at 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    /// the line number
+    pub line: usize,
+    /// the column number, counted in chars
+    pub column: usize,
+}
+
+/// An error in loading the template
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateError {
+    /// where the error occured in the template
+    pub position: Position,
+    /// what went wrong
+    pub kind: ErrorKind,
+}
+
+/// The kinds of error in loading the template
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorKind {
+    /// the template ended before the element is closed
+    UnexpectedEof,
+    /// found a char which is not valid at this location
+    UnexpectedChar(char),
+    /// there is no widget with this name
+    UnknownWidget(String),
+    /// there is no attribute or event with this name
+    UnknownAttribute(String),
+    /// the attribute value can not be converted into the value of the attribute
+    InvalidValue {
+        /// the attribute name
+        attribute: String,
+        /// the supplied value
+        value: String,
+    },
+    /// the handler name is not in the bindings
+    UnknownHandler(String),
+    /// the closing tag doesn't match the opening tag
+    MismatchedTag {
+        /// the name of the opening tag
+        expected: String,
+        /// the name of the closing tag
+        found: String,
+    },
+    /// the widget doesn't accept attributes, ie: paragraph
+    UnexpectedAttribute(String),
+    /// the widget can not have child widgets
+    UnexpectedChild(String),
+    /// text is only allowed in a paragraph
+    UnexpectedText(String),
+    /// an unknown `&name;` entity in the text
+    UnknownEntity(String),
+    /// there can only be one root widget
+    MultipleRoots,
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}",
+            self.position.line, self.position.column, self.kind
+        )
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::UnexpectedEof => write!(f, "unexpected end of template"),
+            ErrorKind::UnexpectedChar(ch) => {
+                write!(f, "unexpected character {:?}", ch)
+            }
+            ErrorKind::UnknownWidget(name) => {
+                write!(f, "unknown widget `{}`", name)
+            }
+            ErrorKind::UnknownAttribute(name) => {
+                write!(f, "unknown attribute `{}`", name)
+            }
+            ErrorKind::InvalidValue { attribute, value } => {
+                write!(f, "invalid value {:?} for `{}`", value, attribute)
+            }
+            ErrorKind::UnknownHandler(name) => {
+                write!(f, "no binding for the handler `{}`", name)
+            }
+            ErrorKind::MismatchedTag { expected, found } => write!(
+                f,
+                "expected closing tag `</{}>`, found `</{}>`",
+                expected, found
+            ),
+            ErrorKind::UnexpectedAttribute(widget) => {
+                write!(f, "`{}` has no attributes", widget)
+            }
+            ErrorKind::UnexpectedChild(widget) => {
+                write!(f, "`{}` can not have children", widget)
+            }
+            ErrorKind::UnexpectedText(widget) => write!(
+                f,
+                "text in `{}` needs to be wrapped in a `paragraph`",
+                widget
+            ),
+            ErrorKind::UnknownEntity(name) => {
+                write!(f, "unknown entity `&{};`", name)
+            }
+            ErrorKind::MultipleRoots => {
+                write!(f, "expecting a single root widget")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// load the widget tree from the template,
+/// the event attributes are bound to msgs using the bindings
+pub fn parse<MSG>(
+    source: &str,
+    bindings: &Bindings<MSG>,
+) -> Result<Node<MSG>, TemplateError>
+where
+    MSG: 'static,
+{
+    let mut parser = Parser {
+        chars: source.chars().collect(),
+        index: 0,
+        position: Position { line: 1, column: 1 },
+        bindings,
+    };
+    parser.skip_misc()?;
+    let root = parser.parse_element()?;
+    parser.skip_misc()?;
+    if parser.peek().is_some() {
+        return Err(parser.error(ErrorKind::MultipleRoots));
+    }
+    Ok(root)
+}
+
+/// the builder function of the widgets which have child widgets
+type ContainerBuilder<MSG> =
+    fn(Vec<Attribute<MSG>>, Vec<Node<MSG>>) -> Node<MSG>;
+
+/// How the node is created from the builder functions
+enum WidgetKind<MSG> {
+    Container(ContainerBuilder<MSG>),
+    Leaf(fn(Vec<Attribute<MSG>>) -> Node<MSG>),
+    Text,
+}
+
+fn widget_kind<MSG>(name: &str) -> Option<WidgetKind<MSG>>
+where
+    MSG: 'static,
+{
+    let kind = match name {
+        "column" => WidgetKind::Container(column),
+        "row" => WidgetKind::Container(row),
+        "vpane" => WidgetKind::Container(vpane),
+        "hpane" => WidgetKind::Container(hpane),
+        "overlay" => WidgetKind::Container(overlay),
+        "groupbox" => WidgetKind::Container(groupbox),
+        "header_bar" => WidgetKind::Container(header_bar),
+        "menu_bar" => WidgetKind::Container(menu_bar),
+        "menu" => WidgetKind::Container(menu),
+        "menu_item" => WidgetKind::Container(menu_item),
+        "dialog" => WidgetKind::Container(dialog),
+        "tab_box" => WidgetKind::Container(tab_box),
+        "button" => WidgetKind::Leaf(button),
+        "text_input" => WidgetKind::Leaf(text_input),
+        "checkbox" => WidgetKind::Leaf(checkbox),
+        "radio" => WidgetKind::Leaf(radio),
+        "image" => WidgetKind::Leaf(image),
+        "svg" => WidgetKind::Leaf(svg),
+        "textarea" => WidgetKind::Leaf(textarea),
+        "text_label" => WidgetKind::Leaf(text_label),
+        "search_input" => WidgetKind::Leaf(search_input),
+        "link" => WidgetKind::Leaf(link),
+        "slider" => WidgetKind::Leaf(slider),
+        "progress_bar" => WidgetKind::Leaf(progress_bar),
+        "spinner" => WidgetKind::Leaf(spinner),
+        "combo_box" => WidgetKind::Leaf(combo_box),
+        "paragraph" => WidgetKind::Text,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+/// How the attribute value is converted
+enum ValueKind {
+    String,
+    Bytes,
+    Bool,
+    Number,
+    PositionType,
+    Orientation,
+    Options,
+}
+
+fn attribute_key(name: &str) -> Option<(AttribKey, ValueKind)> {
+    let key = match name {
+        "value" => (AttribKey::Value, ValueKind::String),
+        "data" => (AttribKey::Data, ValueKind::Bytes),
+        "label" => (AttribKey::Label, ValueKind::String),
+        "svg_image" => (AttribKey::SvgImage, ValueKind::Bytes),
+        "editable" => (AttribKey::Editable, ValueKind::Bool),
+        "scrollable" => (AttribKey::Scrollable, ValueKind::Bool),
+        "width" => (AttribKey::Width, ValueKind::Number),
+        "height" => (AttribKey::Height, ValueKind::Number),
+        "position_type" => (AttribKey::PositionType, ValueKind::PositionType),
+        "resizable" => (AttribKey::Resizable, ValueKind::Bool),
+        "monospace" => (AttribKey::Monospace, ValueKind::Bool),
+        "selectable" => (AttribKey::Selectable, ValueKind::Bool),
+        "preformatted" => (AttribKey::Preformatted, ValueKind::Bool),
+        "name" => (AttribKey::Name, ValueKind::String),
+        "uri" => (AttribKey::Uri, ValueKind::String),
+        "checked" => (AttribKey::Checked, ValueKind::Bool),
+        "placeholder" => (AttribKey::Placeholder, ValueKind::String),
+        "selected" => (AttribKey::Selected, ValueKind::Number),
+        "min" => (AttribKey::Min, ValueKind::Number),
+        "max" => (AttribKey::Max, ValueKind::Number),
+        "step" => (AttribKey::Step, ValueKind::Number),
+        "orientation" => (AttribKey::Orientation, ValueKind::Orientation),
+        "fraction" => (AttribKey::Fraction, ValueKind::Number),
+        "indeterminate" => (AttribKey::Indeterminate, ValueKind::Bool),
+        "options" => (AttribKey::Options, ValueKind::Options),
+        "sort_column" => (AttribKey::SortColumn, ValueKind::Number),
+        "sort_descending" => (AttribKey::SortDescending, ValueKind::Bool),
+        "multi_select" => (AttribKey::MultiSelect, ValueKind::Bool),
+        "scroll_top" => (AttribKey::ScrollTop, ValueKind::Number),
+        "overscan" => (AttribKey::Overscan, ValueKind::Number),
+        "key" => (AttribKey::Key, ValueKind::String),
+        "id" => (AttribKey::Id, ValueKind::String),
+        "for" => (AttribKey::For, ValueKind::String),
+        _ => return None,
+    };
+    Some(key)
+}
+
+fn event_key(name: &str) -> Option<AttribKey> {
+    let key = match name {
+        "on_click" => AttribKey::ClickEvent,
+        "on_mousedown" => AttribKey::MouseDown,
+        "on_mouseup" => AttribKey::MouseUp,
+        "on_mousemove" => AttribKey::MouseMove,
+        "on_doubleclick" => AttribKey::DoubleClickEvent,
+        "on_blur" => AttribKey::BlurEvent,
+        "on_input" => AttribKey::InputEvent,
+        "on_keypress" => AttribKey::KeyEvent,
+        "on_enter" => AttribKey::Activate,
+        "on_select" => AttribKey::InputEvent,
+        "on_sort" => AttribKey::SortEvent,
+        "on_cell_edit" => AttribKey::CellEditEvent,
+        "on_expand" => AttribKey::ExpandEvent,
+        "on_node_select" => AttribKey::NodeSelectEvent,
+        "on_scroll" => AttribKey::ScrollEvent,
+        _ => return None,
+    };
+    Some(key)
+}
+
+fn convert_value(kind: ValueKind, value: &str) -> Option<Value> {
+    match kind {
+        ValueKind::String => Some(Value::String(value.to_string())),
+        ValueKind::Bytes => Some(Value::Bytes(value.as_bytes().to_vec())),
+        ValueKind::Bool => match value {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        ValueKind::Number => value.trim().parse::<f64>().ok().map(Value::F64),
+        ValueKind::PositionType => match value {
+            "relative" => Some(Value::PositionType(PositionType::Relative)),
+            "absolute" => Some(Value::PositionType(PositionType::Absolute)),
+            _ => None,
+        },
+        ValueKind::Orientation => match value {
+            "horizontal" => Some(Value::Orientation(Orientation::Horizontal)),
+            "vertical" => Some(Value::Orientation(Orientation::Vertical)),
+            _ => None,
+        },
+        ValueKind::Options => Some(Value::Options(
+            value
+                .split(',')
+                .map(|option| {
+                    let mut parts = option.splitn(2, ':').map(str::trim);
+                    let value = parts.next().unwrap_or_default();
+                    let label = parts.next().unwrap_or(value);
+                    SelectOption::new(value, label)
+                })
+                .collect(),
+        )),
+    }
+}
+
+struct Parser<'a, MSG> {
+    chars: Vec<char>,
+    index: usize,
+    position: Position,
+    bindings: &'a Bindings<MSG>,
+}
+
+impl<'a, MSG> Parser<'a, MSG>
+where
+    MSG: 'static,
+{
+    fn error(&self, kind: ErrorKind) -> TemplateError {
+        self.error_at(self.position, kind)
+    }
+
+    fn error_at(&self, position: Position, kind: ErrorKind) -> TemplateError {
+        TemplateError { position, kind }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        s.chars()
+            .enumerate()
+            .all(|(i, ch)| self.chars.get(self.index + i) == Some(&ch))
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.index += 1;
+        if ch == '\n' {
+            self.position.line += 1;
+            self.position.column = 1;
+        } else {
+            self.position.column += 1;
+        }
+        Some(ch)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), TemplateError> {
+        match self.peek() {
+            Some(ch) if ch == expected => {
+                self.next();
+                Ok(())
+            }
+            Some(ch) => Err(self.error(ErrorKind::UnexpectedChar(ch))),
+            None => Err(self.error(ErrorKind::UnexpectedEof)),
+        }
+    }
+
+    fn expect_str(&mut self, expected: &str) -> Result<(), TemplateError> {
+        for ch in expected.chars() {
+            self.expect(ch)?;
+        }
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().map(|ch| ch.is_whitespace()).unwrap_or(false) {
+            self.next();
+        }
+    }
+
+    /// skip everything up to and including the `end`
+    fn skip_until(&mut self, end: &str) -> Result<(), TemplateError> {
+        while !self.starts_with(end) {
+            if self.next().is_none() {
+                return Err(self.error(ErrorKind::UnexpectedEof));
+            }
+        }
+        self.expect_str(end)
+    }
+
+    /// skip the whitespaces, comments and the `<?xml ?>` declaration
+    fn skip_misc(&mut self) -> Result<(), TemplateError> {
+        loop {
+            self.skip_whitespace();
+            if self.starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else if self.starts_with("<?") {
+                self.skip_until("?>")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<String, TemplateError> {
+        let mut name = String::new();
+        while let Some(ch) = self.peek() {
+            if ch.is_alphanumeric() || ch == '_' || ch == '-' {
+                name.push(ch);
+                self.next();
+            } else {
+                break;
+            }
+        }
+        if name.is_empty() {
+            match self.peek() {
+                Some(ch) => Err(self.error(ErrorKind::UnexpectedChar(ch))),
+                None => Err(self.error(ErrorKind::UnexpectedEof)),
+            }
+        } else {
+            Ok(name)
+        }
+    }
+
+    /// parse a quoted attribute value, the entities are unescaped
+    fn parse_quoted(&mut self) -> Result<String, TemplateError> {
+        let quote = match self.peek() {
+            Some(ch) if ch == '"' || ch == '\'' => ch,
+            Some(ch) => return Err(self.error(ErrorKind::UnexpectedChar(ch))),
+            None => return Err(self.error(ErrorKind::UnexpectedEof)),
+        };
+        self.next();
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                Some(ch) if ch == quote => {
+                    self.next();
+                    return Ok(value);
+                }
+                Some('&') => value.push(self.parse_entity()?),
+                Some(ch) => {
+                    value.push(ch);
+                    self.next();
+                }
+                None => return Err(self.error(ErrorKind::UnexpectedEof)),
+            }
+        }
+    }
+
+    fn parse_entity(&mut self) -> Result<char, TemplateError> {
+        let start = self.position;
+        self.expect('&')?;
+        let name = self.parse_name()?;
+        self.expect(';')?;
+        match name.as_str() {
+            "lt" => Ok('<'),
+            "gt" => Ok('>'),
+            "amp" => Ok('&'),
+            "quot" => Ok('"'),
+            "apos" => Ok('\''),
+            _ => Err(self.error_at(start, ErrorKind::UnknownEntity(name))),
+        }
+    }
+
+    fn parse_attribute(
+        &mut self,
+        attrs: &mut Vec<Attribute<MSG>>,
+    ) -> Result<(), TemplateError> {
+        let start = self.position;
+        let name = self.parse_name()?;
+        self.skip_whitespace();
+        self.expect('=')?;
+        self.skip_whitespace();
+        let value_start = self.position;
+        let value = self.parse_quoted()?;
+
+        if let Some(event_key) = event_key(&name) {
+            let handler =
+                self.bindings.handlers.get(&value).ok_or_else(|| {
+                    self.error_at(value_start, ErrorKind::UnknownHandler(value))
+                })?;
+            let handler = Rc::clone(handler);
+            attrs.push(mt_dom::on(
+                event_key,
+                Callback::from(move |event: Event| handler(event)),
+            ));
+        } else if let Some((key, kind)) = attribute_key(&name) {
+            let converted = convert_value(kind, &value).ok_or_else(|| {
+                self.error_at(
+                    value_start,
+                    ErrorKind::InvalidValue {
+                        attribute: name.clone(),
+                        value: value.clone(),
+                    },
+                )
+            })?;
+            attrs.push(mt_dom::attr(key, converted));
+        } else {
+            return Err(self.error_at(start, ErrorKind::UnknownAttribute(name)));
+        }
+        Ok(())
+    }
+
+    fn parse_element(&mut self) -> Result<Node<MSG>, TemplateError> {
+        self.expect('<')?;
+        let start = self.position;
+        let name = self.parse_name()?;
+        let kind = widget_kind::<MSG>(&name).ok_or_else(|| {
+            self.error_at(start, ErrorKind::UnknownWidget(name.clone()))
+        })?;
+
+        let mut attrs = vec![];
+        let self_closing = loop {
+            self.skip_whitespace();
+            if self.starts_with("/>") {
+                self.expect_str("/>")?;
+                break true;
+            }
+            if self.starts_with(">") {
+                self.expect('>')?;
+                break false;
+            }
+            if let WidgetKind::Text = kind {
+                return Err(self.error(ErrorKind::UnexpectedAttribute(name)));
+            }
+            self.parse_attribute(&mut attrs)?;
+        };
+
+        let mut children = vec![];
+        let mut text = String::new();
+        if !self_closing {
+            loop {
+                if self.starts_with("</") {
+                    self.expect_str("</")?;
+                    let closing_start = self.position;
+                    let closing = self.parse_name()?;
+                    if closing != name {
+                        return Err(self.error_at(
+                            closing_start,
+                            ErrorKind::MismatchedTag {
+                                expected: name,
+                                found: closing,
+                            },
+                        ));
+                    }
+                    self.skip_whitespace();
+                    self.expect('>')?;
+                    break;
+                } else if self.starts_with("<!--") {
+                    self.skip_until("-->")?;
+                } else if self.starts_with("<") {
+                    let child_start = self.position;
+                    match kind {
+                        WidgetKind::Container(_) => {
+                            children.push(self.parse_element()?)
+                        }
+                        _ => {
+                            return Err(self.error_at(
+                                child_start,
+                                ErrorKind::UnexpectedChild(name),
+                            ))
+                        }
+                    }
+                } else {
+                    let text_start = self.position;
+                    let content = self.parse_text()?;
+                    match kind {
+                        WidgetKind::Text => text.push_str(&content),
+                        _ if content.trim().is_empty() => (),
+                        _ => {
+                            return Err(self.error_at(
+                                text_start,
+                                ErrorKind::UnexpectedText(name),
+                            ))
+                        }
+                    }
+                }
+            }
+        }
+
+        let node = match kind {
+            WidgetKind::Container(builder) => builder(attrs, children),
+            WidgetKind::Leaf(builder) => builder(attrs),
+            WidgetKind::Text => paragraph(text.trim()),
+        };
+        Ok(node)
+    }
+
+    /// parse the text up to the next tag, the entities are unescaped
+    fn parse_text(&mut self) -> Result<String, TemplateError> {
+        let mut text = String::new();
+        loop {
+            match self.peek() {
+                Some('<') => return Ok(text),
+                Some('&') => text.push(self.parse_entity()?),
+                Some(ch) => {
+                    text.push(ch);
+                    self.next();
+                }
+                None => return Err(self.error(ErrorKind::UnexpectedEof)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::{
+        attribute::find_callback, event::MouseEvent, snapshot, Widget,
+    };
+
+    /// the names known to the `view!` macro
+    const WIDGET_DEF: &str =
+        include_str!("../../../sauron-native-macro/src/widget_def.rs");
+
+    /// names known to the `view!` macro, which can not be written in a template
+    const NOT_IN_TEMPLATE: &[&str] = &[
+        "table",
+        "tree_view",
+        "canvas",
+        "columns",
+        "rows",
+        "nodes",
+        "commands",
+    ];
+
+    /// the quoted names in the `const NAME: &[&str]` array of `widget_def.rs`
+    fn def_names(name: &str) -> Vec<String> {
+        let start = WIDGET_DEF
+            .find(&format!("const {}: &[&str] = &[", name))
+            .expect("must find the array");
+        let array = &WIDGET_DEF[start..];
+        let array = &array[..array.find("];").expect("must close the array")];
+        array
+            .split('"')
+            .skip(1)
+            .step_by(2)
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    fn parse_err(source: &str) -> TemplateError {
+        parse::<()>(source, &Bindings::new()).expect_err("must fail")
+    }
+
+    #[test]
+    fn widgets_are_in_sync_with_the_view_macro() {
+        for name in def_names("CONTAINERS") {
+            assert!(
+                matches!(
+                    widget_kind::<()>(&name),
+                    Some(WidgetKind::Container(_))
+                ),
+                "`{}` is not a container in the template",
+                name
+            );
+        }
+        for name in def_names("LEAVES") {
+            if NOT_IN_TEMPLATE.contains(&name.as_str()) {
+                assert!(widget_kind::<()>(&name).is_none());
+                continue;
+            }
+            assert!(
+                matches!(widget_kind::<()>(&name), Some(WidgetKind::Leaf(_))),
+                "`{}` is not a leaf in the template",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn attributes_are_in_sync_with_the_view_macro() {
+        let attributes = def_names("ATTRIBUTES");
+        assert!(!attributes.is_empty(), "must find the attributes");
+        for name in attributes {
+            // `for` is a keyword, so the attribute function is `for_`
+            let name = name.trim_end_matches('_');
+            assert_eq!(
+                attribute_key(name).is_some(),
+                !NOT_IN_TEMPLATE.contains(&name),
+                "`{}` is not in sync with the view! macro",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn events_are_in_sync_with_the_view_macro() {
+        let events = def_names("EVENTS");
+        assert!(!events.is_empty(), "must find the events");
+        for name in events {
+            assert!(
+                event_key(&name).is_some(),
+                "the event `{}` is missing in the template",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn parse_with_bindings() {
+        let source = r#"<?xml version="1.0"?>
+            <!-- the counter -->
+            <column key="main">
+                <button label="Hi" on_click="increment"/>
+                <checkbox label='Done' checked="true"/>
+                <slider min="0" max="10" orientation="vertical"/>
+                <combo_box options="red:Red,green" selected="1"/>
+                <paragraph>Some &lt;text&gt;</paragraph>
+            </column>"#;
+        let bindings = Bindings::new().on("increment", |_event| 1);
+        let node = parse(source, &bindings).expect("must parse");
+
+        assert_eq!(node.tag(), Some(&Widget::Vbox));
+
+        let expected = r#"Vbox key="main"
+  Button label="Hi" events=[click_event]
+  Checkbox label="Done" checked=true
+  Slider min=0 max=10 orientation=Vertical
+  ComboBox selected=1 options=[SelectOption { value: "red", label: "Red" }, SelectOption { value: "green", label: "green" }]
+  Paragraph value="Some <text>"
+"#;
+        assert_eq!(snapshot::render(&node), expected);
+
+        let button =
+            &node.as_element_ref().expect("must be an element").children[0];
+        let callbacks = find_callback(
+            AttribKey::ClickEvent,
+            button.get_attributes().expect("must have attributes"),
+        )
+        .expect("must bind the click");
+        assert_eq!(callbacks[0].emit(MouseEvent::click(1, 1)), 1);
+    }
+
+    #[test]
+    fn unexpected_eof() {
+        let err = parse_err("<column>");
+        assert_eq!(err.kind, ErrorKind::UnexpectedEof);
+        assert_eq!(err.position, Position { line: 1, column: 9 });
+    }
+
+    #[test]
+    fn unexpected_char() {
+        let err = parse_err("<button label=Hi/>");
+        assert_eq!(err.kind, ErrorKind::UnexpectedChar('H'));
+        assert_eq!(
+            err.position,
+            Position {
+                line: 1,
+                column: 15
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_widget() {
+        let err = parse_err("<blink/>");
+        assert_eq!(err.kind, ErrorKind::UnknownWidget("blink".to_string()));
+        assert_eq!(err.position, Position { line: 1, column: 2 });
+    }
+
+    #[test]
+    fn unknown_attribute() {
+        let err = parse_err("<button color=\"red\"/>");
+        assert_eq!(err.kind, ErrorKind::UnknownAttribute("color".to_string()));
+        assert_eq!(err.position, Position { line: 1, column: 9 });
+    }
+
+    #[test]
+    fn invalid_value() {
+        let err = parse_err("<checkbox checked=\"yes\"/>");
+        assert_eq!(
+            err.kind,
+            ErrorKind::InvalidValue {
+                attribute: "checked".to_string(),
+                value: "yes".to_string(),
+            }
+        );
+        assert_eq!(
+            err.position,
+            Position {
+                line: 1,
+                column: 19
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_handler() {
+        let err = parse_err("<button on_click=\"increment\"/>");
+        assert_eq!(
+            err.kind,
+            ErrorKind::UnknownHandler("increment".to_string())
+        );
+        assert_eq!(
+            err.position,
+            Position {
+                line: 1,
+                column: 18
+            }
+        );
+    }
+
+    #[test]
+    fn mismatched_tag() {
+        let err = parse_err("<column></row>");
+        assert_eq!(
+            err.kind,
+            ErrorKind::MismatchedTag {
+                expected: "column".to_string(),
+                found: "row".to_string(),
+            }
+        );
+        assert_eq!(
+            err.position,
+            Position {
+                line: 1,
+                column: 11
+            }
+        );
+    }
+
+    #[test]
+    fn unexpected_attribute() {
+        let err = parse_err("<paragraph key=\"p\">text</paragraph>");
+        assert_eq!(
+            err.kind,
+            ErrorKind::UnexpectedAttribute("paragraph".to_string())
+        );
+        assert_eq!(
+            err.position,
+            Position {
+                line: 1,
+                column: 12
+            }
+        );
+    }
+
+    #[test]
+    fn unexpected_child() {
+        let err = parse_err("<button><spinner/></button>");
+        assert_eq!(err.kind, ErrorKind::UnexpectedChild("button".to_string()));
+        assert_eq!(err.position, Position { line: 1, column: 9 });
+    }
+
+    #[test]
+    fn unexpected_text() {
+        let err = parse_err("<column>hello</column>");
+        assert_eq!(err.kind, ErrorKind::UnexpectedText("column".to_string()));
+        assert_eq!(err.position, Position { line: 1, column: 9 });
+    }
+
+    #[test]
+    fn unknown_entity() {
+        let err = parse_err("<paragraph>a &nbsp; b</paragraph>");
+        assert_eq!(err.kind, ErrorKind::UnknownEntity("nbsp".to_string()));
+        assert_eq!(
+            err.position,
+            Position {
+                line: 1,
+                column: 14
+            }
+        );
+    }
+
+    #[test]
+    fn multiple_roots() {
+        let err = parse_err("<button/>\n<button/>");
+        assert_eq!(err.kind, ErrorKind::MultipleRoots);
+        assert_eq!(err.position, Position { line: 2, column: 1 });
+    }
+
+    #[test]
+    fn position_is_counted_in_chars_after_newlines() {
+        let err = parse_err(
+            "<column>\n\t<paragraph>日本</paragraph>\n\t<button label=\"é\" color=\"red\"/>\n</column>",
+        );
+        assert_eq!(err.kind, ErrorKind::UnknownAttribute("color".to_string()));
+        assert_eq!(
+            err.position,
+            Position {
+                line: 3,
+                column: 20
+            }
+        );
+        assert_eq!(err.to_string(), "3:20: unknown attribute `color`");
+    }
+}
@@ -1,7 +1,8 @@
 use sauron_native::{
     widget::{attribute::*, event::*, *},
-    Component, Node,
+    Cmd, Component, Dispatch, Node,
 };
+use std::rc::Rc;
 
 pub struct App {
     click_count: u32,
@@ -67,7 +68,7 @@ impl App {
 }
 
 impl Component<Msg> for App {
-    fn update(&mut self, msg: Msg) {
+    fn update(&mut self, msg: Msg) -> Cmd<Rc<dyn Dispatch<Msg>>> {
         match msg {
             Msg::Click => {
                 self.click_count += 1;
@@ -86,6 +87,7 @@ impl Component<Msg> for App {
             }
             Msg::Nothing => {}
         }
+        Cmd::none()
     }
 
     fn view(&self) -> Node<Msg> {
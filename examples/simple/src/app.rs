@@ -1,7 +1,5 @@
-use sauron_native::{
-    widget::{attribute::*, event::*, *},
-    Component, Node,
-};
+use sauron_native::{view, Cmd, Component, Dispatch, Node};
+use std::rc::Rc;
 
 pub struct App {
     click_count: u32,
@@ -28,7 +26,7 @@ impl App {
 }
 
 impl Component<Msg> for App {
-    fn update(&mut self, msg: Msg) {
+    fn update(&mut self, msg: Msg) -> Cmd<Rc<dyn Dispatch<Msg>>> {
         match msg {
             Msg::Click => {
                 self.click_count += 1;
@@ -43,76 +41,53 @@ impl Component<Msg> for App {
                 self.paragraph_text = txt;
             }
         }
+        Cmd::none()
     }
 
     fn view(&self) -> Node<Msg> {
-        column(
-            vec![],
-            vec![
-                menu_bar(
-                    vec![],
-                    vec![
-                        menu_item(
-                            vec![],
-                            vec![
-                                text_label(vec![value("File")]),
-                                menu(
-                                    vec![],
-                                    vec![
-                                        menu_item(
-                                            vec![],
-                                            vec![text_label(vec![value(
-                                                "Open",
-                                            )])],
-                                        ),
-                                        menu_item(
-                                            vec![],
-                                            vec![text_label(vec![value(
-                                                "Close",
-                                            )])],
-                                        ),
-                                    ],
-                                ),
-                            ],
-                        ),
-                        menu_item(
-                            vec![],
-                            vec![text_label(vec![value("About")])],
-                        ),
-                        menu_item(
-                            vec![],
-                            vec![text_label(vec![value("Quit")])],
-                        ),
-                    ],
-                ),
-                header_bar(
-                    vec![],
-                    vec![
-                        button(vec![label("Header button1")]),
-                        button(vec![label("Header button2")]),
-                    ],
-                ),
-                column(
-                    vec![],
-                    vec![
-                        textarea(vec![
-                            value(self.text.clone()),
-                            on_input(|input| {
-                                Msg::ParagraphChanged(
-                                    input
-                                        .value
-                                        .as_str()
-                                        .expect("must be a string")
-                                        .to_owned(),
-                                )
-                            }),
-                        ]),
-                        button(vec![label("btn1")]),
-                        button(vec![label("btn2")]),
-                        textarea(vec![value(self.paragraph_text.clone())]),
-                    ],
-                ),
-            ],
-        )
+        view! {
+            <column>
+                <menu_bar>
+                    <menu_item>
+                        <text_label value="File"/>
+                        <menu>
+                            <menu_item>
+                                <text_label value="Open"/>
+                            </menu_item>
+                            <menu_item>
+                                <text_label value="Close"/>
+                            </menu_item>
+                        </menu>
+                    </menu_item>
+                    <menu_item>
+                        <text_label value="About"/>
+                    </menu_item>
+                    <menu_item>
+                        <text_label value="Quit"/>
+                    </menu_item>
+                </menu_bar>
+                <header_bar>
+                    <button label="Header button1"/>
+                    <button label="Header button2"/>
+                </header_bar>
+                <column>
+                    <textarea
+                        value=self.text.clone()
+                        on_input=|input| {
+                            Msg::ParagraphChanged(
+                                input
+                                    .value
+                                    .as_str()
+                                    .expect("must be a string")
+                                    .to_owned(),
+                            )
+                        }
+                    />
+                    <button label="btn1"/>
+                    <button label="btn2"/>
+                    <textarea value=self.paragraph_text.clone()/>
+                </column>
+            </column>
+        }
     }
 }
@@ -3,6 +3,7 @@ use sauron_native::widget::event::*;
 use sauron_native::widget::*;
 use sauron_native::*;
 use serde_derive::{Deserialize, Serialize};
+use std::rc::Rc;
 
 #[derive(Serialize, Deserialize)]
 pub struct Model {
@@ -43,7 +44,7 @@ pub enum Msg {
 }
 
 impl Component<Msg> for Model {
-    fn update(&mut self, msg: Msg) {
+    fn update(&mut self, msg: Msg) -> Cmd<Rc<dyn Dispatch<Msg>>> {
         match msg {
             Msg::Add => {
                 self.entries.push(Entry::new(&self.value, self.uid));
@@ -104,6 +105,7 @@ impl Component<Msg> for Model {
             }
             Msg::NoOp => {}
         }
+        Cmd::none()
     }
 
     fn view(&self) -> Node<Msg> {
@@ -0,0 +1,129 @@
+//! Drives the todomvc app through the `TestBackend`
+use sauron_native::{
+    backend::{
+        test_ui::{Selector, TestError},
+        TestBackend,
+    },
+    widget::{event::*, snapshot},
+    AttribKey,
+};
+use todomvc::app::{Model, Msg};
+
+/// the text input of the new todo
+fn new_todo() -> Selector {
+    Selector::Path(vec![0, 0, 1])
+}
+
+fn clear_completed() -> Selector {
+    Selector::Path(vec![0, 2, 2])
+}
+
+fn add_todo(backend: &TestBackend<Model, Msg>, description: &str) {
+    backend
+        .input(&new_todo(), InputEvent::new(description.to_string()))
+        .expect("must input");
+    backend
+        .enter(&new_todo(), KeyEvent::enter())
+        .expect("must enter");
+}
+
+fn name_of(
+    backend: &TestBackend<Model, Msg>,
+    selector: &Selector,
+) -> Option<String> {
+    backend
+        .value(selector, AttribKey::Name)
+        .map(|v| v.to_string())
+}
+
+#[test]
+fn add_a_todo() {
+    let backend = TestBackend::new(Model::new());
+    backend
+        .input(&new_todo(), InputEvent::new("Buy milk"))
+        .expect("must input");
+    assert_eq!(
+        backend
+            .value(&new_todo(), AttribKey::Value)
+            .map(|v| v.to_string()),
+        Some("Buy milk".to_string())
+    );
+    backend
+        .enter(&new_todo(), KeyEvent::enter())
+        .expect("must enter");
+
+    // the input is cleared after the todo is added
+    assert_eq!(
+        backend
+            .value(&new_todo(), AttribKey::Value)
+            .map(|v| v.to_string()),
+        Some("".to_string())
+    );
+    let todo = Selector::Key("todo-0".to_string());
+    assert_eq!(backend.find(&todo), Some(vec![0, 1, 1, 0]));
+    assert_eq!(name_of(&backend, &todo), Some("todo".to_string()));
+    let expected = r#"Hbox key="todo-0" name="todo"
+  Hbox name="view"
+    Checkbox checked=false id="0" name="toggle" events=[click_event]
+    Label value="Buy milk" for="0" events=[double_click_event]
+    Button label="x" name="destroy" events=[click_event]
+  TextInput value="Buy milk" name="edit" events=[input_event, key_event, blur_event]
+"#;
+    let node = backend.node(&todo).expect("must have the todo");
+    assert_eq!(snapshot::render(&node), expected);
+}
+
+#[test]
+fn toggle_and_clear_completed() {
+    let backend = TestBackend::new(Model::new());
+    add_todo(&backend, "Buy milk");
+    add_todo(&backend, "Walk the dog");
+
+    backend
+        .click(&Selector::Id("0".to_string()), MouseEvent::click(0, 0))
+        .expect("must click");
+    assert_eq!(
+        name_of(&backend, &Selector::Key("todo-0".to_string())),
+        Some("todo completed".to_string())
+    );
+    assert_eq!(
+        name_of(&backend, &Selector::Key("todo-1".to_string())),
+        Some("todo".to_string())
+    );
+    assert_eq!(
+        backend
+            .value(&clear_completed(), AttribKey::Label)
+            .map(|v| v.to_string()),
+        Some("Clear completed (1)".to_string())
+    );
+
+    backend
+        .click(&clear_completed(), MouseEvent::click(0, 0))
+        .expect("must click");
+    assert_eq!(backend.find(&Selector::Key("todo-0".to_string())), None);
+    assert_eq!(
+        backend.find(&Selector::Key("todo-1".to_string())),
+        Some(vec![0, 1, 1, 0])
+    );
+}
+
+#[test]
+fn missing_todo_is_not_found() {
+    let backend = TestBackend::new(Model::new());
+    add_todo(&backend, "Buy milk");
+    let missing = Selector::Key("todo-99".to_string());
+    assert_eq!(
+        backend.click(&missing, MouseEvent::click(0, 0)),
+        Err(TestError::NotFound(missing))
+    );
+}
+
+#[test]
+fn title_label_has_no_listener() {
+    let backend = TestBackend::new(Model::new());
+    let title = Selector::Path(vec![0, 0, 0]);
+    assert_eq!(
+        backend.click(&title, MouseEvent::click(0, 0)),
+        Err(TestError::NoListener(title, AttribKey::ClickEvent))
+    );
+}
@@ -1,6 +1,6 @@
 //!  sauron native supports multiple back-end
 //!
-use crate::Component;
+use crate::{Component, FileDialog, OnChosen};
 
 #[cfg(feature = "with-web")]
 pub mod web_ui;
@@ -22,6 +22,13 @@ pub mod nwg_ui;
 #[cfg(feature = "with-nwg")]
 pub use nwg_ui::NwgBackend;
 
+pub mod patch_target;
+pub mod test_ui;
+pub use test_ui::TestBackend;
+
+#[cfg(any(feature = "with-gtk", feature = "with-titik", feature = "with-web"))]
+mod registry;
+
 /// All backend implementation must implement this trait
 pub trait Backend<APP, MSG>
 where
@@ -40,4 +47,12 @@ where
 pub trait Dispatch<MSG> {
     /// dispatch the msg which will subsequently change the application state
     fn dispatch(&self, msg: MSG);
+
+    /// show the file dialog, then dispatch the msg returned by `on_chosen`
+    /// with the files chosen by the user.
+    /// Dispatchers which can't show a file dialog pass no files, as if the user cancelled
+    fn choose_files(&self, dialog: FileDialog, on_chosen: OnChosen<MSG>) {
+        log::warn!("file dialog is not supported: {:?}", dialog.title);
+        self.dispatch(on_chosen(vec![]));
+    }
 }
@@ -1,36 +1,52 @@
 //! gtk backend
 use super::Dispatch;
-use crate::widget::layout::compute_node_layout;
-use crate::{AttribKey, Backend, Component, Node};
+use crate::{
+    Backend, ChosenFile, Component, FileAction, FileDialog, OnChosen, Program,
+    Window, WindowChange, WindowConfig,
+};
+use gdk_pixbuf::{PixbufLoader, PixbufLoaderExt};
 use gio::{prelude::*, ApplicationFlags};
 pub use gtk;
 use gtk::{
-    prelude::*, Application, ApplicationWindow, Button, CheckButton, Container,
-    Entry, EventBox, Frame, HeaderBar, Image, LinkButton, Menu, MenuBar,
-    MenuItem, Overlay, Paned, RadioButton, ScrolledWindow, SearchEntry,
-    TextView, WidgetExt,
+    prelude::*, Application, ApplicationWindow, Button, CheckButton,
+    ComboBoxText, DrawingArea, Entry, EventBox, FileChooserAction,
+    FileChooserDialog, Frame, HeaderBar, Image, Inhibit, LinkButton, Menu,
+    MenuBar, MenuItem, Notebook, Overlay, Paned, ProgressBar, RadioButton,
+    ResponseType, Scale, ScrolledWindow, SearchEntry, Spinner, TextView,
+    WidgetExt,
 };
 use log::*;
-use std::{cell::RefCell, fmt::Debug, marker::PhantomData, rc::Rc};
-use stretch::geometry::Size;
-use stretch::number::Number;
+use std::{cell::RefCell, collections::BTreeMap, fmt::Debug, rc::Rc};
+
+pub use custom_widget::{register_custom_widget, CustomWidget};
 
 mod apply_patches;
+mod canvas;
 mod convert_widget;
+mod custom_widget;
 mod images;
+mod table;
+mod tree;
+mod virtual_list;
 
 /// backend using gtk
 pub struct GtkBackend<APP, MSG>
 where
     MSG: 'static,
 {
-    app: Rc<RefCell<APP>>,
-    current_vdom: Rc<RefCell<Node<MSG>>>,
+    program: Program<APP, MSG>,
     root_node: Rc<RefCell<Option<GtkWidget>>>,
     application: Application,
     application_window: Rc<RefCell<Option<ApplicationWindow>>>,
     window_size: Rc<RefCell<(i32, i32)>>,
-    _phantom_msg: PhantomData<MSG>,
+    /// the extra windows of the app, keyed by their id
+    windows: Rc<RefCell<BTreeMap<String, Rc<ExtraWindow>>>>,
+}
+
+/// An extra window of the app, which is patched the same way as the main window
+struct ExtraWindow {
+    root_node: RefCell<Option<GtkWidget>>,
+    application_window: RefCell<Option<ApplicationWindow>>,
 }
 
 /// GtkWidget is an enum wrapper for gtk compoments
@@ -38,6 +54,7 @@ pub(crate) enum GtkWidget {
     GBox(gtk::Box),
     GBoxScrollable(ScrolledWindow),
     GroupBox(Frame),
+    TabBox(Notebook),
     Paned(Paned),
     Button(Button),
     LinkButton(LinkButton),
@@ -56,18 +73,32 @@ pub(crate) enum GtkWidget {
     Menu(Menu),
     MenuItem(MenuItem),
     SearchInput(SearchEntry),
+    Slider(Scale),
+    ProgressBar(ProgressBar),
+    Spinner(Spinner),
+    ComboBox(ComboBoxText),
+    /// the tree view of the table is in a scrolled window
+    Table(ScrolledWindow),
+    /// the tree view is in a scrolled window
+    TreeView(ScrolledWindow),
+    /// the built rows of the virtual list are in a box in the scrolled window
+    VirtualList(ScrolledWindow),
+    Canvas(DrawingArea),
+    /// the dialog is a toplevel window, so an empty box takes its place in the parent.
+    /// The dialog is kept in the data of the box and is shown while the box is mapped
+    Dialog(gtk::Box),
+    Custom(gtk::Widget),
 }
 
 impl<APP, MSG> Clone for GtkBackend<APP, MSG> {
     fn clone(&self) -> Self {
         GtkBackend {
-            app: Rc::clone(&self.app),
-            current_vdom: Rc::clone(&self.current_vdom),
+            program: self.program.clone(),
             root_node: Rc::clone(&self.root_node),
             application: self.application.clone(),
             application_window: self.application_window.clone(),
             window_size: self.window_size.clone(),
-            _phantom_msg: PhantomData,
+            windows: Rc::clone(&self.windows),
         }
     }
 }
@@ -79,44 +110,60 @@ where
 {
     fn new(app: APP) {
         let app_title = app.title();
+        let window_config = app.window_config();
 
-        let (initial_width, initial_height) = (800, 600);
-        let current_vdom =
-            Self::calculate_view_layout(&app, (initial_width, initial_height));
-        let root_vdom = current_vdom.clone();
+        // the layout is recalculated once the window is allocated with its actual size
+        let (initial_width, initial_height) = window_config.size;
+        let program = Program::new(
+            app,
+            Some(Self::viewport_size((initial_width, initial_height))),
+        );
 
         if gtk::init().is_err() {
             println!("failed to initialize GTK Application");
         }
         Self::setup_css();
         let application = Application::new(
-            Some("ivanceras.github.io.gtk"),
+            window_config.app_id.as_deref(),
             ApplicationFlags::FLAGS_NONE,
         )
         .expect("Failed to start app");
 
         let root_widget: Option<GtkWidget> = None;
         let backend = GtkBackend {
-            app: Rc::new(RefCell::new(app)),
-            current_vdom: Rc::new(RefCell::new(current_vdom)),
+            program,
             root_node: Rc::new(RefCell::new(root_widget)),
             application_window: Rc::new(RefCell::new(None)),
             application,
             window_size: Rc::new(RefCell::new((initial_width, initial_height))),
-            _phantom_msg: PhantomData,
+            windows: Rc::new(RefCell::new(BTreeMap::new())),
         };
 
-        let root_widget = convert_widget::from_node_tree(&backend, &root_vdom);
+        let root_widget = convert_widget::from_node_tree(
+            &backend.program,
+            &backend.program.current_vdom(),
+        );
         *backend.root_node.borrow_mut() = Some(root_widget);
 
+        let backend_clone = backend.clone();
+        backend
+            .program
+            .set_patch_applier(move |current_vdom, patches| {
+                apply_patches::apply_patches(
+                    &backend_clone.program,
+                    &backend_clone.root_node,
+                    &backend_clone.application_window,
+                    current_vdom,
+                    patches,
+                );
+            });
+
         let backend_clone = backend.clone();
 
         backend.application.connect_activate(move |application| {
             let application_window = ApplicationWindow::new(application);
-            application_window.set_default_size(initial_width, initial_height);
-            application_window.move_(1000, 500);
-            application_window.set_icon_name(Some("applications-graphics"));
             application_window.set_title(&app_title);
+            Self::apply_window_config(&application_window, &window_config);
             application_window.add(
                 backend_clone
                     .root_node
@@ -127,97 +174,246 @@ where
                     .expect("must be a widget"),
             );
             let backend_clone2 = backend_clone.clone();
-            /*
-            application_window.connect_size_allocate(move |_win, rect| {
-                println!(
-                    "moved in ({},{}) resized to ({},{})",
-                    rect.x, rect.y, rect.width, rect.height
-                );
-                *backend_clone2.window_size.borrow_mut() =
-                    (rect.width, rect.height);
-                //backend_clone2.redraw();
+            application_window.connect_size_allocate(move |window, _rect| {
+                // the size of the content, the allocation includes the decorations
+                let window_size = window.get_size();
+                if *backend_clone2.window_size.borrow() != window_size {
+                    *backend_clone2.window_size.borrow_mut() = window_size;
+                    // the view is patched outside of the allocation,
+                    // so the changes in the widgets are allocated in the next cycle
+                    let backend_clone3 = backend_clone2.clone();
+                    glib::idle_add_local(move || {
+                        backend_clone3.relayout();
+                        glib::Continue(false)
+                    });
+                }
             });
-            */
 
             application_window.show_all();
             *backend_clone.application_window.borrow_mut() =
                 Some(application_window);
+
+            let application_window = backend_clone.application_window.clone();
+            backend_clone.program.set_title_applier(move |title| {
+                if let Some(application_window) =
+                    application_window.borrow().as_ref()
+                {
+                    application_window.set_title(title);
+                }
+            });
+
+            let backend_clone2 = backend_clone.clone();
+            backend_clone.program.set_window_applier(move |change| {
+                backend_clone2.apply_window_change(change)
+            });
+
+            let backend_clone2 = backend_clone.clone();
+            backend_clone.program.set_file_dialog_applier(
+                move |dialog, on_chosen| {
+                    backend_clone2.show_file_dialog(dialog, on_chosen)
+                },
+            );
+
+            backend_clone.program.init();
+            backend_clone.program.update_windows();
         });
 
         backend.application.run(&[]);
     }
 
-    fn calculate_view_layout(app: &APP, window_size: (i32, i32)) -> Node<MSG> {
-        let mut new_view = app.view();
+    /// open, patch or close the extra windows of the app as real windows
+    fn apply_window_change(&self, change: WindowChange<MSG>) {
+        match change {
+            WindowChange::Open(window) => self.open_window(window),
+            WindowChange::Patch {
+                id,
+                current_vdom,
+                patches,
+            } => {
+                let extra_window = self.windows.borrow().get(id).cloned();
+                if let Some(extra_window) = extra_window {
+                    apply_patches::apply_patches(
+                        &self.program,
+                        &extra_window.root_node,
+                        &extra_window.application_window,
+                        current_vdom,
+                        patches,
+                    );
+                }
+            }
+            WindowChange::SetTitle { id, title } => {
+                if let Some(extra_window) = self.windows.borrow().get(id) {
+                    if let Some(application_window) =
+                        extra_window.application_window.borrow().as_ref()
+                    {
+                        application_window.set_title(title);
+                    }
+                }
+            }
+            WindowChange::Close(id) => {
+                let extra_window = self.windows.borrow_mut().remove(id);
+                if let Some(extra_window) = extra_window {
+                    if let Some(application_window) =
+                        extra_window.application_window.borrow_mut().take()
+                    {
+                        application_window.destroy();
+                    }
+                }
+            }
+        }
+    }
 
-        let (w, h) = window_size;
-        let (adjusted_w, adjusted_h) = (w as f32 - 100.0, h as f32 - 20.0);
-
-        compute_node_layout(
-            &mut new_view,
-            Size {
-                width: Number::Defined(adjusted_w),
-                height: Number::Defined(adjusted_h),
-            },
-        );
-        new_view
+    fn open_window(&self, window: &Window<MSG>) {
+        let application_window = ApplicationWindow::new(&self.application);
+        application_window.set_title(&window.title);
+        Self::apply_window_config(&application_window, &window.config);
+        let root_widget =
+            convert_widget::from_node_tree(&self.program, &window.view);
+        application_window
+            .add(root_widget.as_widget().expect("must be a widget"));
+
+        // the window is closed when the app no longer returns it
+        let program = self.program.clone();
+        let id = window.id.clone();
+        application_window.connect_delete_event(move |_window, _event| {
+            if let Some(msg) = program.window_close_msg(&id) {
+                program.dispatch(msg);
+            }
+            Inhibit(true)
+        });
+        application_window.show_all();
+
+        let extra_window = ExtraWindow {
+            root_node: RefCell::new(Some(root_widget)),
+            application_window: RefCell::new(Some(application_window)),
+        };
+        self.windows
+            .borrow_mut()
+            .insert(window.id.clone(), Rc::new(extra_window));
     }
 
-    /// redraw the UI due to layout changes caused by resize on the main window
-    fn redraw(&self)
-    where
-        MSG: Debug,
-    {
-        let mut new_view = Self::calculate_view_layout(
-            &self.app.borrow(),
-            *self.window_size.borrow(),
-        );
-        let (w, h) = *self.window_size.borrow();
-        let (adjusted_w, adjusted_h) = (w as f32 - 0.0, h as f32 - 0.0);
-
-        compute_node_layout(
-            &mut new_view,
-            Size {
-                width: Number::Defined(adjusted_w),
-                height: Number::Defined(adjusted_h),
-            },
+    /// show the file dialog on top of the window of the app,
+    /// the chosen files are dispatched when the user responded
+    fn show_file_dialog(&self, dialog: FileDialog, on_chosen: OnChosen<MSG>) {
+        let (action, accept_label) = match dialog.action {
+            FileAction::Open => (FileChooserAction::Open, "_Open"),
+            FileAction::Save => (FileChooserAction::Save, "_Save"),
+        };
+        let application_window = self.application_window.borrow().clone();
+        let chooser = FileChooserDialog::with_buttons(
+            Some(&dialog.title),
+            application_window.as_ref(),
+            action,
+            &[
+                ("_Cancel", ResponseType::Cancel),
+                (accept_label, ResponseType::Accept),
+            ],
         );
-        {
-            let current_vdom = self.current_vdom.borrow();
-            let diff = mt_dom::diff_with_key(
-                &current_vdom,
-                &new_view,
-                &AttribKey::Key,
-            );
-            apply_patches::apply_patches(
-                self,
-                &current_vdom,
-                &self.root_container(),
-                &diff,
-            );
+        chooser.set_select_multiple(dialog.multiple);
+        chooser.set_do_overwrite_confirmation(true);
+        if let Some(current_folder) = &dialog.current_folder {
+            chooser.set_current_folder(current_folder);
+        }
+        if let Some(current_name) = &dialog.current_name {
+            chooser.set_current_name(current_name);
+        }
+        for filter in dialog.filters.iter() {
+            let file_filter = gtk::FileFilter::new();
+            file_filter.set_name(Some(&filter.name));
+            for extension in filter.extensions.iter() {
+                file_filter.add_pattern(&format!("*.{}", extension));
+            }
+            chooser.add_filter(&file_filter);
         }
-        *self.current_vdom.borrow_mut() = new_view;
+
+        let program = self.program.clone();
+        chooser.connect_response(move |chooser, response| {
+            let files = if response == ResponseType::Accept {
+                chooser
+                    .get_filenames()
+                    .into_iter()
+                    .map(ChosenFile::new)
+                    .collect()
+            } else {
+                vec![]
+            };
+            chooser.destroy();
+            program.dispatch(on_chosen(files));
+        });
+        chooser.show_all();
     }
 
-    fn root_container(&self) -> Rc<Container> {
-        let root_widget = self.root_node.borrow();
-        if let Some(root_widget) = &*root_widget {
-            match &root_widget {
-                GtkWidget::GBox(gbox) => {
-                    let container: &Container = gbox.upcast_ref();
-                    Rc::new(container.clone())
-                }
-                GtkWidget::Paned(paned) => {
-                    let container: &Container = paned.upcast_ref();
-                    Rc::new(container.clone())
-                }
-                _ => panic!("expecting it to be a container"),
+    /// apply the configuration of the app into the window
+    fn apply_window_config(
+        application_window: &ApplicationWindow,
+        window_config: &WindowConfig,
+    ) {
+        let (width, height) = window_config.size;
+        application_window.set_default_size(width, height);
+        if let Some((x, y)) = window_config.position {
+            application_window.move_(x, y);
+        }
+        if window_config.min_size.is_some() || window_config.max_size.is_some()
+        {
+            let (min_width, min_height) =
+                window_config.min_size.unwrap_or((-1, -1));
+            let (max_width, max_height) =
+                window_config.max_size.unwrap_or((i32::MAX, i32::MAX));
+            let mut hints = gdk::WindowHints::empty();
+            if window_config.min_size.is_some() {
+                hints |= gdk::WindowHints::MIN_SIZE;
             }
-        } else {
-            panic!("must have a root widget");
+            if window_config.max_size.is_some() {
+                hints |= gdk::WindowHints::MAX_SIZE;
+            }
+            let geometry = gdk::Geometry {
+                min_width,
+                min_height,
+                max_width,
+                max_height,
+                base_width: -1,
+                base_height: -1,
+                width_inc: 0,
+                height_inc: 0,
+                min_aspect: 0.0,
+                max_aspect: 0.0,
+                win_gravity: gdk::Gravity::NorthWest,
+            };
+            application_window.set_geometry_hints(
+                None::<&gtk::Widget>,
+                Some(&geometry),
+                hints,
+            );
+        }
+        application_window.set_resizable(window_config.resizable);
+        application_window.set_decorated(window_config.decorated);
+        if let Some(icon) = &window_config.icon {
+            let pixbuf_loader = PixbufLoader::new();
+            pixbuf_loader
+                .write(icon)
+                .expect("Unable to write icon data into pixbuf_loader");
+            pixbuf_loader.close().expect("error creating pixbuf");
+            application_window.set_icon(pixbuf_loader.get_pixbuf().as_ref());
+        }
+        if window_config.fullscreen {
+            application_window.fullscreen();
         }
     }
 
+    /// the size used for calculating the layout of the view
+    fn viewport_size(window_size: (i32, i32)) -> (f32, f32) {
+        let (w, h) = window_size;
+        (w as f32, h as f32)
+    }
+
+    /// recalculate the layout of the view using the latest size of the window,
+    /// only the widgets that changed are patched.
+    /// Allocations with the same size do not trigger a relayout, which prevents a feedback loop.
+    fn relayout(&self) {
+        let (width, height) = Self::viewport_size(*self.window_size.borrow());
+        self.program.resize(width, height);
+    }
+
     // https://shallowsky.com/blog/programming/styling-gtk3-with-css-python.html
     // https://developer.gnome.org/gtk3/stable/chap-css-properties.html
     // https://stackoverflow.com/questions/44401069/how-can-i-trigger-or-force-a-css-transition-in-gtk
@@ -268,20 +464,6 @@ where
     }
 }
 
-impl<APP, MSG> Dispatch<MSG> for GtkBackend<APP, MSG>
-where
-    MSG: Debug + Clone + 'static,
-    APP: Component<MSG> + 'static,
-{
-    fn dispatch(&self, msg: MSG)
-    where
-        MSG: Debug,
-    {
-        self.app.borrow_mut().update(msg);
-        self.redraw();
-    }
-}
-
 impl GtkWidget {
     fn as_widget(&self) -> Option<&gtk::Widget> {
         match self {
@@ -309,6 +491,42 @@ impl GtkWidget {
                 let widget: &gtk::Widget = group_box.upcast_ref();
                 Some(widget)
             }
+            GtkWidget::Slider(scale) => {
+                let widget: &gtk::Widget = scale.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::TabBox(notebook) => {
+                let widget: &gtk::Widget = notebook.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::ProgressBar(progress_bar) => {
+                let widget: &gtk::Widget = progress_bar.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Spinner(spinner) => {
+                let widget: &gtk::Widget = spinner.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::ComboBox(combo_box) => {
+                let widget: &gtk::Widget = combo_box.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Table(scroll) => {
+                let widget: &gtk::Widget = scroll.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::TreeView(scroll) => {
+                let widget: &gtk::Widget = scroll.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::VirtualList(scroll) => {
+                let widget: &gtk::Widget = scroll.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Canvas(area) => {
+                let widget: &gtk::Widget = area.upcast_ref();
+                Some(widget)
+            }
             GtkWidget::Paned(paned) => {
                 let widget: &gtk::Widget = paned.upcast_ref();
                 Some(widget)
@@ -369,6 +587,11 @@ impl GtkWidget {
                 let widget: &gtk::Widget = entry.upcast_ref();
                 Some(widget)
             }
+            GtkWidget::Dialog(placeholder) => {
+                let widget: &gtk::Widget = placeholder.upcast_ref();
+                Some(widget)
+            }
+            GtkWidget::Custom(widget) => Some(widget),
         }
     }
 }
@@ -1,116 +1,388 @@
+use super::canvas;
 use super::convert_widget;
-use super::Dispatch;
+use super::custom_widget::CustomWidget;
+use super::table;
+use super::tree;
+use super::virtual_list;
+use super::{Dispatch, GtkWidget};
+use crate::backend::{
+    patch_target::{self, PatchTarget, Target},
+    registry,
+};
 use crate::{
     widget::attribute::util::is_scrollable, AttribKey, Attribute, Node, Patch,
 };
 use gdk_pixbuf::{PixbufLoader, PixbufLoaderExt};
 use gtk::{
-    prelude::*, Button, Container, ContainerExt, EventBox, Image, Label,
-    MenuItem, Overlay, TextView, Widget,
+    prelude::*, ApplicationWindow, Button, ComboBoxText, Container,
+    ContainerExt, DrawingArea, EventBox, Image, Label, Menu, MenuItem,
+    MenuShell, Notebook, Overlay, Paned, ProgressBar, ResponseType, Scale,
+    TextView, TreeView, Widget,
 };
-use mt_dom::patch::{AddAttributes, AppendChildren, RemoveNode, ReplaceNode};
-use std::{collections::HashMap, fmt::Debug};
+use std::{cell::RefCell, fmt::Debug, rc::Rc};
 
 pub fn apply_patches<MSG, DSP>(
     program: &DSP,
-    node: &Node<MSG>,
-    root_container: &Container,
-    patches: &Vec<Patch<MSG>>,
+    root_node: &RefCell<Option<GtkWidget>>,
+    application_window: &RefCell<Option<ApplicationWindow>>,
+    current_vdom: &Node<MSG>,
+    patches: &[Patch<MSG>],
 ) where
     MSG: Debug + 'static,
     DSP: Clone + Dispatch<MSG> + 'static,
 {
-    let nodes_to_patch = find_nodes(node, root_container, patches);
+    let mut gtk_target = GtkTarget {
+        program,
+        root_node,
+        application_window,
+    };
+    patch_target::apply_patches(&mut gtk_target, current_vdom, patches);
+}
 
-    for patch in patches {
-        let patch_node_idx = patch.node_idx();
-        let widget = nodes_to_patch
-            .get(&patch_node_idx)
-            .expect("must have a node to patch");
-        match patch {
-            Patch::AddAttributes(AddAttributes {
-                tag,
-                node_idx: _,
-                new_node_idx: _,
-                attrs,
-            }) => {
-                set_widget_attributes(tag, widget, attrs);
-            }
-            Patch::AppendChildren(AppendChildren {
-                tag,
-                node_idx: _,
-                children,
-            }) => {
-                match tag {
-                    crate::Widget::Overlay => {
-                        let overlay = widget
-                            .downcast_ref::<Overlay>()
-                            .expect("must be an overlay");
-                        for (child_idx, child) in children {
-                            if let Some(element) = child.as_element_ref() {
-                                let child =
-                                    convert_widget::from_node(program, element);
-                                let widget = child
-                                    .as_widget()
-                                    .expect("must be a widget");
-                                //Note: overlay have different behavior when adding child widget
-                                overlay.add_overlay(widget);
-                                overlay.set_child_index(widget, -1);
-                                widget.show();
-                                overlay.show_all();
-                            }
-                        }
-                    }
+/// patches the gtk widgets, starting from the root widget of the window
+struct GtkTarget<'a, DSP> {
+    program: &'a DSP,
+    root_node: &'a RefCell<Option<GtkWidget>>,
+    application_window: &'a RefCell<Option<ApplicationWindow>>,
+}
 
-                    _ => {
-                        let container = widget
-                            .downcast_ref::<Container>()
-                            .expect("must be a container");
-                        for (child_idx, child) in children {
-                            if let Some(element) = child.as_element_ref() {
-                                let child = convert_widget::from_node(
-                                    program, &element,
-                                );
-                                let widget = child
-                                    .as_widget()
-                                    .expect("must be a widget");
-                                //Note: overlay have different behavior when adding child widget
-                                container.add(widget);
-                                widget.show();
-                            }
-                        }
+impl<'a, DSP> GtkTarget<'a, DSP> {
+    /// the gtk widget at the path of the target, this is the outer most widget
+    /// such as the scrolled window which wraps a scrollable widget
+    fn find_widget<MSG>(&self, target: &Target<MSG>) -> Widget
+    where
+        MSG: 'static,
+    {
+        let mut widget = self
+            .root_node
+            .borrow()
+            .as_ref()
+            .and_then(|root_widget| root_widget.as_widget().cloned())
+            .expect("must have a root widget");
+        for (node, child_idx) in target.nodes.iter().zip(target.path.iter()) {
+            let tag = node.tag().expect("must have a tag");
+            let attrs = node.get_attributes().expect("must have attributes");
+            let container = widget
+                .downcast_ref::<Container>()
+                .expect("must be a container");
+            let widget_children = get_widget_children(tag, container, &attrs);
+            widget = widget_children
+                .get(*child_idx)
+                .unwrap_or_else(|| {
+                    panic!("must have a child at {} in {:?}", child_idx, tag)
+                })
+                .clone();
+        }
+        widget
+    }
+}
+
+impl<'a, DSP> GtkTarget<'a, DSP> {
+    /// the widgets are sized by gtk, only the divider of the paned follows
+    /// the calculated layout of its first child
+    fn set_layout<MSG>(&self, target: &Target<MSG>, attr: &Attribute<MSG>)
+    where
+        MSG: 'static,
+    {
+        if let Some((parent, 0)) = target.parent() {
+            let parent_tag = parent.tag().expect("must have a tag");
+            let layout =
+                attr.get_plain().into_iter().find_map(|v| v.as_layout());
+            if let Some(layout) = layout {
+                let position = match parent_tag {
+                    crate::Widget::Hpane => layout.size.width,
+                    crate::Widget::Vpane => layout.size.height,
+                    _ => return,
+                };
+                let paned_widget = self.find_widget(&parent);
+                let paned = paned_widget
+                    .downcast_ref::<Paned>()
+                    .expect("must be a paned");
+                if paned.get_position() != position as i32 {
+                    paned.set_position(position as i32);
+                }
+            }
+        }
+    }
+}
+
+impl<'a, DSP> GtkTarget<'a, DSP> {
+    /// the label of a child in a tab_box is displayed in its tab
+    fn set_tab_label<MSG>(&self, target: &Target<MSG>, label: &str)
+    where
+        MSG: 'static,
+    {
+        if let Some((parent, _)) = target.parent() {
+            if parent.tag() == Some(&crate::Widget::TabBox) {
+                let notebook_widget = self.find_widget(&parent);
+                let notebook = notebook_widget
+                    .downcast_ref::<Notebook>()
+                    .expect("must be a notebook");
+                notebook.set_tab_label_text(&self.find_widget(target), label);
+            }
+        }
+    }
+}
+
+impl<'a, MSG, DSP> PatchTarget<MSG> for GtkTarget<'a, DSP>
+where
+    MSG: Debug + 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    type Widget = GtkWidget;
+
+    fn create(&mut self, node: &Node<MSG>) -> Self::Widget {
+        convert_widget::from_node_tree(self.program, node)
+    }
+
+    fn set_attribute(&mut self, target: &Target<MSG>, attr: &Attribute<MSG>) {
+        match attr.name() {
+            AttribKey::Layout => {
+                self.set_layout(target, attr);
+                return;
+            }
+            // the style is only used in calculating the layout
+            AttribKey::Style => return,
+            AttribKey::Label => {
+                let label = attr
+                    .get_plain()
+                    .first()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default();
+                self.set_tab_label(target, &label);
+            }
+            _ => (),
+        }
+        let tag = target.tag().expect("must have a tag");
+        let attrs = target
+            .node()
+            .get_attributes()
+            .expect("must have attributes");
+        let widget =
+            get_actual_node_to_patch(tag, &self.find_widget(target), &attrs);
+        set_widget_attributes(tag, &widget, &[attr]);
+    }
+
+    fn remove_attribute(
+        &mut self,
+        target: &Target<MSG>,
+        attr: &Attribute<MSG>,
+    ) {
+        if *attr.name() == AttribKey::Label {
+            self.set_tab_label(target, "");
+        }
+        let tag = target.tag().expect("must have a tag");
+        let attrs = target
+            .node()
+            .get_attributes()
+            .expect("must have attributes");
+        let widget =
+            get_actual_node_to_patch(tag, &self.find_widget(target), &attrs);
+        remove_widget_attribute(tag, &widget, attr);
+    }
+
+    fn insert_child(
+        &mut self,
+        parent: &Target<MSG>,
+        index: usize,
+        node: &Node<MSG>,
+        child: Self::Widget,
+    ) {
+        let tag = parent.tag().expect("must have a tag");
+        let attrs = parent
+            .node()
+            .get_attributes()
+            .expect("must have attributes");
+        let widget = self.find_widget(parent);
+        let child_widget = child.as_widget().expect("must be a widget");
+        match tag {
+            crate::Widget::Overlay => {
+                let overlay = widget
+                    .downcast_ref::<Overlay>()
+                    .expect("must be an overlay");
+                //Note: overlay have different behavior when adding child widget
+                overlay.add_overlay(child_widget);
+                overlay.set_child_index(child_widget, index as i32);
+            }
+            crate::Widget::Vpane | crate::Widget::Hpane => {
+                let paned =
+                    widget.downcast_ref::<Paned>().expect("must be a paned");
+                match index {
+                    0 => paned.pack1(child_widget, true, true),
+                    1 => paned.pack2(child_widget, true, true),
+                    _ => panic!("paned can only have 2 children"),
+                }
+            }
+            crate::Widget::TabBox => {
+                let notebook = widget
+                    .downcast_ref::<Notebook>()
+                    .expect("must be a notebook");
+                let tab_label =
+                    Label::new(convert_widget::tab_label(node).as_deref());
+                notebook.insert_page(
+                    child_widget,
+                    Some(&tab_label),
+                    Some(index as u32),
+                );
+            }
+            crate::Widget::Menu | crate::Widget::MenuBar => {
+                let menu_shell = widget
+                    .downcast_ref::<MenuShell>()
+                    .expect("must be a menu shell");
+                menu_shell.insert(child_widget, index as i32);
+            }
+            // the sub menu is not a child of the menu item container
+            crate::Widget::MenuItem if child_widget.is::<Menu>() => {
+                let menu_item = widget
+                    .downcast_ref::<MenuItem>()
+                    .expect("must be a menu item");
+                let sub_menu = child_widget
+                    .downcast_ref::<Menu>()
+                    .expect("must be a menu");
+                menu_item.set_submenu(Some(sub_menu));
+            }
+            // the buttons are in the action area, the rest are in the content area
+            crate::Widget::Dialog => {
+                let dialog = convert_widget::find_dialog(&widget)
+                    .expect("must have a dialog for the placeholder");
+                let content_area = dialog.get_content_area();
+                let content_len = content_area.get_children().len();
+                if node.tag() == Some(&crate::Widget::Button) {
+                    dialog.add_action_widget(child_widget, ResponseType::None);
+                    convert_widget::dialog_action_area(&dialog).reorder_child(
+                        child_widget,
+                        index.saturating_sub(content_len) as i32,
+                    );
+                } else {
+                    content_area.add(child_widget);
+                    content_area.reorder_child(
+                        child_widget,
+                        index.min(content_len) as i32,
+                    );
+                }
+            }
+            crate::Widget::Custom(name) => {
+                match registry::lookup_custom_widget::<Rc<dyn CustomWidget<MSG>>>(
+                    name,
+                ) {
+                    Some(custom_widget) => {
+                        custom_widget.add_child(&widget, child_widget)
                     }
+                    // the placeholder of a missing custom widget is a gtk box
+                    None => widget
+                        .downcast_ref::<gtk::Container>()
+                        .expect("must be a container")
+                        .add(child_widget),
                 }
             }
-            Patch::RemoveNode(RemoveNode {
-                tag: _,
-                node_idx: _,
-            }) => {
-                let parent = widget.get_parent().expect("must have a parent");
-                if let Some(container) = parent.downcast_ref::<Container>() {
-                    container.remove(widget);
+            _ => {
+                let container = get_inner_container(tag, &widget, &attrs);
+                container.add(child_widget);
+                if let Some(gbox) = container.downcast_ref::<gtk::Box>() {
+                    gbox.reorder_child(child_widget, index as i32);
                 }
             }
-            Patch::ReplaceNode(ReplaceNode {
-                tag: _,
-                node_idx: _,
-                new_node_idx: _,
-                replacement,
-            }) => {
-                root_container.remove(widget);
-                if let Some(new_element) = replacement.as_element_ref() {
-                    let new_widget =
-                        convert_widget::from_node(program, new_element);
+        }
+        child_widget.show_all();
+    }
+
+    fn remove_child(&mut self, parent: &Target<MSG>, index: usize) {
+        let tag = parent.tag().expect("must have a tag");
+        let attrs = parent
+            .node()
+            .get_attributes()
+            .expect("must have attributes");
+        let widget = self.find_widget(parent);
+        let container = widget
+            .downcast_ref::<Container>()
+            .expect("must be a container");
+        let widget_children = get_widget_children(tag, container, &attrs);
+        let child = widget_children
+            .get(index)
+            .expect("must have a child to remove");
+        if child.is::<Menu>() {
+            // the sub menu is not a child of the menu item container
+            let menu_item = widget
+                .downcast_ref::<MenuItem>()
+                .expect("must be a menu item");
+            menu_item.set_submenu(None::<&Menu>);
+        } else if let Some(child_parent) = child.get_parent() {
+            if let Some(child_container) =
+                child_parent.downcast_ref::<Container>()
+            {
+                child_container.remove(child);
+            }
+        }
+    }
+
+    fn replace(
+        &mut self,
+        target: &Target<MSG>,
+        node: &Node<MSG>,
+        replacement: Self::Widget,
+    ) {
+        match target.parent() {
+            Some((parent, index)) => {
+                self.remove_child(&parent, index);
+                self.insert_child(&parent, index, node, replacement);
+            }
+            None => {
+                let old_widget = self.find_widget(target);
+                let application_window = self.application_window.borrow();
+                if let Some(application_window) = application_window.as_ref() {
+                    application_window.remove(&old_widget);
                     let new_widget =
-                        new_widget.as_widget().expect("must be a widget");
-                    root_container.add(new_widget);
-                    new_widget.show();
+                        replacement.as_widget().expect("must be a widget");
+                    application_window.add(new_widget);
+                    new_widget.show_all();
                 }
+                *self.root_node.borrow_mut() = Some(replacement);
             }
-            _ => {
-                println!("container: {:?}", root_container);
-                println!("todo for: {:?}", patch);
+        }
+    }
+
+    fn move_child(&mut self, parent: &Target<MSG>, from: usize, to: usize) {
+        let tag = parent.tag().expect("must have a tag");
+        let attrs = parent
+            .node()
+            .get_attributes()
+            .expect("must have attributes");
+        let widget = self.find_widget(parent);
+        if *tag == crate::Widget::Dialog {
+            // the buttons are only moved among the buttons in the action area
+            let dialog = convert_widget::find_dialog(&widget)
+                .expect("must have a dialog for the placeholder");
+            let content_area = dialog.get_content_area();
+            let content_len = content_area.get_children().len();
+            let action_area = convert_widget::dialog_action_area(&dialog);
+            let mut children = content_area.get_children();
+            children.extend(action_area.get_children());
+            let child = children.get(from).expect("must have a child to move");
+            if from < content_len {
+                content_area.reorder_child(child, to.min(content_len) as i32);
+            } else {
+                action_area.reorder_child(
+                    child,
+                    to.saturating_sub(content_len) as i32,
+                );
             }
+            return;
+        }
+        let container = get_inner_container(tag, &widget, &attrs);
+        let children = container.get_children();
+        let child = children.get(from).expect("must have a child to move");
+        if let Some(gbox) = container.downcast_ref::<gtk::Box>() {
+            gbox.reorder_child(child, to as i32);
+        } else if let Some(menu_shell) = container.downcast_ref::<MenuShell>() {
+            // the child is kept alive by the reference held in `children`
+            menu_shell.remove(child);
+            menu_shell.insert(child, to as i32);
+        } else if let Some(overlay) = container.downcast_ref::<Overlay>() {
+            overlay.set_child_index(child, to as i32);
+        } else if let Some(notebook) = container.downcast_ref::<Notebook>() {
+            notebook.reorder_child(child, Some(to as u32));
+        } else {
+            log::warn!("moving a child is not supported in {:?}", tag);
         }
     }
 }
@@ -208,90 +480,387 @@ fn set_widget_attributes<MSG: 'static>(
                 }
             }
         }
+        crate::Widget::Slider => {
+            let scale =
+                widget.downcast_ref::<Scale>().expect("must be a scale");
+            let adjustment = scale.get_adjustment();
+            for att in attrs {
+                for value in att.get_plain() {
+                    match (att.name(), value.as_f64()) {
+                        // setting the same value would emit the input event again
+                        (AttribKey::Value, Some(value))
+                            if scale.get_value() != value =>
+                        {
+                            scale.set_value(value)
+                        }
+                        (AttribKey::Min, Some(min)) => {
+                            adjustment.set_lower(min)
+                        }
+                        (AttribKey::Max, Some(max)) => {
+                            adjustment.set_upper(max)
+                        }
+                        (AttribKey::Step, Some(step)) => {
+                            scale.set_increments(step, step * 10.0)
+                        }
+                        (AttribKey::Orientation, _) => {
+                            if let Some(orientation) = value.as_orientation() {
+                                convert_widget::set_scale_orientation(
+                                    scale,
+                                    orientation,
+                                );
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+        // the entry of an editable combo box can only be added when it is created
+        crate::Widget::ComboBox => {
+            let combo_box = widget
+                .downcast_ref::<ComboBoxText>()
+                .expect("must be a combo box");
+            for att in attrs {
+                for value in att.get_plain() {
+                    match att.name() {
+                        AttribKey::Options => {
+                            if let Some(options) = value.as_options() {
+                                convert_widget::set_combo_options(
+                                    combo_box, options,
+                                );
+                            }
+                        }
+                        AttribKey::Value => convert_widget::set_combo_value(
+                            combo_box,
+                            &value.to_string(),
+                        ),
+                        _ => (),
+                    }
+                }
+            }
+        }
+        // the columns are only set when the table is created,
+        // the table needs a different key to change its columns
+        crate::Widget::Table => {
+            let tree_view = widget
+                .downcast_ref::<TreeView>()
+                .expect("must be a tree view");
+            for att in attrs {
+                for value in att.get_plain() {
+                    match att.name() {
+                        AttribKey::Rows => {
+                            if let Some(rows) = value.as_rows() {
+                                table::set_rows(tree_view, rows);
+                            }
+                        }
+                        AttribKey::Selected => table::select_row(
+                            tree_view,
+                            value.as_f64().map(|row| row as usize),
+                        ),
+                        AttribKey::SortColumn => {
+                            let descending = tree_view
+                                .get_columns()
+                                .iter()
+                                .find(|column| column.get_sort_indicator())
+                                .map(|column| {
+                                    column.get_sort_order()
+                                        == gtk::SortType::Descending
+                                })
+                                .unwrap_or(false);
+                            table::set_sort(
+                                tree_view,
+                                value.as_f64().map(|column| {
+                                    (column as usize, descending)
+                                }),
+                            );
+                        }
+                        AttribKey::SortDescending => {
+                            let sort_column = tree_view
+                                .get_columns()
+                                .iter()
+                                .position(|column| column.get_sort_indicator());
+                            table::set_sort(
+                                tree_view,
+                                sort_column
+                                    .map(|column| (column, value.as_bool())),
+                            );
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::TreeView => {
+            let tree_view = widget
+                .downcast_ref::<TreeView>()
+                .expect("must be a tree view");
+            for att in attrs {
+                for value in att.get_plain() {
+                    match att.name() {
+                        AttribKey::Nodes => {
+                            if let Some(nodes) = value.as_nodes() {
+                                tree::set_nodes(tree_view, nodes);
+                            }
+                        }
+                        AttribKey::MultiSelect => {
+                            tree::set_multi_select(tree_view, value.as_bool())
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::VirtualList => {
+            for att in attrs {
+                for value in att.get_plain() {
+                    match (att.name(), value.as_f64()) {
+                        (AttribKey::ContentHeight, Some(content_height)) => {
+                            virtual_list::set_content_height(
+                                widget,
+                                content_height,
+                            )
+                        }
+                        (AttribKey::RowsOffset, Some(rows_offset)) => {
+                            virtual_list::set_rows_offset(widget, rows_offset)
+                        }
+                        (AttribKey::ScrollTop, Some(scroll_top)) => {
+                            virtual_list::set_scroll_top(widget, scroll_top)
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::Canvas => {
+            let area = widget
+                .downcast_ref::<DrawingArea>()
+                .expect("must be a drawing area");
+            for att in attrs {
+                for value in att.get_plain() {
+                    if let (AttribKey::Commands, Some(commands)) =
+                        (att.name(), value.as_commands())
+                    {
+                        canvas::set_commands(area, commands);
+                    }
+                }
+            }
+        }
+        crate::Widget::ProgressBar => {
+            let progress_bar = widget
+                .downcast_ref::<ProgressBar>()
+                .expect("must be a progress bar");
+            for att in attrs {
+                for value in att.get_plain() {
+                    match att.name() {
+                        AttribKey::Fraction => {
+                            if let Some(fraction) = value.as_f64() {
+                                progress_bar
+                                    .set_fraction(fraction.max(0.0).min(1.0));
+                            }
+                        }
+                        AttribKey::Label => {
+                            progress_bar.set_text(Some(&value.to_string()));
+                            progress_bar.set_show_text(true);
+                        }
+                        AttribKey::Indeterminate => {
+                            convert_widget::set_indeterminate(
+                                progress_bar,
+                                value.as_bool(),
+                            );
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::TabBox => {
+            let notebook = widget
+                .downcast_ref::<Notebook>()
+                .expect("must be a notebook");
+            for att in attrs {
+                for value in att.get_plain() {
+                    match att.name() {
+                        AttribKey::Selected => {
+                            if let Some(selected) = value.as_f64() {
+                                convert_widget::set_selected_tab(
+                                    notebook,
+                                    selected as u32,
+                                )
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::Dialog => {
+            let dialog = convert_widget::find_dialog(widget)
+                .expect("must have a dialog for the placeholder");
+            for att in attrs {
+                for value in att.get_plain() {
+                    match att.name() {
+                        AttribKey::Label => {
+                            dialog.set_title(&value.to_string())
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+        crate::Widget::Custom(name) => {
+            if let Some(custom_widget) = registry::lookup_custom_widget::<
+                Rc<dyn CustomWidget<MSG>>,
+            >(name)
+            {
+                custom_widget.update_attributes(widget, attrs);
+            }
+        }
         _ => {
             println!("todo for other widgets");
         }
     }
 }
 
-fn find_nodes<MSG>(
-    node: &Node<MSG>,
-    container: &Container,
-    patches: &[Patch<MSG>],
-) -> HashMap<usize, Widget>
-where
-    MSG: 'static,
-{
-    let mut nodes_to_find: HashMap<usize, &crate::Widget> = HashMap::new();
-    let mut cur_node_idx = 0;
-
-    for patch in patches {
-        let tag = patch.tag().expect("must have a tag");
-        nodes_to_find.insert(patch.node_idx(), tag);
-    }
-    find_nodes_recursive(node, container, &mut cur_node_idx, &nodes_to_find)
-}
-
-fn find_nodes_recursive<MSG>(
-    node: &Node<MSG>,
-    container: &Container,
-    cur_node_idx: &mut usize,
-    nodes_to_find: &HashMap<usize, &crate::Widget>,
-) -> HashMap<usize, Widget>
-where
-    MSG: 'static,
-{
-    let tag = node.tag().expect("must have a tag");
-    let mut nodes_to_patch: HashMap<usize, Widget> = HashMap::new();
-
-    if let Some(_) = nodes_to_find.get(cur_node_idx) {
-        let container_widget: Widget = container.clone().upcast();
-        nodes_to_patch.insert(*cur_node_idx, container_widget);
-    }
-
-    if tag.is_container() {
-        let node_children = node.get_children().expect("must have children");
-
-        let attrs = node.get_attributes().expect("must have have attributes");
-        let widget_children = get_widget_children(tag, container, &attrs);
-
-        assert_eq!(
-            node_children.len(),
-            widget_children.len(),
-            "must have the same children len, in widget: {:?}",
-            tag
-        );
-        for (child_node, widget_child) in
-            node_children.iter().zip(widget_children.iter())
-        {
-            *cur_node_idx += 1;
-            let child_tag = child_node.tag().expect("must have a child tag");
-            if let Some(_patch_tag) = nodes_to_find.get(&cur_node_idx) {
-                let child_attrs =
-                    child_node.get_attributes().expect("must have attributes");
-                let widget: Widget = get_actual_node_to_patch(
-                    child_tag,
-                    widget_child,
-                    &child_attrs,
-                );
-                nodes_to_patch.insert(*cur_node_idx, widget);
-            }
-            if child_tag.is_container() {
-                if let Some(container) =
-                    widget_child.downcast_ref::<Container>()
-                {
-                    let child_nodes_to_patch = find_nodes_recursive(
-                        child_node,
-                        container,
-                        cur_node_idx,
-                        nodes_to_find,
-                    );
-                    nodes_to_patch.extend(child_nodes_to_patch);
-                }
+/// reset the attribute of the widget to its default value
+fn remove_widget_attribute<MSG: 'static>(
+    tag: &crate::Widget,
+    widget: &Widget,
+    attr: &Attribute<MSG>,
+) {
+    match (tag, attr.name()) {
+        (crate::Widget::Button, AttribKey::Label) => {
+            let button =
+                widget.downcast_ref::<Button>().expect("must be a button");
+            button.set_label("");
+        }
+        (crate::Widget::TextArea, AttribKey::Value) => {
+            let text_view = widget
+                .downcast_ref::<TextView>()
+                .expect("must be a text_view");
+            if let Some(buffer) = text_view.get_buffer() {
+                buffer.set_text("");
             }
         }
+        (crate::Widget::TextArea, AttribKey::Editable) => {
+            let text_view = widget
+                .downcast_ref::<TextView>()
+                .expect("must be a text_view");
+            text_view.set_editable(true);
+        }
+        (crate::Widget::Svg, AttribKey::Data) => {
+            let image =
+                widget.downcast_ref::<Image>().expect("must be an image");
+            image.clear();
+        }
+        (crate::Widget::Slider, AttribKey::Value) => {
+            let scale =
+                widget.downcast_ref::<Scale>().expect("must be a scale");
+            scale.set_value(scale.get_adjustment().get_lower());
+        }
+        (crate::Widget::Slider, AttribKey::Min) => {
+            let scale =
+                widget.downcast_ref::<Scale>().expect("must be a scale");
+            scale.get_adjustment().set_lower(0.0);
+        }
+        (crate::Widget::Slider, AttribKey::Max) => {
+            let scale =
+                widget.downcast_ref::<Scale>().expect("must be a scale");
+            scale.get_adjustment().set_upper(100.0);
+        }
+        (crate::Widget::Slider, AttribKey::Step) => {
+            let scale =
+                widget.downcast_ref::<Scale>().expect("must be a scale");
+            scale.set_increments(1.0, 10.0);
+        }
+        (crate::Widget::Slider, AttribKey::Orientation) => {
+            let scale =
+                widget.downcast_ref::<Scale>().expect("must be a scale");
+            convert_widget::set_scale_orientation(scale, Default::default());
+        }
+        (crate::Widget::ComboBox, AttribKey::Options) => {
+            let combo_box = widget
+                .downcast_ref::<ComboBoxText>()
+                .expect("must be a combo box");
+            combo_box.remove_all();
+        }
+        (crate::Widget::ComboBox, AttribKey::Value) => {
+            let combo_box = widget
+                .downcast_ref::<ComboBoxText>()
+                .expect("must be a combo box");
+            convert_widget::set_combo_value(combo_box, "");
+        }
+        (crate::Widget::Table, AttribKey::Rows) => {
+            let tree_view = widget
+                .downcast_ref::<TreeView>()
+                .expect("must be a tree view");
+            table::set_rows(tree_view, &[]);
+        }
+        (crate::Widget::Table, AttribKey::Selected) => {
+            let tree_view = widget
+                .downcast_ref::<TreeView>()
+                .expect("must be a tree view");
+            table::select_row(tree_view, None);
+        }
+        (crate::Widget::Table, AttribKey::SortColumn) => {
+            let tree_view = widget
+                .downcast_ref::<TreeView>()
+                .expect("must be a tree view");
+            table::set_sort(tree_view, None);
+        }
+        (crate::Widget::TreeView, AttribKey::Nodes) => {
+            let tree_view = widget
+                .downcast_ref::<TreeView>()
+                .expect("must be a tree view");
+            tree::set_nodes(tree_view, &[]);
+        }
+        (crate::Widget::TreeView, AttribKey::MultiSelect) => {
+            let tree_view = widget
+                .downcast_ref::<TreeView>()
+                .expect("must be a tree view");
+            tree::set_multi_select(tree_view, false);
+        }
+        (crate::Widget::Canvas, AttribKey::Commands) => {
+            let area = widget
+                .downcast_ref::<DrawingArea>()
+                .expect("must be a drawing area");
+            canvas::set_commands(area, &[]);
+        }
+        (crate::Widget::ProgressBar, AttribKey::Label) => {
+            let progress_bar = widget
+                .downcast_ref::<ProgressBar>()
+                .expect("must be a progress bar");
+            progress_bar.set_text(None);
+            progress_bar.set_show_text(false);
+        }
+        (crate::Widget::ProgressBar, AttribKey::Fraction) => {
+            let progress_bar = widget
+                .downcast_ref::<ProgressBar>()
+                .expect("must be a progress bar");
+            progress_bar.set_fraction(0.0);
+        }
+        (crate::Widget::ProgressBar, AttribKey::Indeterminate) => {
+            let progress_bar = widget
+                .downcast_ref::<ProgressBar>()
+                .expect("must be a progress bar");
+            convert_widget::set_indeterminate(progress_bar, false);
+        }
+        (crate::Widget::TabBox, AttribKey::Selected) => {
+            let notebook = widget
+                .downcast_ref::<Notebook>()
+                .expect("must be a notebook");
+            convert_widget::set_selected_tab(notebook, 0);
+        }
+        (crate::Widget::Dialog, AttribKey::Label) => {
+            let dialog = convert_widget::find_dialog(widget)
+                .expect("must have a dialog for the placeholder");
+            dialog.set_title("");
+        }
+        _ => log::warn!(
+            "removing the attribute {:?} is not supported in {:?}",
+            attr.name(),
+            tag
+        ),
     }
-    nodes_to_patch
 }
 
 /// return the actual node to be patched
@@ -309,6 +878,10 @@ where
     MSG: 'static,
 {
     match child_tag {
+        crate::Widget::Table | crate::Widget::TreeView => {
+            let tree_view: Widget = table::tree_view(widget_child).upcast();
+            tree_view
+        }
         crate::Widget::TextArea => {
             if is_scrollable(&attrs) {
                 // ScrolledWindow -> TextArea
@@ -441,6 +1014,68 @@ where
             }
             widgets
         }
+        // the children of the dialog are in the content area and the buttons
+        // in the action area of the dialog, and not in its placeholder
+        crate::Widget::Dialog => {
+            let dialog = convert_widget::find_dialog(container.upcast_ref())
+                .expect("must have a dialog for the placeholder");
+            let mut widgets = dialog.get_content_area().get_children();
+            widgets.extend(
+                convert_widget::dialog_action_area(&dialog).get_children(),
+            );
+            widgets
+        }
+        // the rows of the virtual list are in its box of rows
+        crate::Widget::VirtualList => {
+            get_inner_container(tag, container.upcast_ref(), attrs)
+                .get_children()
+        }
         _ => container.get_children(),
     }
 }
+
+/// return the container where the children of this widget are added into
+/// dealing with widgets that are wrapped with frame or scrolled window
+fn get_inner_container<MSG>(
+    tag: &crate::Widget,
+    widget: &Widget,
+    attrs: &[crate::Attribute<MSG>],
+) -> Container
+where
+    MSG: 'static,
+{
+    let container = widget
+        .downcast_ref::<Container>()
+        .expect("must be a container");
+    match *tag {
+        // GroupBox(Frame(Box))
+        crate::Widget::GroupBox => {
+            let frame_children = container.get_children();
+            let gbox = frame_children.get(0).expect("must have one child");
+            gbox.downcast_ref::<Container>()
+                .expect("must be a container")
+                .clone()
+        }
+        // ScrolledWindow -> ViewPort -> Box
+        crate::Widget::Vbox | crate::Widget::Hbox if is_scrollable(attrs) => {
+            let scrolled_children = container.get_children();
+            let view_port = scrolled_children
+                .get(0)
+                .and_then(|view_port| view_port.downcast_ref::<Container>())
+                .expect("must have a view port");
+            let view_port_children = view_port.get_children();
+            view_port_children
+                .get(0)
+                .and_then(|gbox| gbox.downcast_ref::<Container>())
+                .expect("must have a box inside the view port")
+                .clone()
+        }
+        crate::Widget::VirtualList => virtual_list::rows_box(widget).upcast(),
+        crate::Widget::Dialog => {
+            let dialog = convert_widget::find_dialog(widget)
+                .expect("must have a dialog for the placeholder");
+            dialog.get_content_area().upcast()
+        }
+        _ => container.clone(),
+    }
+}
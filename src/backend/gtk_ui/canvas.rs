@@ -0,0 +1,218 @@
+//! The canvas is a drawing area, which draws its commands with cairo whenever gtk redraws it.
+//! The commands are kept in the data of the drawing area, so a patch only replaces them
+//! and queues a redraw.
+use super::{table, Dispatch};
+use crate::{
+    widget::{
+        attribute::{
+            find_callback, find_value, util::find_commands, Color, DrawCommand,
+        },
+        event::MouseEvent,
+    },
+    AttribKey, Attribute,
+};
+use cairo::Context;
+use gdk::{ContextExt, EventMask};
+use gtk::{prelude::*, DrawingArea};
+use std::fmt::Debug;
+
+/// the data key of the drawing commands of the canvas
+const CANVAS_COMMANDS: &str = "sauron-native-canvas-commands";
+
+/// the font of the text commands
+const FONT_FAMILY: &str = "Sans";
+
+/// create the drawing area, the mouse events are at the position in the drawing area
+pub(crate) fn canvas<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+) -> DrawingArea
+where
+    MSG: Debug + 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let area = DrawingArea::new();
+    let width = find_value(AttribKey::Width, attrs).and_then(|v| v.as_f64());
+    let height = find_value(AttribKey::Height, attrs).and_then(|v| v.as_f64());
+    area.set_size_request(
+        width.map(|w| w as i32).unwrap_or(-1),
+        height.map(|h| h as i32).unwrap_or(-1),
+    );
+    area.set_hexpand(width.is_none());
+    area.set_vexpand(height.is_none());
+    set_commands(&area, find_commands(attrs));
+
+    area.connect_draw(|area, cr| {
+        let commands = unsafe {
+            area.get_data::<Vec<DrawCommand>>(CANVAS_COMMANDS).cloned()
+        };
+        draw(cr, &commands.unwrap_or_default());
+        Inhibit(false)
+    });
+
+    area.add_events(
+        EventMask::BUTTON_PRESS_MASK
+            | EventMask::BUTTON_RELEASE_MASK
+            | EventMask::POINTER_MOTION_MASK,
+    );
+    if let Some(callbacks) = find_callback(AttribKey::MouseDown, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            area.connect_button_press_event(move |_area, event| {
+                let (x, y) = event.get_position();
+                let mouse_event = MouseEvent::pressed(x as i32, y as i32);
+                let msg = cb_clone.emit(mouse_event);
+                program_clone.dispatch(msg);
+                Inhibit(false)
+            });
+        }
+    }
+    if let Some(callbacks) = find_callback(AttribKey::MouseUp, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            area.connect_button_release_event(move |_area, event| {
+                let (x, y) = event.get_position();
+                let mouse_event = MouseEvent::release(x as i32, y as i32);
+                let msg = cb_clone.emit(mouse_event);
+                program_clone.dispatch(msg);
+                Inhibit(false)
+            });
+        }
+    }
+    // the click is emitted when the button is released in the canvas
+    if let Some(callbacks) = find_callback(AttribKey::ClickEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            area.connect_button_release_event(move |area, event| {
+                let (x, y) = event.get_position();
+                let allocation = area.get_allocation();
+                if x >= 0.0
+                    && y >= 0.0
+                    && x < allocation.width as f64
+                    && y < allocation.height as f64
+                {
+                    let mouse_event = MouseEvent::click(x as i32, y as i32);
+                    let msg = cb_clone.emit(mouse_event);
+                    program_clone.dispatch(msg);
+                }
+                Inhibit(false)
+            });
+        }
+    }
+    if let Some(callbacks) = find_callback(AttribKey::MouseMove, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            area.connect_motion_notify_event(move |_area, event| {
+                let (x, y) = event.get_position();
+                let mouse_event = MouseEvent::mousemove(x as i32, y as i32);
+                let msg = cb_clone.emit(mouse_event);
+                program_clone.dispatch(msg);
+                Inhibit(false)
+            });
+        }
+    }
+    area
+}
+
+/// replace the drawing commands of the canvas and redraw it
+pub(crate) fn set_commands(area: &DrawingArea, commands: &[DrawCommand]) {
+    unsafe { area.set_data(CANVAS_COMMANDS, commands.to_vec()) };
+    area.queue_draw();
+}
+
+/// draw the commands in order, the path is kept after it is filled or stroked
+/// and only discarded with `BeginPath`, the same as in an html canvas
+fn draw(cr: &Context, commands: &[DrawCommand]) {
+    let black = Color::rgb(0, 0, 0);
+    let mut fill_color = black;
+    let mut stroke_color = black;
+    cr.new_path();
+    cr.set_line_width(1.0);
+    for command in commands {
+        match command {
+            DrawCommand::BeginPath => cr.new_path(),
+            DrawCommand::MoveTo { x, y } => cr.move_to(*x, *y),
+            DrawCommand::LineTo { x, y } => cr.line_to(*x, *y),
+            DrawCommand::CurveTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+            } => cr.curve_to(*x1, *y1, *x2, *y2, *x, *y),
+            DrawCommand::Arc {
+                x,
+                y,
+                radius,
+                start_angle,
+                end_angle,
+            } => cr.arc(*x, *y, *radius, *start_angle, *end_angle),
+            DrawCommand::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => cr.rectangle(*x, *y, *width, *height),
+            DrawCommand::ClosePath => cr.close_path(),
+            DrawCommand::FillColor(color) => fill_color = *color,
+            DrawCommand::StrokeColor(color) => stroke_color = *color,
+            DrawCommand::LineWidth(width) => cr.set_line_width(*width),
+            DrawCommand::Fill => {
+                set_source_color(cr, &fill_color);
+                cr.fill_preserve();
+            }
+            DrawCommand::Stroke => {
+                set_source_color(cr, &stroke_color);
+                cr.stroke_preserve();
+            }
+            DrawCommand::Text { x, y, text, size } => {
+                // the text is added to the path by cairo, so the path is restored after it
+                let path = cr.copy_path();
+                cr.new_path();
+                set_source_color(cr, &fill_color);
+                cr.select_font_face(
+                    FONT_FAMILY,
+                    cairo::FontSlant::Normal,
+                    cairo::FontWeight::Normal,
+                );
+                cr.set_font_size(*size);
+                cr.move_to(*x, *y);
+                cr.show_text(text);
+                cr.new_path();
+                cr.append_path(&path);
+            }
+            DrawCommand::Image {
+                x,
+                y,
+                width,
+                height,
+                data,
+            } => {
+                if let Some(pixbuf) = table::to_pixbuf(data) {
+                    let pixbuf_width = pixbuf.get_width().max(1) as f64;
+                    let pixbuf_height = pixbuf.get_height().max(1) as f64;
+                    cr.save();
+                    cr.translate(*x, *y);
+                    cr.scale(width / pixbuf_width, height / pixbuf_height);
+                    cr.set_source_pixbuf(&pixbuf, 0.0, 0.0);
+                    cr.paint();
+                    cr.restore();
+                }
+            }
+        }
+    }
+}
+
+fn set_source_color(cr: &Context, color: &Color) {
+    cr.set_source_rgba(
+        color.r as f64 / 255.0,
+        color.g as f64 / 255.0,
+        color.b as f64 / 255.0,
+        color.a,
+    );
+}
@@ -1,9 +1,17 @@
+use super::canvas;
+use super::custom_widget::CustomWidget;
 use super::images;
+use super::table;
+use super::tree;
+use super::virtual_list;
 use super::Dispatch;
 use super::GtkWidget;
-use crate::image_util;
-use crate::widget::attribute::util::get_layout;
+use crate::widget::attribute::util::{
+    find_fraction, find_options, find_orientation, find_range, get_layout,
+    is_indeterminate,
+};
 use crate::widget::event::{InputEvent, KeyEvent, MouseEvent};
+use crate::{backend::registry, image_util};
 use crate::{
     widget::attribute::{find_callback, find_value, util::is_scrollable},
     AttribKey, Attribute, Widget,
@@ -11,13 +19,34 @@ use crate::{
 use gdk_pixbuf::{PixbufLoader, PixbufLoaderExt};
 use gio::prelude::*;
 use gtk::{
-    prelude::*, Adjustment, Button, CheckButton, Entry, EntryBuffer, EventBox,
-    Frame, HeaderBar, Image, Label, LabelBuilder, LinkButton, Menu, MenuBar,
-    MenuItem, Orientation, Overlay, Paned, RadioButton, ScrolledWindow,
-    SearchEntry, TextBuffer, TextBufferExt, TextTagTable, TextView,
-    TextViewExt, WidgetExt,
+    prelude::*, Adjustment, Button, CheckButton, ComboBoxText, Entry,
+    EntryBuffer, EventBox, Frame, HeaderBar, Image, Label, LabelBuilder,
+    LinkButton, Menu, MenuBar, MenuItem, Notebook, Orientation, Overlay, Paned,
+    ProgressBar, RadioButton, ResponseType, Scale, ScrolledWindow, SearchEntry,
+    Spinner, TextBuffer, TextBufferExt, TextTagTable, TextView, TextViewExt,
+    WidgetExt,
 };
-use std::fmt::Debug;
+use std::{fmt::Debug, rc::Rc};
+
+/// the data key of the dialog which is shown in place of the placeholder
+const DIALOG: &str = "sauron-native-dialog";
+
+/// the style class of a progress bar which is pulsed, since its progress is unknown
+const INDETERMINATE_CLASS: &str = "indeterminate";
+
+/// the milliseconds between the pulses of an indeterminate progress bar
+const PULSE_INTERVAL: u32 = 100;
+
+/// the data key of the value of a combo box which is not one of its options yet
+const PENDING_COMBO_VALUE: &str = "sauron-native-pending-value";
+
+/// the data key of the combo box, which is set while the options and the value
+/// of the app are set, so the changes are not dispatched back to the app
+const SETTING_COMBO: &str = "sauron-native-setting-combo";
+
+/// the data key of the notebook, which is set while the selected tab of the app is set,
+/// so the switched page is not dispatched back to the app
+const SETTING_TAB: &str = "sauron-native-setting-tab";
 
 pub(crate) fn from_node_tree<DSP, MSG>(
     program: &DSP,
@@ -113,6 +142,49 @@ where
             frame.add(&vbox);
             GtkWidget::GroupBox(frame)
         }
+        // the label of each child is the label of its tab
+        Widget::TabBox => {
+            let notebook = Notebook::new();
+            for (child, widget_child) in
+                children.iter().zip(widget_children.iter())
+            {
+                if let Some(child_widget) = widget_child.as_widget() {
+                    let tab_label = Label::new(tab_label(child).as_deref());
+                    notebook.append_page(child_widget, Some(&tab_label));
+                } else {
+                    log::warn!(
+                        "was not able to add child widget: {:?}",
+                        widget_child.as_widget()
+                    );
+                }
+            }
+            if let Some(selected) =
+                find_value(AttribKey::Selected, &attrs).and_then(|v| v.as_f64())
+            {
+                set_selected_tab(&notebook, selected as u32);
+            }
+            // connected after the pages are added, since adding the first page
+            // also switches to it
+            if let Some(callbacks) =
+                find_callback(AttribKey::InputEvent, &attrs)
+            {
+                for cb in callbacks {
+                    let cb_clone = cb.clone();
+                    let program_clone = program.clone();
+                    notebook.connect_switch_page(
+                        move |notebook, _, page_num| {
+                            if is_setting_tab(notebook) {
+                                return;
+                            }
+                            let input_event = InputEvent::new(page_num);
+                            let msg = cb_clone.emit(input_event);
+                            program_clone.dispatch(msg);
+                        },
+                    );
+                }
+            }
+            GtkWidget::TabBox(notebook)
+        }
         // paned has only 2 children
         Widget::Hpane => {
             //TODO: make these infallable and more ergonomic
@@ -272,6 +344,109 @@ where
             }
             GtkWidget::TextInput(entry)
         }
+        Widget::ProgressBar => {
+            let progress_bar = ProgressBar::new();
+            if let Some(label) =
+                find_value(AttribKey::Label, &attrs).map(|v| v.to_string())
+            {
+                progress_bar.set_text(Some(&label));
+                progress_bar.set_show_text(true);
+            }
+            progress_bar.set_fraction(find_fraction(&attrs));
+            set_indeterminate(&progress_bar, is_indeterminate(&attrs));
+
+            // the progress bar is pulsed while it is indeterminate,
+            // the timer stops once the progress bar is destroyed
+            let progress_bar_weak = progress_bar.downgrade();
+            glib::timeout_add_local(PULSE_INTERVAL, move || {
+                match progress_bar_weak.upgrade() {
+                    Some(progress_bar) => {
+                        if progress_bar
+                            .get_style_context()
+                            .has_class(INDETERMINATE_CLASS)
+                        {
+                            progress_bar.pulse();
+                        }
+                        glib::Continue(true)
+                    }
+                    None => glib::Continue(false),
+                }
+            });
+            GtkWidget::ProgressBar(progress_bar)
+        }
+        // the spinner is animated by gtk
+        Widget::Spinner => {
+            let spinner = Spinner::new();
+            spinner.start();
+            GtkWidget::Spinner(spinner)
+        }
+        Widget::Table => GtkWidget::Table(table::table(program, &attrs)),
+        Widget::TreeView => {
+            GtkWidget::TreeView(tree::tree_view(program, &attrs))
+        }
+        Widget::Canvas => GtkWidget::Canvas(canvas::canvas(program, &attrs)),
+        Widget::VirtualList => GtkWidget::VirtualList(
+            virtual_list::virtual_list(program, &attrs, &widget_children),
+        ),
+        Widget::ComboBox => {
+            let is_editable = find_value(AttribKey::Editable, &attrs)
+                .map(|v| v.as_bool())
+                .unwrap_or(false);
+            let combo_box = if is_editable {
+                ComboBoxText::new_with_entry()
+            } else {
+                ComboBoxText::new()
+            };
+            set_combo_options(&combo_box, find_options(&attrs));
+            if let Some(value) = find_value(AttribKey::Value, &attrs) {
+                set_combo_value(&combo_box, &value.to_string());
+            }
+
+            if let Some(callbacks) =
+                find_callback(AttribKey::InputEvent, &attrs)
+            {
+                for cb in callbacks {
+                    let cb_clone = cb.clone();
+                    let program_clone = program.clone();
+                    combo_box.connect_changed(move |combo_box| {
+                        if is_setting_combo(combo_box) {
+                            return;
+                        }
+                        if let Some(value) = combo_value(combo_box) {
+                            let input_event = InputEvent::new(value);
+                            let msg = cb_clone.emit(input_event);
+                            program_clone.dispatch(msg);
+                        }
+                    });
+                }
+            }
+            GtkWidget::ComboBox(combo_box)
+        }
+        Widget::Slider => {
+            let (min, max, step) = find_range(&attrs);
+            let value = find_value(AttribKey::Value, &attrs)
+                .and_then(|v| v.as_f64())
+                .unwrap_or(min);
+            let scale =
+                Scale::new_with_range(Orientation::Horizontal, min, max, step);
+            set_scale_orientation(&scale, find_orientation(&attrs));
+            scale.set_value(value);
+
+            if let Some(callbacks) =
+                find_callback(AttribKey::InputEvent, &attrs)
+            {
+                for cb in callbacks {
+                    let cb_clone = cb.clone();
+                    let program_clone = program.clone();
+                    scale.connect_value_changed(move |scale| {
+                        let input_event = InputEvent::new(scale.get_value());
+                        let msg = cb_clone.emit(input_event);
+                        program_clone.dispatch(msg);
+                    });
+                }
+            }
+            GtkWidget::Slider(scale)
+        }
         Widget::Label => {
             let value = find_value(AttribKey::Value, &attrs)
                 .map(|v| v.to_string())
@@ -695,5 +870,217 @@ where
             //link_btn.set_size_request(width as i32, height as i32);
             GtkWidget::LinkButton(link_btn)
         }
+        Widget::Dialog => {
+            let title = find_value(AttribKey::Label, &attrs)
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            let dialog = gtk::Dialog::new();
+            dialog.set_title(&title);
+            dialog.set_modal(true);
+            dialog.set_default_size(width as i32, height as i32);
+            // the dialog stays open until the app removes it from the view
+            dialog.connect_delete_event(|_, _| Inhibit(true));
+
+            // the buttons are in the action area, the rest are in the content area
+            let content_area = dialog.get_content_area();
+            for (child, widget_child) in
+                children.iter().zip(widget_children.iter())
+            {
+                if let Some(child_widget) = widget_child.as_widget() {
+                    if child.tag() == Some(&Widget::Button) {
+                        dialog.add_action_widget(
+                            child_widget,
+                            ResponseType::None,
+                        );
+                    } else {
+                        content_area.add(child_widget);
+                    }
+                } else {
+                    log::warn!(
+                        "was not able to add child widget: {:?}",
+                        widget_child.as_widget()
+                    );
+                }
+            }
+
+            let placeholder = gtk::Box::new(Orientation::Vertical, 0);
+            unsafe { placeholder.set_data(DIALOG, dialog.clone()) };
+
+            let dialog_clone = dialog.clone();
+            placeholder.connect_map(move |placeholder| {
+                let toplevel =
+                    placeholder.get_toplevel().and_then(|toplevel| {
+                        toplevel.downcast::<gtk::Window>().ok()
+                    });
+                dialog_clone.set_transient_for(toplevel.as_ref());
+                dialog_clone.show_all();
+            });
+            let dialog_clone = dialog.clone();
+            placeholder.connect_unmap(move |_| dialog_clone.hide());
+            placeholder.connect_destroy(move |_| dialog.destroy());
+            GtkWidget::Dialog(placeholder)
+        }
+        Widget::Custom(name) => {
+            let custom_widget = registry::lookup_custom_widget::<
+                Rc<dyn CustomWidget<MSG>>,
+            >(name);
+            let widget = match &custom_widget {
+                Some(custom_widget) => {
+                    let dispatcher: Rc<dyn Dispatch<MSG>> =
+                        Rc::new(program.clone());
+                    custom_widget.create(attrs, dispatcher)
+                }
+                // the children are still displayed in the placeholder
+                None => gtk::Box::new(Orientation::Vertical, 0).upcast(),
+            };
+            for child in widget_children.iter() {
+                if let Some(child_widget) = child.as_widget() {
+                    match &custom_widget {
+                        Some(custom_widget) => {
+                            custom_widget.add_child(&widget, child_widget)
+                        }
+                        None => widget
+                            .downcast_ref::<gtk::Container>()
+                            .expect("must be a container")
+                            .add(child_widget),
+                    }
+                }
+            }
+            GtkWidget::Custom(widget)
+        }
     }
 }
+
+/// an indeterminate progress bar is marked with a style class, so it is pulsed by its timer.
+/// Setting the fraction again stops the pulsing
+pub(crate) fn set_indeterminate(
+    progress_bar: &ProgressBar,
+    indeterminate: bool,
+) {
+    let style_context = progress_bar.get_style_context();
+    if indeterminate {
+        style_context.add_class(INDETERMINATE_CLASS);
+    } else if style_context.has_class(INDETERMINATE_CLASS) {
+        style_context.remove_class(INDETERMINATE_CLASS);
+        progress_bar.set_fraction(progress_bar.get_fraction());
+    }
+}
+
+/// a vertical scale is inverted, so the highest value is at the top
+pub(crate) fn set_scale_orientation(
+    scale: &Scale,
+    orientation: crate::widget::attribute::Orientation,
+) {
+    match orientation {
+        crate::widget::attribute::Orientation::Horizontal => {
+            scale.set_orientation(Orientation::Horizontal);
+            scale.set_inverted(false);
+        }
+        crate::widget::attribute::Orientation::Vertical => {
+            scale.set_orientation(Orientation::Vertical);
+            scale.set_inverted(true);
+        }
+    }
+}
+
+/// replace the options of the combo box, the id of each option is its value.
+/// The chosen option is kept if it is still one of the options,
+/// otherwise the value which was not one of the previous options is chosen
+pub(crate) fn set_combo_options(
+    combo_box: &ComboBoxText,
+    options: &[crate::widget::attribute::SelectOption],
+) {
+    let pending_value: Option<String> =
+        unsafe { combo_box.steal_data(PENDING_COMBO_VALUE) };
+    let active_id = combo_box
+        .get_active_id()
+        .map(|active_id| active_id.to_string())
+        .or(pending_value);
+    unsafe { combo_box.set_data(SETTING_COMBO, true) };
+    combo_box.remove_all();
+    for option in options {
+        combo_box.append(Some(&option.value), &option.label);
+    }
+    if let Some(active_id) = active_id {
+        combo_box.set_active_id(Some(&active_id));
+    }
+    let _: Option<bool> = unsafe { combo_box.steal_data(SETTING_COMBO) };
+}
+
+/// choose the option with the value, the value is typed into the entry
+/// of an editable combo box when it is not one of the options.
+/// The attributes are patched one at a time, so the value which is not one of the options
+/// is kept until the options are replaced
+pub(crate) fn set_combo_value(combo_box: &ComboBoxText, value: &str) {
+    if combo_value(combo_box).as_deref() == Some(value) {
+        return;
+    }
+    unsafe { combo_box.set_data(SETTING_COMBO, true) };
+    if !combo_box.set_active_id(Some(value)) {
+        match combo_box
+            .get_child()
+            .and_then(|c| c.downcast::<Entry>().ok())
+        {
+            Some(entry) => entry.set_text(value),
+            None => {
+                combo_box.set_active(None);
+                unsafe {
+                    combo_box.set_data(PENDING_COMBO_VALUE, value.to_string())
+                };
+            }
+        }
+    }
+    let _: Option<bool> = unsafe { combo_box.steal_data(SETTING_COMBO) };
+}
+
+fn is_setting_combo(combo_box: &ComboBoxText) -> bool {
+    unsafe { combo_box.get_data::<bool>(SETTING_COMBO) }.is_some()
+}
+
+/// switch to the page of the selected tab, without dispatching it back to the app
+pub(crate) fn set_selected_tab(notebook: &Notebook, page_num: u32) {
+    unsafe { notebook.set_data(SETTING_TAB, true) };
+    notebook.set_current_page(Some(page_num));
+    let _: Option<bool> = unsafe { notebook.steal_data(SETTING_TAB) };
+}
+
+fn is_setting_tab(notebook: &Notebook) -> bool {
+    unsafe { notebook.get_data::<bool>(SETTING_TAB) }.is_some()
+}
+
+/// the value of the chosen option, or the text typed into an editable combo box.
+/// Nothing is chosen while the options of a combo box are being replaced
+fn combo_value(combo_box: &ComboBoxText) -> Option<String> {
+    match combo_box.get_active_id() {
+        Some(active_id) => Some(active_id.to_string()),
+        None if combo_box.get_has_entry() => {
+            combo_box.get_active_text().map(|text| text.to_string())
+        }
+        None => None,
+    }
+}
+
+/// the label of the tab which displays this child of the tab_box
+pub(crate) fn tab_label<MSG>(child: &crate::Node<MSG>) -> Option<String>
+where
+    MSG: 'static,
+{
+    child
+        .get_attributes()
+        .and_then(|attrs| find_value(AttribKey::Label, attrs))
+        .map(|v| v.to_string())
+}
+
+/// the dialog which is shown in place of this placeholder
+pub(crate) fn find_dialog(placeholder: &gtk::Widget) -> Option<gtk::Dialog> {
+    unsafe { placeholder.get_data::<gtk::Dialog>(DIALOG).cloned() }
+}
+
+/// the box of the dialog where its buttons are added
+#[allow(deprecated)]
+pub(crate) fn dialog_action_area(dialog: &gtk::Dialog) -> gtk::Box {
+    dialog
+        .get_action_area()
+        .downcast::<gtk::Box>()
+        .expect("the action area must be a box")
+}
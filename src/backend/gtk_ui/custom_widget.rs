@@ -0,0 +1,45 @@
+//! Custom widgets for the gtk backend
+use super::Dispatch;
+use crate::{backend::registry, Attribute};
+use gtk::{prelude::*, Container};
+use std::rc::Rc;
+
+/// A widget which is not part of the `Widget` enum, created with
+/// `widget::custom(name, attrs, children)` in the view.
+pub trait CustomWidget<MSG> {
+    /// create the gtk widget from the attributes,
+    /// the msgs emitted from the event callbacks are dispatched using the dispatcher
+    fn create(
+        &self,
+        attrs: &[Attribute<MSG>],
+        dispatcher: Rc<dyn Dispatch<MSG>>,
+    ) -> gtk::Widget;
+
+    /// set the changed attributes into the gtk widget
+    fn update_attributes(
+        &self,
+        widget: &gtk::Widget,
+        attrs: &[&Attribute<MSG>],
+    );
+
+    /// add the child widget, by default the child is added
+    /// if the widget is a gtk container
+    fn add_child(&self, widget: &gtk::Widget, child: &gtk::Widget) {
+        if let Some(container) = widget.downcast_ref::<Container>() {
+            container.add(child);
+        } else {
+            log::warn!("can not add child to custom widget: {:?}", widget);
+        }
+    }
+}
+
+/// register the custom widget which will be used to create
+/// the `Widget::Custom` of the same name in the gtk backend
+pub fn register_custom_widget<MSG, CW>(name: &str, custom_widget: CW)
+where
+    MSG: 'static,
+    CW: CustomWidget<MSG> + 'static,
+{
+    let custom_widget: Rc<dyn CustomWidget<MSG>> = Rc::new(custom_widget);
+    registry::register(name, custom_widget);
+}
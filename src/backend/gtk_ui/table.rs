@@ -0,0 +1,304 @@
+//! The table is a tree view of a list store, each column of the table
+//! is a column in the store with the type of its cells.
+//!
+//! The rows are sorted by the app, so the store is refilled with the rows in their order
+//! and the headers of the columns only display the sort indicator.
+use super::Dispatch;
+use crate::{
+    widget::{
+        attribute::{
+            find_callback,
+            util::{
+                cell_text, find_columns, find_rows, find_selected, find_sort,
+            },
+            CellKind, TableColumn,
+        },
+        event::{CellEditEvent, InputEvent, SortEvent},
+    },
+    AttribKey, Attribute, Value,
+};
+use gdk_pixbuf::{Pixbuf, PixbufLoader, PixbufLoaderExt};
+use glib::{StaticType, ToValue};
+use gtk::{
+    prelude::*, Adjustment, CellRendererPixbuf, CellRendererProgress,
+    CellRendererText, CellRendererToggle, ListStore, ScrolledWindow, SortType,
+    TreePath, TreeView, TreeViewColumn, TreeViewColumnSizing,
+};
+use std::fmt::Debug;
+
+/// create the tree view of the table in a scrolled window
+pub(crate) fn table<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+) -> ScrolledWindow
+where
+    MSG: Debug + 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let columns = find_columns(attrs);
+    let column_types: Vec<glib::Type> = columns
+        .iter()
+        .map(|column| match column.kind {
+            CellKind::Text => String::static_type(),
+            CellKind::Toggle => bool::static_type(),
+            CellKind::Progress => i32::static_type(),
+            CellKind::Image => Pixbuf::static_type(),
+        })
+        .collect();
+    let store = ListStore::new(&column_types);
+    let tree_view = TreeView::new_with_model(&store);
+
+    for (index, column) in columns.iter().enumerate() {
+        let tree_column =
+            tree_view_column(program, attrs, &store, index, column);
+        tree_view.append_column(&tree_column);
+    }
+    set_rows(&tree_view, find_rows(attrs));
+    set_sort(&tree_view, find_sort(attrs));
+    select_row(&tree_view, find_selected(attrs));
+
+    if let Some(callbacks) = find_callback(AttribKey::InputEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            tree_view.get_selection().connect_changed(move |selection| {
+                if let Some(row) = selection
+                    .get_selected()
+                    .and_then(|(model, iter)| model.get_path(&iter))
+                    .and_then(|path| path.get_indices().first().copied())
+                {
+                    let input_event = InputEvent::new(row as usize);
+                    let msg = cb_clone.emit(input_event);
+                    program_clone.dispatch(msg);
+                }
+            });
+        }
+    }
+
+    let scroll = ScrolledWindow::new(None::<&Adjustment>, None::<&Adjustment>);
+    scroll.add(&tree_view);
+    scroll
+}
+
+/// the column of the tree view, with the cell renderer of its kind
+fn tree_view_column<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+    store: &ListStore,
+    index: usize,
+    column: &TableColumn,
+) -> TreeViewColumn
+where
+    MSG: Debug + 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let tree_column = TreeViewColumn::new();
+    tree_column.set_title(&column.title);
+    tree_column.set_resizable(true);
+    if let Some(width) = column.width {
+        tree_column.set_sizing(TreeViewColumnSizing::Fixed);
+        tree_column.set_fixed_width(width as i32);
+    }
+    let edit_callbacks =
+        find_callback(AttribKey::CellEditEvent, attrs).unwrap_or_default();
+    match column.kind {
+        CellKind::Text => {
+            let renderer = CellRendererText::new();
+            tree_column.pack_start(&renderer, true);
+            tree_column.add_attribute(&renderer, "text", index as i32);
+            if column.editable {
+                renderer.set_property_editable(true);
+                for cb in edit_callbacks {
+                    let cb_clone = cb.clone();
+                    let program_clone = program.clone();
+                    renderer.connect_edited(
+                        move |_renderer, path, new_text| {
+                            if let Some(row) = path.get_indices().first() {
+                                let cell_edit_event = CellEditEvent::new(
+                                    *row as usize,
+                                    index,
+                                    new_text.to_string(),
+                                );
+                                let msg = cb_clone.emit(cell_edit_event);
+                                program_clone.dispatch(msg);
+                            }
+                        },
+                    );
+                }
+            }
+        }
+        CellKind::Toggle => {
+            let renderer = CellRendererToggle::new();
+            tree_column.pack_start(&renderer, false);
+            tree_column.add_attribute(&renderer, "active", index as i32);
+            if column.editable {
+                renderer.set_activatable(true);
+                for cb in edit_callbacks {
+                    let cb_clone = cb.clone();
+                    let program_clone = program.clone();
+                    let store_clone = store.clone();
+                    renderer.connect_toggled(move |_renderer, path| {
+                        let row = path.get_indices().first().copied();
+                        let iter = store_clone.get_iter(&path);
+                        if let (Some(row), Some(iter)) = (row, iter) {
+                            let checked = store_clone
+                                .get_value(&iter, index as i32)
+                                .get_some::<bool>()
+                                .unwrap_or(false);
+                            let cell_edit_event = CellEditEvent::new(
+                                row as usize,
+                                index,
+                                !checked,
+                            );
+                            let msg = cb_clone.emit(cell_edit_event);
+                            program_clone.dispatch(msg);
+                        }
+                    });
+                }
+            }
+        }
+        CellKind::Progress => {
+            let renderer = CellRendererProgress::new();
+            tree_column.pack_start(&renderer, true);
+            tree_column.add_attribute(&renderer, "value", index as i32);
+        }
+        CellKind::Image => {
+            let renderer = CellRendererPixbuf::new();
+            tree_column.pack_start(&renderer, false);
+            tree_column.add_attribute(&renderer, "pixbuf", index as i32);
+        }
+    }
+
+    if column.sortable {
+        tree_column.set_clickable(true);
+        if let Some(callbacks) = find_callback(AttribKey::SortEvent, attrs) {
+            for cb in callbacks {
+                let cb_clone = cb.clone();
+                let program_clone = program.clone();
+                tree_column.connect_clicked(move |tree_column| {
+                    let current = if tree_column.get_sort_indicator() {
+                        Some((
+                            index,
+                            tree_column.get_sort_order()
+                                == SortType::Descending,
+                        ))
+                    } else {
+                        None
+                    };
+                    let sort_event = SortEvent::pressed(index, current);
+                    let msg = cb_clone.emit(sort_event);
+                    program_clone.dispatch(msg);
+                });
+            }
+        }
+    }
+    tree_column
+}
+
+/// the tree view of a table or a tree_view in its scrolled window
+pub(crate) fn tree_view(scroll: &gtk::Widget) -> TreeView {
+    scroll
+        .downcast_ref::<ScrolledWindow>()
+        .expect("must be a scrolled window")
+        .get_child()
+        .and_then(|child| child.downcast::<TreeView>().ok())
+        .expect("must have a tree view")
+}
+
+/// refill the store with the rows, each value is converted into the type of its column.
+/// The selected row is kept if it is still one of the rows
+pub(crate) fn set_rows(tree_view: &TreeView, rows: &[Vec<Value>]) {
+    let store = tree_view
+        .get_model()
+        .and_then(|model| model.downcast::<ListStore>().ok())
+        .expect("must have a list store");
+    let selected = selected_row(tree_view);
+    store.clear();
+    let n_columns = store.get_n_columns();
+    let column_indices: Vec<u32> = (0..n_columns as u32).collect();
+    for row in rows {
+        let values: Vec<glib::Value> = (0..n_columns)
+            .map(|index| {
+                let column_type = store.get_column_type(index);
+                let value = row.get(index as usize);
+                to_cell_value(column_type, value)
+            })
+            .collect();
+        let values: Vec<&dyn ToValue> =
+            values.iter().map(|value| value as &dyn ToValue).collect();
+        store.insert_with_values(None, &column_indices, &values);
+    }
+    select_row(
+        tree_view,
+        selected.filter(|selected| *selected < rows.len()),
+    );
+}
+
+/// convert the value of the cell into the type of the column in the store
+fn to_cell_value(
+    column_type: glib::Type,
+    value: Option<&Value>,
+) -> glib::Value {
+    if column_type == bool::static_type() {
+        value.map(|v| v.as_bool()).unwrap_or(false).to_value()
+    } else if column_type == i32::static_type() {
+        let fraction = value.and_then(|v| v.as_f64()).unwrap_or(0.0);
+        ((fraction.max(0.0).min(1.0) * 100.0).round() as i32).to_value()
+    } else if column_type == Pixbuf::static_type() {
+        value
+            .and_then(|v| v.as_bytes())
+            .and_then(|bytes| to_pixbuf(bytes))
+            .to_value()
+    } else {
+        value.map(|v| cell_text(v)).unwrap_or_default().to_value()
+    }
+}
+
+/// the pixbuf of the image bytes, none if the bytes is not an image
+pub(crate) fn to_pixbuf(bytes: &[u8]) -> Option<Pixbuf> {
+    let mime = crate::image_util::image_mime_type(bytes)?;
+    let pixbuf_loader = PixbufLoader::new_with_mime_type(mime).ok()?;
+    pixbuf_loader.write(bytes).ok()?;
+    pixbuf_loader.close().ok()?;
+    pixbuf_loader.get_pixbuf()
+}
+
+/// display the sort indicator in the header of the sorted column only
+pub(crate) fn set_sort(tree_view: &TreeView, sort: Option<(usize, bool)>) {
+    for (index, tree_column) in tree_view.get_columns().iter().enumerate() {
+        match sort {
+            Some((sort_column, descending)) if sort_column == index => {
+                tree_column.set_sort_indicator(true);
+                tree_column.set_sort_order(if descending {
+                    SortType::Descending
+                } else {
+                    SortType::Ascending
+                });
+            }
+            _ => tree_column.set_sort_indicator(false),
+        }
+    }
+}
+
+/// select the row, all the rows are unselected when there is none
+pub(crate) fn select_row(tree_view: &TreeView, row: Option<usize>) {
+    if selected_row(tree_view) == row {
+        return;
+    }
+    let selection = tree_view.get_selection();
+    match row {
+        Some(row) => {
+            selection.select_path(&TreePath::new_from_indicesv(&[row as i32]))
+        }
+        None => selection.unselect_all(),
+    }
+}
+
+fn selected_row(tree_view: &TreeView) -> Option<usize> {
+    tree_view
+        .get_selection()
+        .get_selected()
+        .and_then(|(model, iter)| model.get_path(&iter))
+        .and_then(|path| path.get_indices().first().copied())
+        .map(|row| row as usize)
+}
@@ -0,0 +1,205 @@
+//! The tree view is a gtk tree view of a tree store, with the id, label and icon of each node.
+//!
+//! A node whose children are not loaded yet has a placeholder row as its child,
+//! so the expander of the node is displayed.
+//! The store is refilled when the nodes are patched, then the nodes are expanded and selected
+//! as they are in the app. The events of these changes are not emitted, since they are
+//! from the app.
+use super::{table, Dispatch};
+use crate::{
+    widget::{
+        attribute::{
+            find_callback,
+            util::{find_nodes, is_multi_select},
+            TreeNode,
+        },
+        event::{ExpandEvent, NodeSelectEvent},
+    },
+    AttribKey, Attribute,
+};
+use gdk_pixbuf::Pixbuf;
+use glib::{StaticType, ToValue};
+use gtk::{
+    prelude::*, Adjustment, CellRendererPixbuf, CellRendererText,
+    ScrolledWindow, SelectionMode, TreeIter, TreeModel, TreeStore, TreeView,
+    TreeViewColumn,
+};
+use std::fmt::Debug;
+
+const ID_COLUMN: u32 = 0;
+const LABEL_COLUMN: u32 = 1;
+const ICON_COLUMN: u32 = 2;
+
+/// the data key of the store, which is set while the nodes of the app are set
+const SETTING_NODES: &str = "sauron-native-setting-nodes";
+
+/// create the tree view of the nodes in a scrolled window
+pub(crate) fn tree_view<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+) -> ScrolledWindow
+where
+    MSG: Debug + 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let store = TreeStore::new(&[
+        String::static_type(),
+        String::static_type(),
+        Pixbuf::static_type(),
+    ]);
+    let tree_view = TreeView::new_with_model(&store);
+    tree_view.set_headers_visible(false);
+
+    let tree_column = TreeViewColumn::new();
+    let icon_renderer = CellRendererPixbuf::new();
+    tree_column.pack_start(&icon_renderer, false);
+    tree_column.add_attribute(&icon_renderer, "pixbuf", ICON_COLUMN as i32);
+    let label_renderer = CellRendererText::new();
+    tree_column.pack_start(&label_renderer, true);
+    tree_column.add_attribute(&label_renderer, "text", LABEL_COLUMN as i32);
+    tree_view.append_column(&tree_column);
+
+    set_multi_select(&tree_view, is_multi_select(attrs));
+    set_nodes(&tree_view, find_nodes(attrs));
+
+    if let Some(callbacks) = find_callback(AttribKey::ExpandEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            tree_view.connect_row_expanded(move |tree_view, iter, _path| {
+                if let Some(id) = user_changed_node(tree_view, iter) {
+                    let msg = cb_clone.emit(ExpandEvent::new(id, true));
+                    program_clone.dispatch(msg);
+                }
+            });
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            tree_view.connect_row_collapsed(move |tree_view, iter, _path| {
+                if let Some(id) = user_changed_node(tree_view, iter) {
+                    let msg = cb_clone.emit(ExpandEvent::new(id, false));
+                    program_clone.dispatch(msg);
+                }
+            });
+        }
+    }
+
+    if let Some(callbacks) = find_callback(AttribKey::NodeSelectEvent, attrs) {
+        for cb in callbacks {
+            let cb_clone = cb.clone();
+            let program_clone = program.clone();
+            tree_view.get_selection().connect_changed(move |selection| {
+                let (paths, model) = selection.get_selected_rows();
+                if is_setting_nodes(&model) {
+                    return;
+                }
+                let ids = paths
+                    .iter()
+                    .filter_map(|path| model.get_iter(path))
+                    .filter_map(|iter| node_id(&model, &iter))
+                    .collect();
+                let msg = cb_clone.emit(NodeSelectEvent::new(ids));
+                program_clone.dispatch(msg);
+            });
+        }
+    }
+
+    let scroll = ScrolledWindow::new(None::<&Adjustment>, None::<&Adjustment>);
+    scroll.add(&tree_view);
+    scroll
+}
+
+/// the id of the node in the row, none for the placeholder rows
+fn node_id(model: &TreeModel, iter: &TreeIter) -> Option<String> {
+    model
+        .get_value(iter, ID_COLUMN as i32)
+        .get::<String>()
+        .ok()
+        .flatten()
+        .filter(|id| !id.is_empty())
+}
+
+/// the id of the node which is expanded or collapsed by the user
+fn user_changed_node(tree_view: &TreeView, iter: &TreeIter) -> Option<String> {
+    let model = tree_view.get_model()?;
+    if is_setting_nodes(&model) {
+        None
+    } else {
+        node_id(&model, iter)
+    }
+}
+
+fn is_setting_nodes(model: &TreeModel) -> bool {
+    unsafe { model.get_data::<bool>(SETTING_NODES) }.is_some()
+}
+
+/// set whether more than one node can be selected
+pub(crate) fn set_multi_select(tree_view: &TreeView, multi_select: bool) {
+    tree_view.get_selection().set_mode(if multi_select {
+        SelectionMode::Multiple
+    } else {
+        SelectionMode::Single
+    });
+}
+
+/// refill the store with the nodes, then expand and select the nodes
+/// as they are in the app
+pub(crate) fn set_nodes(tree_view: &TreeView, nodes: &[TreeNode]) {
+    let store = tree_view
+        .get_model()
+        .and_then(|model| model.downcast::<TreeStore>().ok())
+        .expect("must have a tree store");
+    unsafe { store.set_data(SETTING_NODES, true) };
+    store.clear();
+    insert_nodes(&store, None, nodes);
+    // the parents are expanded before their children, since only the rows
+    // which are displayed can be selected
+    set_node_state(tree_view, &store, None, nodes);
+    let _: Option<bool> = unsafe { store.steal_data(SETTING_NODES) };
+}
+
+fn insert_nodes(
+    store: &TreeStore,
+    parent: Option<&TreeIter>,
+    nodes: &[TreeNode],
+) {
+    for node in nodes {
+        let icon = node.icon.as_ref().and_then(|bytes| table::to_pixbuf(bytes));
+        let iter = store.insert_with_values(
+            parent,
+            None,
+            &[ID_COLUMN, LABEL_COLUMN, ICON_COLUMN],
+            &[&node.id as &dyn ToValue, &node.label, &icon],
+        );
+        if node.has_children && node.children.is_empty() {
+            store.insert_with_values(
+                Some(&iter),
+                None,
+                &[ID_COLUMN, LABEL_COLUMN],
+                &[&String::new() as &dyn ToValue, &"…".to_string()],
+            );
+        } else {
+            insert_nodes(store, Some(&iter), &node.children);
+        }
+    }
+}
+
+fn set_node_state(
+    tree_view: &TreeView,
+    store: &TreeStore,
+    parent: Option<&TreeIter>,
+    nodes: &[TreeNode],
+) {
+    for (index, node) in nodes.iter().enumerate() {
+        if let Some(iter) = store.iter_nth_child(parent, index as i32) {
+            if node.expanded {
+                if let Some(path) = store.get_path(&iter) {
+                    tree_view.expand_row(&path, false);
+                }
+            }
+            if node.selected {
+                tree_view.get_selection().select_iter(&iter);
+            }
+            set_node_state(tree_view, store, Some(&iter), &node.children);
+        }
+    }
+}
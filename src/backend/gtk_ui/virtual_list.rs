@@ -0,0 +1,112 @@
+//! The virtual list is a scrolled window of a box which is as tall as all the rows.
+//! The built rows are in an inner box, which is pushed down to the offset of the first
+//! built row, so the rows are displayed where they would be if all the rows were built.
+//!
+//! ScrolledWindow -> Viewport -> Box(content) -> Box(rows)
+use super::{Dispatch, GtkWidget};
+use crate::{
+    widget::{
+        attribute::{find_callback, find_value, util::find_scroll_top},
+        event::ScrollEvent,
+    },
+    AttribKey, Attribute,
+};
+use gtk::{
+    prelude::*, Adjustment, Container, Orientation, ScrolledWindow, Widget,
+};
+use std::fmt::Debug;
+
+/// create the virtual list with the widgets of the built rows
+pub(crate) fn virtual_list<MSG, DSP>(
+    program: &DSP,
+    attrs: &[Attribute<MSG>],
+    widget_children: &[GtkWidget],
+) -> ScrolledWindow
+where
+    MSG: Debug + 'static,
+    DSP: Clone + Dispatch<MSG> + 'static,
+{
+    let rows_box = gtk::Box::new(Orientation::Vertical, 0);
+    for child in widget_children.iter() {
+        if let Some(child_widget) = child.as_widget() {
+            rows_box.add(child_widget);
+        }
+    }
+    let content = gtk::Box::new(Orientation::Vertical, 0);
+    content.pack_start(&rows_box, false, false, 0);
+
+    let scroll = ScrolledWindow::new(None::<&Adjustment>, None::<&Adjustment>);
+    scroll.add(&content);
+    if let Some(content_height) =
+        find_value(AttribKey::ContentHeight, attrs).and_then(|v| v.as_f64())
+    {
+        set_content_height(scroll.upcast_ref(), content_height);
+    }
+    if let Some(rows_offset) =
+        find_value(AttribKey::RowsOffset, attrs).and_then(|v| v.as_f64())
+    {
+        set_rows_offset(scroll.upcast_ref(), rows_offset);
+    }
+    set_scroll_top(scroll.upcast_ref(), find_scroll_top(attrs));
+
+    if let Some(callbacks) = find_callback(AttribKey::ScrollEvent, attrs) {
+        if let Some(adjustment) = scroll.get_vadjustment() {
+            for cb in callbacks {
+                let cb_clone = cb.clone();
+                let program_clone = program.clone();
+                adjustment.connect_value_changed(move |adjustment| {
+                    let scroll_event = ScrollEvent::new(adjustment.get_value());
+                    let msg = cb_clone.emit(scroll_event);
+                    program_clone.dispatch(msg);
+                });
+            }
+        }
+    }
+    scroll
+}
+
+/// the box which is as tall as all the rows
+fn content_box(scroll: &Widget) -> gtk::Box {
+    scroll
+        .downcast_ref::<Container>()
+        .expect("must be a scrolled window")
+        .get_children()
+        .first()
+        .and_then(|view_port| view_port.downcast_ref::<Container>())
+        .expect("must have a view port")
+        .get_children()
+        .first()
+        .and_then(|content| content.downcast_ref::<gtk::Box>().cloned())
+        .expect("must have a content box inside the view port")
+}
+
+/// the box where the widgets of the built rows are added into
+pub(crate) fn rows_box(scroll: &Widget) -> gtk::Box {
+    content_box(scroll)
+        .get_children()
+        .first()
+        .and_then(|rows_box| rows_box.downcast_ref::<gtk::Box>().cloned())
+        .expect("must have a box of the rows")
+}
+
+/// set the height of all the rows, which is the height scrolled through
+pub(crate) fn set_content_height(scroll: &Widget, content_height: f64) {
+    content_box(scroll).set_size_request(-1, content_height as i32);
+}
+
+/// push the built rows down to the offset of the first built row
+pub(crate) fn set_rows_offset(scroll: &Widget, rows_offset: f64) {
+    rows_box(scroll).set_margin_top(rows_offset as i32);
+}
+
+/// scroll to the offset set by the app, unless it is already scrolled there
+pub(crate) fn set_scroll_top(scroll: &Widget, scroll_top: f64) {
+    let adjustment = scroll
+        .downcast_ref::<ScrolledWindow>()
+        .and_then(|scroll| scroll.get_vadjustment());
+    if let Some(adjustment) = adjustment {
+        if (adjustment.get_value() - scroll_top).abs() >= 1.0 {
+            adjustment.set_value(scroll_top);
+        }
+    }
+}
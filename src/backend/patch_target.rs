@@ -0,0 +1,735 @@
+//! The shared driver which applies the patches into the actual widgets of a backend.
+//!
+//! A backend implements the primitive operations of `PatchTarget`, then `apply_patches`
+//! walks the patches, locates the nodes to be patched and calls the primitives.
+//!
+//! The patches are applied starting from the last node in the tree, and the native children
+//! of each patched parent are tracked, so the nodes that are yet to be patched can still be
+//! located after nodes are inserted, removed or moved.
+use crate::{
+    widget::attribute::util, AttribKey, Attribute, Node, Patch, Value, Widget,
+};
+use mt_dom::patch::{
+    AddAttributes, AppendChildren, ChangeText, InsertNode, RemoveAttributes,
+    ReplaceNode,
+};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+};
+
+/// A node in the current view, which is to be patched
+pub struct Target<'a, MSG> {
+    /// the child indexes from the root node, the root node has an empty path
+    pub path: Vec<usize>,
+    /// the nodes from the root node down to the target node
+    pub nodes: Vec<&'a Node<MSG>>,
+}
+
+impl<'a, MSG> Target<'a, MSG> {
+    /// locate the node at the path in the root node
+    pub fn new(root: &'a Node<MSG>, path: Vec<usize>) -> Self {
+        let mut nodes = vec![root];
+        let mut node = root;
+        for child_idx in path.iter() {
+            node = node
+                .get_children()
+                .and_then(|children| children.get(*child_idx))
+                .expect("must have a child at the path");
+            nodes.push(node);
+        }
+        Target { path, nodes }
+    }
+
+    /// the node being patched
+    pub fn node(&self) -> &'a Node<MSG> {
+        self.nodes.last().expect("must have the root node")
+    }
+
+    /// the widget of the node being patched
+    pub fn tag(&self) -> Option<&'a Widget> {
+        self.node().tag()
+    }
+
+    /// whether this is the root node of the view
+    pub fn is_root(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    /// returns the parent and the index of this node in the parent,
+    /// `None` if this is the root node
+    pub fn parent(&self) -> Option<(Target<'a, MSG>, usize)> {
+        let (child_idx, parent_path) = self.path.split_last()?;
+        let parent = Target {
+            path: parent_path.to_vec(),
+            nodes: self.nodes[0..self.nodes.len() - 1].to_vec(),
+        };
+        Some((parent, *child_idx))
+    }
+}
+
+/// The primitive operations that a backend implements to be able to apply patches.
+pub trait PatchTarget<MSG> {
+    /// the actual widget of the backend
+    type Widget;
+
+    /// create the actual widget of the node, including its children
+    fn create(&mut self, node: &Node<MSG>) -> Self::Widget;
+
+    /// set the attribute to the widget of the target
+    fn set_attribute(&mut self, target: &Target<MSG>, attr: &Attribute<MSG>);
+
+    /// remove the attribute from the widget of the target
+    fn remove_attribute(&mut self, target: &Target<MSG>, attr: &Attribute<MSG>);
+
+    /// insert the child widget at the index of the parent,
+    /// an index equal to the number of children appends the child.
+    /// The node of the child is passed for the containers which display the attributes
+    /// of their children, such as the label of the tabs in a tab_box
+    fn insert_child(
+        &mut self,
+        parent: &Target<MSG>,
+        index: usize,
+        node: &Node<MSG>,
+        child: Self::Widget,
+    );
+
+    /// remove the child widget at the index of the parent
+    fn remove_child(&mut self, parent: &Target<MSG>, index: usize);
+
+    /// replace the widget of the target with the replacement created from the node
+    fn replace(
+        &mut self,
+        target: &Target<MSG>,
+        node: &Node<MSG>,
+        replacement: Self::Widget,
+    );
+
+    /// move the child widget of the parent from one index to the other
+    fn move_child(&mut self, parent: &Target<MSG>, from: usize, to: usize);
+}
+
+/// A child in the native children of a parent
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Slot {
+    /// a child which is in the current view, identified by its index in the parent
+    Current(usize),
+    /// a child which is inserted by the patches
+    New,
+    /// a child which is removed from the native children,
+    /// kept so nodes can still be inserted before it
+    Removed(usize),
+}
+
+/// Tracks the native children of a parent while the patches are applied,
+/// so the index of the native child can be located from the index in the current view
+struct Children<'a> {
+    slots: Vec<Slot>,
+    /// keyed children which are removed, but will be moved to where the same key is inserted
+    detached: Vec<(&'a Value, usize)>,
+}
+
+impl<'a> Children<'a> {
+    fn new(len: usize) -> Self {
+        Children {
+            slots: (0..len).map(Slot::Current).collect(),
+            detached: vec![],
+        }
+    }
+
+    /// the index in the slots of the child from the current view
+    fn slot_index(&self, child_idx: usize) -> usize {
+        self.slots
+            .iter()
+            .position(|slot| {
+                *slot == Slot::Current(child_idx)
+                    || *slot == Slot::Removed(child_idx)
+            })
+            .expect("must have the child")
+    }
+
+    /// the native index of the slot, the removed children are not counted
+    fn native_index(&self, slot_index: usize) -> usize {
+        self.slots[0..slot_index]
+            .iter()
+            .filter(|slot| !matches!(slot, Slot::Removed(_)))
+            .count()
+    }
+
+    /// the native index of the child from the current view
+    fn position(&self, child_idx: usize) -> usize {
+        self.native_index(self.slot_index(child_idx))
+    }
+
+    /// remove the child from the current view and return its native index
+    fn remove(&mut self, child_idx: usize) -> usize {
+        let slot_index = self.slot_index(child_idx);
+        self.slots[slot_index] = Slot::Removed(child_idx);
+        self.native_index(slot_index)
+    }
+
+    /// take the detached child which has this key
+    fn take_detached(&mut self, key: Option<&Value>) -> Option<usize> {
+        let key = key?;
+        let pos = self.detached.iter().position(|(k, _)| *k == key)?;
+        Some(self.detached.remove(pos).1)
+    }
+}
+
+/// the native children of the parents which are patched, keyed by the path in the current view
+struct Parents<'a, MSG> {
+    /// the native path and the nodes above the root of the patched tree,
+    /// which are empty unless the patches are applied into a moved child
+    ancestors: Target<'a, MSG>,
+    children: BTreeMap<Vec<usize>, Children<'a>>,
+}
+
+impl<'a, MSG> Parents<'a, MSG> {
+    fn children(&mut self, parent: &Target<'a, MSG>) -> &mut Children<'a> {
+        let len = parent
+            .node()
+            .get_children()
+            .map(|children| children.len())
+            .unwrap_or(0);
+        self.children
+            .entry(parent.path.clone())
+            .or_insert_with(|| Children::new(len))
+    }
+
+    /// convert the path in the current view into the path of the native widgets
+    fn native_path(&self, path: &[usize]) -> Vec<usize> {
+        path.iter()
+            .enumerate()
+            .map(|(depth, child_idx)| {
+                match self.children.get(&path[0..depth]) {
+                    Some(children) => children.position(*child_idx),
+                    None => *child_idx,
+                }
+            })
+            .collect()
+    }
+
+    /// the target with its path converted into the path of the native widgets
+    fn native(&self, target: &Target<'a, MSG>) -> Target<'a, MSG> {
+        let mut path = self.ancestors.path.clone();
+        path.extend(self.native_path(&target.path));
+        let mut nodes = self.ancestors.nodes.clone();
+        nodes.extend(target.nodes.iter());
+        Target { path, nodes }
+    }
+}
+
+fn get_key<MSG>(node: &Node<MSG>) -> Option<&Value> {
+    node.as_element_ref()
+        .and_then(|element| util::get_key(element))
+}
+
+/// apply the patches, which are computed from the current view,
+/// into the actual widgets of the patch target
+///
+/// A keyed child which is removed and then inserted with the same key in the same parent
+/// is moved instead, so the native widget keeps its state.
+/// If the content of the keyed child has changed, the changes are patched into the moved widget.
+pub fn apply_patches<MSG, T>(
+    patch_target: &mut T,
+    current_vdom: &Node<MSG>,
+    patches: &[Patch<MSG>],
+) where
+    T: PatchTarget<MSG>,
+{
+    let ancestors = Target {
+        path: vec![],
+        nodes: vec![],
+    };
+    apply_patches_at(patch_target, ancestors, current_vdom, patches);
+}
+
+/// apply the patches of the tree, which is located below the ancestors
+fn apply_patches_at<'a, MSG, T>(
+    patch_target: &mut T,
+    ancestors: Target<'a, MSG>,
+    current_vdom: &'a Node<MSG>,
+    patches: &[Patch<MSG>],
+) where
+    T: PatchTarget<MSG>,
+{
+    let node_idxs: BTreeSet<usize> =
+        patches.iter().map(|patch| patch.node_idx()).collect();
+    let paths = find_paths(current_vdom, &node_idxs);
+    let path_of = |patch: &Patch<MSG>| {
+        paths
+            .get(&patch.node_idx())
+            .expect("must have a path to the node")
+            .clone()
+    };
+
+    // the keys of the nodes inserted in each parent
+    let mut inserted_keys: BTreeMap<Vec<usize>, Vec<&Value>> = BTreeMap::new();
+    for patch in patches {
+        match patch {
+            Patch::InsertNode(InsertNode { node, .. }) => {
+                let mut parent_path = path_of(patch);
+                parent_path.pop();
+                if let Some(key) = get_key(node) {
+                    inserted_keys.entry(parent_path).or_default().push(key);
+                }
+            }
+            Patch::AppendChildren(AppendChildren { children, .. }) => {
+                let keys = inserted_keys.entry(path_of(patch)).or_default();
+                keys.extend(children.iter().filter_map(|(_, c)| get_key(c)));
+            }
+            _ => (),
+        }
+    }
+
+    // inserting before a node is done after the other patches of that node,
+    // since it shifts the location of the node
+    let mut patches: Vec<&Patch<MSG>> = patches.iter().collect();
+    patches.sort_by_key(|patch| {
+        (
+            Reverse(patch.node_idx()),
+            matches!(patch, Patch::InsertNode(_)),
+        )
+    });
+
+    let mut parents = Parents {
+        ancestors,
+        children: BTreeMap::new(),
+    };
+
+    for patch in patches {
+        let target = Target::new(current_vdom, path_of(patch));
+        match patch {
+            Patch::AddAttributes(AddAttributes { attrs, .. }) => {
+                let target = parents.native(&target);
+                for attr in attrs {
+                    patch_target.set_attribute(&target, attr);
+                }
+            }
+            Patch::RemoveAttributes(RemoveAttributes { attrs, .. }) => {
+                let target = parents.native(&target);
+                for attr in attrs {
+                    patch_target.remove_attribute(&target, attr);
+                }
+            }
+            Patch::AppendChildren(AppendChildren { children, .. }) => {
+                let native_target = parents.native(&target);
+                for (_new_node_idx, child) in children {
+                    insert_or_move(
+                        patch_target,
+                        &native_target,
+                        &target,
+                        parents.children(&target),
+                        None,
+                        child,
+                    );
+                }
+            }
+            Patch::InsertNode(InsertNode { node, .. }) => {
+                let (parent, child_idx) = target
+                    .parent()
+                    .expect("can not insert a node before the root node");
+                let native_parent = parents.native(&parent);
+                let parent_children = parents.children(&parent);
+                insert_or_move(
+                    patch_target,
+                    &native_parent,
+                    &parent,
+                    parent_children,
+                    Some(child_idx),
+                    node,
+                );
+            }
+            Patch::RemoveNode(_) => {
+                let (parent, child_idx) =
+                    target.parent().expect("can not remove the root node");
+                let native_parent = parents.native(&parent);
+                let is_moved = match get_key(target.node()) {
+                    Some(key) => inserted_keys
+                        .get(&parent.path)
+                        .map(|keys| keys.contains(&key))
+                        .unwrap_or(false),
+                    None => false,
+                };
+                let parent_children = parents.children(&parent);
+                if is_moved {
+                    let key = get_key(target.node()).expect("must have a key");
+                    parent_children.detached.push((key, child_idx));
+                } else {
+                    let index = parent_children.remove(child_idx);
+                    patch_target.remove_child(&native_parent, index);
+                }
+            }
+            Patch::ReplaceNode(ReplaceNode { replacement, .. }) => {
+                let replacement_widget = patch_target.create(replacement);
+                patch_target.replace(
+                    &parents.native(&target),
+                    replacement,
+                    replacement_widget,
+                );
+            }
+            // the native backends display text with a `paragraph` or a `text_label`,
+            // so a text node in the view has no widget which can be changed
+            Patch::ChangeText(ChangeText { new, .. }) => log::warn!(
+                "text nodes are not supported, the text is not changed into: {:?}",
+                new.text
+            ),
+        }
+    }
+
+    // detached children which are not inserted back are removed
+    let detached: Vec<(Vec<usize>, usize)> = parents
+        .children
+        .iter()
+        .flat_map(|(path, children)| {
+            children
+                .detached
+                .iter()
+                .map(move |(_key, child_idx)| (path.clone(), *child_idx))
+        })
+        .collect();
+    for (path, child_idx) in detached {
+        let parent = Target::new(current_vdom, path);
+        let native_parent = parents.native(&parent);
+        let index = parents.children(&parent).remove(child_idx);
+        patch_target.remove_child(&native_parent, index);
+    }
+}
+
+/// insert the node before the child at `before_idx` of the current view,
+/// or after the last child if there is no `before_idx`.
+/// A detached child with the same key is moved there instead of creating a new widget.
+fn insert_or_move<'a, MSG, T>(
+    patch_target: &mut T,
+    native_parent: &Target<'a, MSG>,
+    parent: &Target<'a, MSG>,
+    parent_children: &mut Children<'a>,
+    before_idx: Option<usize>,
+    node: &Node<MSG>,
+) where
+    T: PatchTarget<MSG>,
+{
+    let slot_index_of = |parent_children: &Children| match before_idx {
+        Some(before_idx) => parent_children.slot_index(before_idx),
+        None => parent_children.slots.len(),
+    };
+    match parent_children.take_detached(get_key(node)) {
+        Some(child_idx) => {
+            let from = parent_children.position(child_idx);
+            let from_slot = parent_children.slot_index(child_idx);
+            let slot = parent_children.slots.remove(from_slot);
+            let to_slot = slot_index_of(parent_children);
+            let to = parent_children.native_index(to_slot);
+            parent_children.slots.insert(to_slot, slot);
+            if from != to {
+                patch_target.move_child(native_parent, from, to);
+            }
+            let old_node = parent
+                .node()
+                .get_children()
+                .and_then(|children| children.get(child_idx))
+                .expect("must have the moved child");
+            // the changes are patched into the moved widget, so it keeps its state
+            let patches =
+                mt_dom::diff_with_key(old_node, node, &AttribKey::Key);
+            if !patches.is_empty() {
+                let mut path = native_parent.path.clone();
+                path.push(to);
+                let ancestors = Target {
+                    path,
+                    nodes: native_parent.nodes.clone(),
+                };
+                apply_patches_at(patch_target, ancestors, old_node, &patches);
+            }
+        }
+        None => {
+            let slot_index = slot_index_of(parent_children);
+            let index = parent_children.native_index(slot_index);
+            let child_widget = patch_target.create(node);
+            patch_target.insert_child(native_parent, index, node, child_widget);
+            parent_children.slots.insert(slot_index, Slot::New);
+        }
+    }
+}
+
+/// returns the path of child indexes for each of the node_idx,
+/// the node_idx is counted in depth first order starting from the root node
+fn find_paths<MSG>(
+    root: &Node<MSG>,
+    node_idxs: &BTreeSet<usize>,
+) -> BTreeMap<usize, Vec<usize>> {
+    let mut paths = BTreeMap::new();
+    let mut cur_node_idx = 0;
+    find_paths_recursive(
+        root,
+        &mut vec![],
+        &mut cur_node_idx,
+        node_idxs,
+        &mut paths,
+    );
+    paths
+}
+
+fn find_paths_recursive<MSG>(
+    node: &Node<MSG>,
+    path: &mut Vec<usize>,
+    cur_node_idx: &mut usize,
+    node_idxs: &BTreeSet<usize>,
+    paths: &mut BTreeMap<usize, Vec<usize>>,
+) {
+    if node_idxs.contains(cur_node_idx) {
+        paths.insert(*cur_node_idx, path.clone());
+    }
+    if let Some(children) = node.get_children() {
+        for (child_idx, child) in children.iter().enumerate() {
+            *cur_node_idx += 1;
+            path.push(child_idx);
+            find_paths_recursive(child, path, cur_node_idx, node_idxs, paths);
+            path.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::{
+        attribute::{key, label},
+        button, checkbox, column, row,
+    };
+
+    /// a widget of the mock backend, identified by its label
+    #[derive(Debug, Clone, PartialEq)]
+    struct MockWidget {
+        label: String,
+        children: Vec<MockWidget>,
+    }
+
+    impl MockWidget {
+        fn from_node(node: &Node<()>) -> Self {
+            let label = node
+                .get_attribute_value(&AttribKey::Label)
+                .and_then(|values| values.first().map(|v| v.to_string()))
+                .unwrap_or_else(|| format!("{:?}", node.tag()));
+            MockWidget {
+                label,
+                children: node
+                    .get_children()
+                    .unwrap_or(&[])
+                    .iter()
+                    .map(MockWidget::from_node)
+                    .collect(),
+            }
+        }
+
+        fn at(&mut self, path: &[usize]) -> &mut MockWidget {
+            match path.split_first() {
+                Some((child_idx, rest)) => self.children[*child_idx].at(rest),
+                None => self,
+            }
+        }
+    }
+
+    /// applies the patches into the mock widgets and records the operations
+    struct Recorder {
+        root: MockWidget,
+        ops: Vec<String>,
+    }
+
+    impl PatchTarget<()> for Recorder {
+        type Widget = MockWidget;
+
+        fn create(&mut self, node: &Node<()>) -> MockWidget {
+            let widget = MockWidget::from_node(node);
+            self.ops.push(format!("create {}", widget.label));
+            widget
+        }
+
+        fn set_attribute(&mut self, target: &Target<()>, attr: &Attribute<()>) {
+            if *attr.name() == AttribKey::Label {
+                let value = attr.get_plain()[0].to_string();
+                self.root.at(&target.path).label = value;
+            }
+            self.ops
+                .push(format!("set {:?} {}", target.path, attr.name()));
+        }
+
+        fn remove_attribute(
+            &mut self,
+            target: &Target<()>,
+            attr: &Attribute<()>,
+        ) {
+            self.ops.push(format!(
+                "remove_attr {:?} {}",
+                target.path,
+                attr.name()
+            ));
+        }
+
+        fn insert_child(
+            &mut self,
+            parent: &Target<()>,
+            index: usize,
+            _node: &Node<()>,
+            child: MockWidget,
+        ) {
+            self.root.at(&parent.path).children.insert(index, child);
+            self.ops.push(format!("insert {:?} {}", parent.path, index));
+        }
+
+        fn remove_child(&mut self, parent: &Target<()>, index: usize) {
+            self.root.at(&parent.path).children.remove(index);
+            self.ops.push(format!("remove {:?} {}", parent.path, index));
+        }
+
+        fn replace(
+            &mut self,
+            target: &Target<()>,
+            _node: &Node<()>,
+            replacement: MockWidget,
+        ) {
+            *self.root.at(&target.path) = replacement;
+            self.ops.push(format!("replace {:?}", target.path));
+        }
+
+        fn move_child(&mut self, parent: &Target<()>, from: usize, to: usize) {
+            let children = &mut self.root.at(&parent.path).children;
+            let child = children.remove(from);
+            children.insert(to, child);
+            self.ops
+                .push(format!("move {:?} {} {}", parent.path, from, to));
+        }
+    }
+
+    /// apply the patches from the old view to the new view into the mock widgets,
+    /// which must then match the new view
+    fn patch(old: &Node<()>, new: &Node<()>) -> Vec<String> {
+        let mut recorder = Recorder {
+            root: MockWidget::from_node(old),
+            ops: vec![],
+        };
+        let patches = mt_dom::diff_with_key(old, new, &AttribKey::Key);
+        apply_patches(&mut recorder, old, &patches);
+        assert_eq!(recorder.root, MockWidget::from_node(new));
+        recorder.ops
+    }
+
+    fn keyed(labels: &[&'static str]) -> Node<()> {
+        column(
+            vec![],
+            labels
+                .iter()
+                .map(|l| button(vec![key(*l), label(*l)]))
+                .collect(),
+        )
+    }
+
+    fn unkeyed(labels: &[&'static str]) -> Node<()> {
+        column(
+            vec![],
+            labels.iter().map(|l| button(vec![label(*l)])).collect(),
+        )
+    }
+
+    #[test]
+    fn insert_node_before_a_sibling() {
+        let ops = patch(&keyed(&["a", "c"]), &keyed(&["a", "b", "c"]));
+        assert_eq!(ops, vec!["create b", "insert [] 1"]);
+    }
+
+    #[test]
+    fn remove_node() {
+        let ops = patch(&keyed(&["a", "b", "c"]), &keyed(&["a", "c"]));
+        assert_eq!(ops, vec!["remove [] 1"]);
+    }
+
+    #[test]
+    fn replace_the_root_node() {
+        let old = unkeyed(&["a"]);
+        let new = row(vec![], vec![button(vec![label("a")])]);
+        let ops = patch(&old, &new);
+        assert!(ops.contains(&"replace []".to_string()), "{:?}", ops);
+    }
+
+    #[test]
+    fn replace_a_child_node() {
+        let old = unkeyed(&["a", "b"]);
+        let new = column(
+            vec![],
+            vec![button(vec![label("a")]), checkbox(vec![label("b")])],
+        );
+        let ops = patch(&old, &new);
+        assert_eq!(ops, vec!["create b", "replace [1]"]);
+    }
+
+    #[test]
+    fn append_children() {
+        let ops = patch(&unkeyed(&["a"]), &unkeyed(&["a", "b", "c"]));
+        assert_eq!(
+            ops,
+            vec!["create b", "insert [] 1", "create c", "insert [] 2"]
+        );
+    }
+
+    #[test]
+    fn nodes_after_a_removed_sibling_are_located() {
+        let old = column(
+            vec![],
+            vec![
+                column(
+                    vec![],
+                    vec![
+                        button(vec![key("a"), label("a")]),
+                        button(vec![key("b"), label("b")]),
+                    ],
+                ),
+                button(vec![label("c")]),
+            ],
+        );
+        let new = column(
+            vec![],
+            vec![
+                column(vec![], vec![button(vec![key("b"), label("b2")])]),
+                button(vec![label("c2")]),
+            ],
+        );
+        patch(&old, &new);
+    }
+
+    #[test]
+    fn keyed_children_are_moved() {
+        let ops = patch(&keyed(&["a", "b", "c"]), &keyed(&["b", "c", "a"]));
+        assert!(ops.iter().any(|op| op.starts_with("move [] ")), "{:?}", ops);
+        // the widgets are moved, not created again
+        assert!(!ops.iter().any(|op| op.starts_with("create")), "{:?}", ops);
+    }
+
+    #[test]
+    fn changes_are_patched_into_the_moved_child() {
+        let old = column(
+            vec![],
+            vec![
+                button(vec![key("a"), label("a")]),
+                button(vec![key("b"), label("b")]),
+            ],
+        );
+        let new = column(
+            vec![],
+            vec![
+                button(vec![key("b"), label("b2")]),
+                button(vec![key("a"), label("a")]),
+            ],
+        );
+        let ops = patch(&old, &new);
+        assert!(ops.iter().any(|op| op.starts_with("move [] ")), "{:?}", ops);
+        // the moved widget is patched in place, it is not created again
+        assert!(
+            ops.iter()
+                .any(|op| op.starts_with("set ") && op.ends_with(" Label")),
+            "{:?}",
+            ops
+        );
+        assert!(!ops.iter().any(|op| op.starts_with("create")), "{:?}", ops);
+        assert!(!ops.iter().any(|op| op.starts_with("replace")), "{:?}", ops);
+    }
+}
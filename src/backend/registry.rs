@@ -0,0 +1,46 @@
+//! Keeps the custom widgets registered by name.
+//! Each backend stores its own custom widget trait object,
+//! so the same name can be registered once per backend.
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::BTreeMap,
+};
+
+thread_local! {
+    static CUSTOM_WIDGETS: RefCell<BTreeMap<(String, TypeId), Box<dyn Any>>> =
+        RefCell::new(BTreeMap::new());
+}
+
+/// register the custom widget with the name,
+/// replacing the previous one of the same type
+pub(crate) fn register<T>(name: &str, custom_widget: T)
+where
+    T: Clone + 'static,
+{
+    CUSTOM_WIDGETS.with(|custom_widgets| {
+        custom_widgets.borrow_mut().insert(
+            (name.to_string(), TypeId::of::<T>()),
+            Box::new(custom_widget),
+        );
+    })
+}
+
+/// lookup the custom widget of type `T` registered with the name.
+/// A warning is logged when there is none, the backends render a placeholder instead
+pub(crate) fn lookup_custom_widget<T>(name: &str) -> Option<T>
+where
+    T: Clone + 'static,
+{
+    let custom_widget = CUSTOM_WIDGETS.with(|custom_widgets| {
+        custom_widgets
+            .borrow()
+            .get(&(name.to_string(), TypeId::of::<T>()))
+            .and_then(|custom_widget| custom_widget.downcast_ref::<T>())
+            .cloned()
+    });
+    if custom_widget.is_none() {
+        log::warn!("no custom widget is registered with the name: {:?}", name);
+    }
+    custom_widget
+}
@@ -0,0 +1,235 @@
+//! A headless backend which keeps the rendered view in memory.
+//! It doesn't need a terminal, a gtk display or a browser, which makes it
+//! usable for testing components.
+//!
+//! Widgets are located with a `Selector`, then events are fired on them using
+//! synthetic events. The msgs emitted by the callbacks are dispatched to the
+//! program, so the resulting view can be asserted right after.
+use crate::{
+    widget::{
+        attribute::util::{find_callback, get_id, get_key},
+        event::{InputEvent, KeyEvent, MouseEvent},
+        snapshot,
+    },
+    AttribKey, Backend, Component, Dispatch, Event, Node, Program, Value,
+};
+use std::{cell::Ref, fmt};
+
+/// Locates a widget in the rendered view
+#[derive(Debug, Clone, PartialEq)]
+pub enum Selector {
+    /// the widget with the matching `AttribKey::Id`
+    Id(String),
+    /// the widget with the matching `AttribKey::Key`
+    Key(String),
+    /// the path of child indexes from the root node,
+    /// an empty path is the root node itself
+    Path(Vec<usize>),
+}
+
+/// Errors when firing an event on a widget
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestError {
+    /// no widget matches the selector
+    NotFound(Selector),
+    /// the widget has no listener attached for this event
+    NoListener(Selector, AttribKey),
+}
+
+impl fmt::Display for TestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TestError::NotFound(selector) => {
+                write!(f, "no widget found for {:?}", selector)
+            }
+            TestError::NoListener(selector, event) => {
+                write!(f, "widget {:?} has no {} listener", selector, event)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TestError {}
+
+/// A backend which renders the view in memory
+pub struct TestBackend<APP, MSG>
+where
+    MSG: 'static,
+{
+    program: Program<APP, MSG>,
+}
+
+impl<APP, MSG> TestBackend<APP, MSG>
+where
+    MSG: 'static,
+    APP: Component<MSG> + 'static,
+{
+    /// mount the app, the init Cmd of the app is executed right away
+    pub fn new(app: APP) -> Self {
+        let program = Program::new(app, None);
+        // the patches don't need to be applied anywhere,
+        // since the view is kept by the program
+        program.set_patch_applier(|_current_vdom, _patches| {});
+        program.init();
+        TestBackend { program }
+    }
+
+    /// returns a reference to the app
+    pub fn app(&self) -> Ref<'_, APP> {
+        self.program.app()
+    }
+
+    /// returns the currently rendered view
+    pub fn view(&self) -> Ref<'_, Node<MSG>> {
+        self.program.current_vdom()
+    }
+
+    /// render the current view into a stable text form,
+    /// which can be compared against a committed snapshot
+    pub fn snapshot(&self) -> String {
+        snapshot::render(&self.view())
+    }
+
+    /// dispatch a msg to the app, the view is updated right after
+    pub fn dispatch(&self, msg: MSG) {
+        self.program.dispatch(msg);
+    }
+
+    /// returns the path of the widget that matches the selector
+    pub fn find(&self, selector: &Selector) -> Option<Vec<usize>> {
+        match selector {
+            Selector::Path(path) => {
+                find_node_by_path(&self.view(), path).map(|_| path.clone())
+            }
+            Selector::Id(id) => find_path(&self.view(), &|node| {
+                node.as_element_ref()
+                    .and_then(get_id)
+                    .map(|v| v.to_string() == *id)
+                    .unwrap_or(false)
+            }),
+            Selector::Key(key) => find_path(&self.view(), &|node| {
+                node.as_element_ref()
+                    .and_then(get_key)
+                    .map(|v| v.to_string() == *key)
+                    .unwrap_or(false)
+            }),
+        }
+    }
+
+    /// returns a copy of the widget node that matches the selector
+    pub fn node(&self, selector: &Selector) -> Option<Node<MSG>> {
+        let path = self.find(selector)?;
+        find_node_by_path(&self.view(), &path).cloned()
+    }
+
+    /// returns the first plain value of the attribute of the widget
+    pub fn value(&self, selector: &Selector, key: AttribKey) -> Option<Value> {
+        let node = self.node(selector)?;
+        let values = node.get_attribute_value(&key)?;
+        values.first().map(|v| (*v).clone())
+    }
+
+    /// fire an event on the widget, the msgs emitted by the attached callbacks
+    /// are dispatched to the app
+    pub fn fire(
+        &self,
+        selector: &Selector,
+        event_key: AttribKey,
+        event: Event,
+    ) -> Result<(), TestError> {
+        let msgs = {
+            let view = self.view();
+            let path = self
+                .find(selector)
+                .ok_or_else(|| TestError::NotFound(selector.clone()))?;
+            let node = find_node_by_path(&view, &path)
+                .ok_or_else(|| TestError::NotFound(selector.clone()))?;
+            let attrs = node.get_attributes().unwrap_or(&[]);
+            let callbacks = find_callback(event_key.clone(), attrs)
+                .filter(|callbacks| !callbacks.is_empty())
+                .ok_or_else(|| {
+                    TestError::NoListener(selector.clone(), event_key.clone())
+                })?;
+            callbacks
+                .into_iter()
+                .map(|cb| cb.emit(event.clone()))
+                .collect::<Vec<MSG>>()
+        };
+        for msg in msgs {
+            self.dispatch(msg);
+        }
+        Ok(())
+    }
+
+    /// fire the `on_click` callbacks of the widget
+    pub fn click(
+        &self,
+        selector: &Selector,
+        mouse_event: MouseEvent,
+    ) -> Result<(), TestError> {
+        self.fire(selector, AttribKey::ClickEvent, Event::from(mouse_event))
+    }
+
+    /// fire the `on_input` callbacks of the widget
+    pub fn input(
+        &self,
+        selector: &Selector,
+        input_event: InputEvent,
+    ) -> Result<(), TestError> {
+        self.fire(selector, AttribKey::InputEvent, Event::from(input_event))
+    }
+
+    /// fire the `on_enter` callbacks of the widget
+    pub fn enter(
+        &self,
+        selector: &Selector,
+        key_event: KeyEvent,
+    ) -> Result<(), TestError> {
+        self.fire(selector, AttribKey::Activate, Event::from(key_event))
+    }
+}
+
+impl<APP, MSG> Backend<APP, MSG> for TestBackend<APP, MSG>
+where
+    MSG: 'static,
+    APP: Component<MSG> + 'static,
+{
+    /// mount the app, there is no event loop in this backend.
+    /// Use `TestBackend::new` to be able to fire events on the app.
+    fn init(app: APP) {
+        TestBackend::new(app);
+    }
+}
+
+/// traverse the children of the node following the path
+fn find_node_by_path<'a, MSG>(
+    node: &'a Node<MSG>,
+    path: &[usize],
+) -> Option<&'a Node<MSG>> {
+    match path.split_first() {
+        Some((child_idx, rest)) => {
+            let child = node.get_children()?.get(*child_idx)?;
+            find_node_by_path(child, rest)
+        }
+        None => Some(node),
+    }
+}
+
+/// return the path of the first node in depth first order, which matches the predicate
+fn find_path<MSG>(
+    node: &Node<MSG>,
+    predicate: &dyn Fn(&Node<MSG>) -> bool,
+) -> Option<Vec<usize>> {
+    if predicate(node) {
+        return Some(vec![]);
+    }
+    node.get_children()?
+        .iter()
+        .enumerate()
+        .find_map(|(child_idx, child)| {
+            find_path(child, predicate).map(|mut path| {
+                path.insert(0, child_idx);
+                path
+            })
+        })
+}
@@ -5,62 +5,387 @@ use crate::{
         attribute::{find_callback, find_value},
         Widget,
     },
-    AttribKey, Attribute, Backend, Component, Node,
+    AttribKey, Attribute, Backend, Component, Node, Program, WindowChange,
 };
+use dialog::{Centered, DIALOG_LAYER};
+use file_browser::{Action, FileBrowser, Outcome, FILE_BROWSER_LAYER};
 use image::GenericImageView;
 use mt_dom::Callback;
+use screen::Screen;
 use std::{
     cell::RefCell,
+    collections::BTreeMap,
     fmt::Debug,
-    io::{self},
-    marker::PhantomData,
+    io::{self, Write},
     rc::Rc,
 };
 use titik::{
-    renderer::Renderer, Button, Checkbox, Dispatch, FlexBox, GroupBox, Image,
-    Link, Radio, TextArea, TextInput, TextLabel, Widget as Control,
+    Button, Checkbox, Dispatch, FlexBox, GroupBox, Image, Link, Radio,
+    TextArea, TextInput, TextLabel, Widget as Control,
 };
 
+pub use custom_widget::{register_custom_widget, CustomWidget};
+
 mod apply_patches;
+mod canvas;
+mod combo_box;
 mod convert_event;
 mod convert_widget;
+mod custom_widget;
+mod dialog;
+mod file_browser;
+mod progress;
+mod renderer;
+mod rewrite;
+mod screen;
+mod slider;
+mod tab_box;
+mod table;
+mod tree;
+mod virtual_list;
 
 /// Titik Backend
 pub struct TitikBackend<APP, MSG>
 where
     MSG: 'static,
 {
-    app: Rc<RefCell<APP>>,
-    current_dom: Rc<RefCell<Node<MSG>>>,
-    _phantom_msg: PhantomData<MSG>,
+    program: Program<APP, MSG>,
+    /// the extra windows of the app are full screen layers stacked on top of the view,
+    /// this is the order of their ids from the bottom to the top
+    layers: RefCell<Vec<String>>,
+    /// the layer in the screen, `None` is the view of the app
+    displayed: RefCell<Option<String>>,
+    /// the layers which are not in the screen, each is a wrapper which contains its view.
+    /// The wrapper of the displayed layer is in the screen
+    hidden: RefCell<BTreeMap<Option<String>, Box<dyn titik::Widget<MSG>>>>,
+    /// the dialog in the view of the app, which is displayed in its own layer
+    dialog: RefCell<Option<Node<MSG>>>,
+    /// the file browser requested by the commands of the app, which is opened
+    /// once the root node is available
+    pending_file_browser: Rc<RefCell<Option<FileBrowser<MSG>>>>,
+    /// the file browser which is displayed on top of the other layers
+    file_browser: RefCell<Option<FileBrowser<MSG>>>,
 }
 
-impl<APP, MSG> TitikBackend<APP, MSG>
+impl<APP, MSG> Backend<APP, MSG> for TitikBackend<APP, MSG>
 where
     APP: Component<MSG> + 'static,
     MSG: Debug + 'static,
 {
+    fn init(app: APP) {
+        let mut stdout = io::stdout();
+        Self::set_terminal_title(&app.title());
+        let program = Program::new(app, None);
+        program.set_view_filter(rewrite::rewrite_view);
+        program.set_title_applier(Self::set_terminal_title);
+        let pending_file_browser = Rc::new(RefCell::new(None));
+        let pending_file_browser_clone = Rc::clone(&pending_file_browser);
+        program.set_file_dialog_applier(move |dialog, on_chosen| {
+            *pending_file_browser_clone.borrow_mut() =
+                Some(FileBrowser::new(dialog, on_chosen));
+        });
+        register_custom_widget::<MSG, _>(
+            table::SCROLL_BUTTON,
+            table::ScrollButtons,
+        );
+        register_custom_widget::<MSG, _>(
+            combo_box::POPUP_BUTTON,
+            combo_box::PopupButtons,
+        );
+        register_custom_widget::<MSG, _>(canvas::CANVAS, canvas::Canvases);
+        let vdom = program.current_vdom().clone();
+
+        let backend = Rc::new(TitikBackend {
+            program,
+            layers: RefCell::new(vec![]),
+            displayed: RefCell::new(None),
+            hidden: RefCell::new(BTreeMap::new()),
+            dialog: RefCell::new(None),
+            pending_file_browser,
+            file_browser: RefCell::new(None),
+        });
+        // the view is wrapped, so the root of the view can also be replaced
+        let mut layer = FlexBox::new();
+        layer.vertical();
+        layer.add_child(convert_widget::from_node_tree(vdom));
+        let backend_clone = Rc::clone(&backend);
+        let mut root_node =
+            Screen::new(move |root_node| backend_clone.redraw(root_node));
+        root_node.add_child(Box::new(layer));
+        // the msgs dispatched by the init cmd are queued, since titik
+        // needs the root_node to apply the patches
+        backend.program.init();
+        backend.process_pending_msgs(&mut root_node);
+        renderer::run(&mut stdout, &*backend, &mut root_node)
+            .expect("must run");
+    }
 }
 
-impl<APP, MSG> Backend<APP, MSG> for TitikBackend<APP, MSG>
+impl<APP, MSG> TitikBackend<APP, MSG>
 where
     APP: Component<MSG> + 'static,
     MSG: Debug + 'static,
 {
-    fn init(app: APP) {
+    /// set the title of the terminal window using the xterm escape sequence,
+    /// the title is only cosmetic so a failed write is logged and ignored
+    fn set_terminal_title(title: &str) {
         let mut stdout = io::stdout();
-        let vdom = app.view();
-        let current_dom = app.view();
-        let mut root_node = convert_widget::from_node_tree(vdom);
-
-        let backend = TitikBackend {
-            app: Rc::new(RefCell::new(app)),
-            current_dom: Rc::new(RefCell::new(current_dom)),
-            _phantom_msg: PhantomData,
+        if let Err(e) =
+            write!(stdout, "\x1b]0;{}\x07", title).and_then(|_| stdout.flush())
+        {
+            log::warn!("unable to set the terminal title: {}", e);
+        }
+    }
+
+    fn process_pending_msgs(&self, root_node: &mut dyn titik::Widget<MSG>) {
+        self.program
+            .process_pending_msgs_with(|current_vdom, patches| {
+                self.with_layer(&mut *root_node, None, |layer| {
+                    apply_patches::apply_patches(layer, current_vdom, patches)
+                });
+            });
+        self.update_dialog(&mut *root_node);
+        self.program.update_windows_with(|change| {
+            self.apply_window_change(&mut *root_node, change)
+        });
+        self.open_pending_file_browser(&mut *root_node);
+        self.display_top_layer(&mut *root_node);
+    }
+
+    /// redraw the view without updating the app,
+    /// used when only the state kept in the backend is changed
+    fn redraw(&self, root_node: &mut dyn titik::Widget<MSG>) {
+        self.program.redraw_with(|current_vdom, patches| {
+            self.with_layer(&mut *root_node, None, |layer| {
+                apply_patches::apply_patches(layer, current_vdom, patches)
+            });
+        });
+        self.update_dialog(&mut *root_node);
+        self.program.update_windows_with(|change| {
+            self.apply_window_change(&mut *root_node, change)
+        });
+        self.display_top_layer(&mut *root_node);
+    }
+
+    /// a new file browser replaces the file browser which is still open
+    fn open_pending_file_browser(
+        &self,
+        root_node: &mut dyn titik::Widget<MSG>,
+    ) {
+        let file_browser = self.pending_file_browser.borrow_mut().take();
+        if let Some(file_browser) = file_browser {
+            self.show_file_browser(root_node, &file_browser);
+            *self.file_browser.borrow_mut() = Some(file_browser);
+        }
+    }
+
+    /// display the view of the file browser in its layer,
+    /// the layer is added on top of the other layers if it is not open yet
+    fn show_file_browser(
+        &self,
+        root_node: &mut dyn titik::Widget<MSG>,
+        file_browser: &FileBrowser<MSG>,
+    ) {
+        let view = convert_widget::from_node_tree(file_browser.view());
+        let is_open = self
+            .layers
+            .borrow()
+            .iter()
+            .any(|layer| layer == FILE_BROWSER_LAYER);
+        if is_open {
+            self.with_layer(root_node, Some(FILE_BROWSER_LAYER), |layer| {
+                layer.take_child(0);
+                layer.add_child(view);
+            });
+        } else {
+            let mut layer = FlexBox::new();
+            layer.vertical();
+            layer.add_child(view);
+            self.open_layer(FILE_BROWSER_LAYER, Box::new(layer), &[]);
+        }
+    }
+
+    fn close_file_browser(&self, root_node: &mut dyn titik::Widget<MSG>) {
+        self.close_layer(root_node, FILE_BROWSER_LAYER);
+    }
+
+    /// display the dialog of the view in its layer,
+    /// the layer is opened and closed as the dialog appears and disappears from the view
+    fn update_dialog(&self, root_node: &mut dyn titik::Widget<MSG>) {
+        let new_dialog =
+            dialog::find_dialog(&self.program.current_vdom()).cloned();
+        let old_dialog = self.dialog.borrow_mut().take();
+        match (&old_dialog, &new_dialog) {
+            (None, Some(new_dialog)) => {
+                let mut layer = Centered::new();
+                layer.add_child(dialog::dialog_widget(new_dialog.clone()));
+                // the file browser stays on top of the dialog
+                self.open_layer(
+                    DIALOG_LAYER,
+                    Box::new(layer),
+                    &[FILE_BROWSER_LAYER],
+                );
+            }
+            (Some(old_dialog), Some(new_dialog)) => {
+                let patches = mt_dom::diff_with_key(
+                    old_dialog,
+                    new_dialog,
+                    &AttribKey::Key,
+                );
+                if !patches.is_empty() {
+                    self.with_layer(root_node, Some(DIALOG_LAYER), |layer| {
+                        apply_patches::apply_dialog_patches(
+                            layer, old_dialog, &patches,
+                        )
+                    });
+                }
+            }
+            (Some(_), None) => self.close_layer(root_node, DIALOG_LAYER),
+            (None, None) => (),
+        }
+        *self.dialog.borrow_mut() = new_dialog;
+    }
+
+    /// apply the action of the file browser,
+    /// the msg of the chosen files is dispatched when the user is done
+    fn apply_file_browser_action(
+        &self,
+        action: Action,
+        root_node: &mut dyn titik::Widget<MSG>,
+    ) {
+        let mut file_browser = self
+            .file_browser
+            .borrow_mut()
+            .take()
+            .expect("must have a file browser");
+        match file_browser.apply(action) {
+            Outcome::Redraw => {
+                self.show_file_browser(root_node, &file_browser);
+                *self.file_browser.borrow_mut() = Some(file_browser);
+            }
+            Outcome::Unchanged => {
+                *self.file_browser.borrow_mut() = Some(file_browser);
+            }
+            Outcome::Done(msg) => {
+                self.close_file_browser(root_node);
+                self.program.queue_msg(msg);
+                self.process_pending_msgs(root_node);
+            }
+        }
+    }
+
+    /// call `f` with the wrapper of the layer, which is in the screen if the layer is displayed
+    fn with_layer<F>(
+        &self,
+        root_node: &mut dyn titik::Widget<MSG>,
+        id: Option<&str>,
+        f: F,
+    ) where
+        F: FnOnce(&mut dyn titik::Widget<MSG>),
+    {
+        if self.displayed.borrow().as_deref() == id {
+            let layer = root_node
+                .children_mut()
+                .and_then(|children| children.get_mut(0))
+                .expect("must have a displayed layer");
+            f(layer.as_mut())
+        } else {
+            let key = id.map(|id| id.to_string());
+            let mut hidden = self.hidden.borrow_mut();
+            let layer = hidden.get_mut(&key).expect("must have the layer");
+            f(layer.as_mut())
+        }
+    }
+
+    fn apply_window_change(
+        &self,
+        root_node: &mut dyn titik::Widget<MSG>,
+        change: WindowChange<MSG>,
+    ) {
+        match change {
+            WindowChange::Open(window) => {
+                let mut layer = FlexBox::new();
+                layer.vertical();
+                layer.add_child(convert_widget::from_node_tree(
+                    window.view.clone(),
+                ));
+                // the dialog and the file browser stay on top of the windows
+                self.open_layer(
+                    &window.id,
+                    Box::new(layer),
+                    &[DIALOG_LAYER, FILE_BROWSER_LAYER],
+                );
+            }
+            WindowChange::Patch {
+                id,
+                current_vdom,
+                patches,
+            } => {
+                self.with_layer(root_node, Some(id), |layer| {
+                    apply_patches::apply_patches(layer, current_vdom, patches)
+                });
+            }
+            WindowChange::SetTitle { id, title } => {
+                if self.displayed.borrow().as_deref() == Some(id) {
+                    Self::set_terminal_title(title);
+                }
+            }
+            WindowChange::Close(id) => self.close_layer(root_node, id),
+        }
+    }
+
+    /// add the layer below the first of the layers in `below` which is open,
+    /// otherwise the layer is added on top of the other layers
+    fn open_layer(
+        &self,
+        id: &str,
+        layer: Box<dyn titik::Widget<MSG>>,
+        below: &[&str],
+    ) {
+        self.hidden.borrow_mut().insert(Some(id.to_string()), layer);
+        let mut layers = self.layers.borrow_mut();
+        let position = layers
+            .iter()
+            .position(|layer| below.contains(&layer.as_str()))
+            .unwrap_or(layers.len());
+        layers.insert(position, id.to_string());
+    }
+
+    fn close_layer(&self, root_node: &mut dyn titik::Widget<MSG>, id: &str) {
+        self.layers.borrow_mut().retain(|layer| layer != id);
+        if self.displayed.borrow().as_deref() == Some(id) {
+            self.display_top_layer(root_node);
+        }
+        self.hidden.borrow_mut().remove(&Some(id.to_string()));
+    }
+
+    /// swap the wrapper of the top most layer into the screen
+    fn display_top_layer(&self, root_node: &mut dyn titik::Widget<MSG>) {
+        let top = self.layers.borrow().last().cloned();
+        let displayed = self.displayed.borrow().clone();
+        if top == displayed {
+            return;
+        }
+        let layer = root_node
+            .take_child(0)
+            .expect("must have a displayed layer");
+        let mut hidden = self.hidden.borrow_mut();
+        hidden.insert(displayed, layer);
+        let top_layer = hidden.remove(&top).expect("must have the layer");
+        root_node.add_child(top_layer);
+        let title = match &top {
+            Some(id) => self
+                .program
+                .window_views()
+                .into_iter()
+                .find(|window| window.id == *id)
+                .map(|window| window.title),
+            None => Some(self.program.title()),
         };
-        let mut renderer =
-            Renderer::new(&mut stdout, Some(&backend), root_node.as_mut());
-        renderer.run().expect("must run");
+        if let Some(title) = title {
+            Self::set_terminal_title(&title);
+        }
+        *self.displayed.borrow_mut() = top;
     }
 }
 
@@ -72,22 +397,17 @@ where
     /// root_node is added as argument in this dispatch function so that they are in the same
     /// borrow, otherwise an AlreadyBorrowedError will be invoke at runtime.
     fn dispatch(&self, msg: MSG, root_node: &mut dyn titik::Widget<MSG>) {
-        eprintln!("dispatching... {:?}", msg);
-        self.app.borrow_mut().update(msg);
-        let new_view = self.app.borrow().view();
-        let current_view = self.app.borrow().view();
-
-        {
-            let previous_dom = self.current_dom.borrow();
-            let diff = mt_dom::diff_with_key(
-                &previous_dom,
-                &new_view,
-                &AttribKey::Key,
-            );
-            eprintln!("diff: {:#?}", diff);
-            apply_patches::apply_patches(&self, root_node, &diff);
+        let action = self
+            .file_browser
+            .borrow()
+            .as_ref()
+            .and_then(|file_browser| file_browser.take_action());
+        // the msg emitted alongside the action of the file browser is ignored
+        if let Some(action) = action {
+            self.apply_file_browser_action(action, root_node);
+            return;
         }
-
-        *self.current_dom.borrow_mut() = current_view;
+        self.program.queue_msg(msg);
+        self.process_pending_msgs(root_node);
     }
 }
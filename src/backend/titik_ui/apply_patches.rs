@@ -1,57 +1,201 @@
 use super::convert_widget;
-use crate::{AttribKey, Attribute, Patch, Widget};
-use mt_dom::patch::{AddAttributes, AppendChildren, RemoveNode};
-use std::fmt::Debug;
-use titik::{Button, TextArea};
+use super::custom_widget::CustomWidget;
+use super::progress::Animation;
+use crate::backend::{
+    patch_target::{self, PatchTarget, Target},
+    registry,
+};
+use crate::{AttribKey, Attribute, Node, Patch, Value, Widget};
+use std::{fmt::Debug, rc::Rc};
+use titik::{
+    Button, Checkbox, GroupBox, Image, Radio, TextArea, TextInput, TextLabel,
+};
 
-pub fn apply_patches<MSG, DSP>(
-    _program: &DSP,
+pub fn apply_patches<MSG>(
     root_node: &mut dyn titik::Widget<MSG>,
+    current_vdom: &Node<MSG>,
     patches: &[Patch<MSG>],
 ) where
     MSG: Debug + 'static,
 {
-    for patch in patches {
-        let patch_node_idx = patch.node_idx();
-        let widget: &mut dyn titik::Widget<MSG> =
-            titik::find_widget_mut(root_node, patch_node_idx)
-                .expect("must have a node to patch");
-        match patch {
-            Patch::AddAttributes(AddAttributes {
-                tag,
-                node_idx: _,
-                new_node_idx,
-                attrs,
-            }) => {
-                eprintln!("setting attributes...");
-                set_widget_attributes::<MSG>(tag, widget, attrs);
-            }
-            Patch::AppendChildren(AppendChildren {
-                tag,
-                node_idx: _,
-                children,
-            }) => {
-                eprintln!("adding children..");
-                for (child_idx, child) in children {
-                    let child_element =
-                        child.as_element_ref().expect("must be an element");
-                    let child_widget = convert_widget::from_node(
-                        &child_element.tag,
-                        &child_element.attrs,
-                    );
-                    eprintln!("added 1 {:?} to {:?}", child_widget, widget);
-                    let added = widget.add_child(child_widget);
-                    eprintln!("widget becomes: {:?}", widget);
-                    assert!(added);
-                }
+    let mut titik_target = TitikTarget {
+        root_node,
+        is_dialog_layer: false,
+    };
+    patch_target::apply_patches(&mut titik_target, current_vdom, patches);
+}
+
+/// apply the patches of the dialog into the dialog layer,
+/// the root node of `current_vdom` is the dialog
+pub fn apply_dialog_patches<MSG>(
+    root_node: &mut dyn titik::Widget<MSG>,
+    current_vdom: &Node<MSG>,
+    patches: &[Patch<MSG>],
+) where
+    MSG: Debug + 'static,
+{
+    let mut titik_target = TitikTarget {
+        root_node,
+        is_dialog_layer: true,
+    };
+    patch_target::apply_patches(&mut titik_target, current_vdom, patches);
+}
+
+/// patches the titik widgets, the root node is a wrapper which
+/// contains the widget of the view, so the view can be replaced
+struct TitikTarget<'a, MSG> {
+    root_node: &'a mut dyn titik::Widget<MSG>,
+    /// the dialogs in the views are empty placeholders, which are patched in their own layer.
+    /// In the dialog layer, the root node is the dialog
+    is_dialog_layer: bool,
+}
+
+impl<'a, MSG> TitikTarget<'a, MSG>
+where
+    MSG: 'static,
+{
+    /// the titik widget at the path of the target
+    fn widget_mut(
+        &mut self,
+        target: &Target<MSG>,
+    ) -> &mut dyn titik::Widget<MSG> {
+        let mut widget: &mut dyn titik::Widget<MSG> = self
+            .root_node
+            .children_mut()
+            .and_then(|children| children.get_mut(0))
+            .expect("must have the view inside the root node")
+            .as_mut();
+        for child_idx in target.path.iter() {
+            widget = widget
+                .children_mut()
+                .and_then(|children| children.get_mut(*child_idx))
+                .expect("must have a child at the path")
+                .as_mut();
+        }
+        widget
+    }
+
+    /// whether any of the nodes is a dialog which is not displayed in this layer,
+    /// the nodes inside such dialog are not patched here
+    fn in_dialog(&self, nodes: &[&Node<MSG>]) -> bool {
+        let skip = if self.is_dialog_layer { 1 } else { 0 };
+        nodes
+            .iter()
+            .skip(skip)
+            .any(|node| node.tag() == Some(&Widget::Dialog))
+    }
+}
+
+impl<'a, MSG> PatchTarget<MSG> for TitikTarget<'a, MSG>
+where
+    MSG: Debug + 'static,
+{
+    type Widget = Box<dyn titik::Widget<MSG>>;
+
+    fn create(&mut self, node: &Node<MSG>) -> Self::Widget {
+        convert_widget::from_node_tree(node.clone())
+    }
+
+    fn set_attribute(&mut self, target: &Target<MSG>, attr: &Attribute<MSG>) {
+        if self.in_dialog(&target.nodes) {
+            return;
+        }
+        let tag = target.tag().expect("must have a tag");
+        let widget = self.widget_mut(target);
+        set_widget_attributes::<MSG>(tag, widget, &[attr]);
+    }
+
+    fn remove_attribute(
+        &mut self,
+        target: &Target<MSG>,
+        attr: &Attribute<MSG>,
+    ) {
+        if self.in_dialog(&target.nodes) {
+            return;
+        }
+        let tag = target.tag().expect("must have a tag");
+        let widget = self.widget_mut(target);
+        remove_widget_attribute::<MSG>(tag, widget, attr);
+    }
+
+    fn insert_child(
+        &mut self,
+        parent: &Target<MSG>,
+        index: usize,
+        _node: &Node<MSG>,
+        child: Self::Widget,
+    ) {
+        if self.in_dialog(&parent.nodes) {
+            return;
+        }
+        let tag = parent.tag().expect("must have a tag");
+        let widget = self.widget_mut(parent);
+        insert_child(tag, widget, index, child);
+    }
+
+    fn remove_child(&mut self, parent: &Target<MSG>, index: usize) {
+        if self.in_dialog(&parent.nodes) {
+            return;
+        }
+        let widget = self.widget_mut(parent);
+        widget
+            .take_child(index)
+            .expect("must have a child to remove");
+    }
+
+    fn replace(
+        &mut self,
+        target: &Target<MSG>,
+        node: &Node<MSG>,
+        replacement: Self::Widget,
+    ) {
+        // the dialog itself can be replaced, since it has a placeholder
+        let ancestors = &target.nodes[..target.nodes.len() - 1];
+        if self.in_dialog(ancestors) {
+            return;
+        }
+        match target.parent() {
+            Some((parent, index)) => {
+                self.remove_child(&parent, index);
+                self.insert_child(&parent, index, node, replacement);
             }
-            Patch::RemoveNode(RemoveNode { tag: _, node_idx }) => {
-                titik::remove_widget(root_node, *node_idx);
+            None => {
+                self.root_node.take_child(0);
+                self.root_node.add_child(replacement);
             }
-            // todo for other patches here.
-            _ => eprintln!("todo for: {:?}", patch),
         }
     }
+
+    fn move_child(&mut self, parent: &Target<MSG>, from: usize, to: usize) {
+        if self.in_dialog(&parent.nodes) {
+            return;
+        }
+        let tag = parent.tag().expect("must have a tag");
+        let widget = self.widget_mut(parent);
+        let child = widget.take_child(from).expect("must have a child to move");
+        insert_child(tag, widget, to, child);
+    }
+}
+
+/// titik can only append a child, so the children after the index are taken out
+/// and added back after the child
+fn insert_child<MSG>(
+    tag: &Widget,
+    widget: &mut dyn titik::Widget<MSG>,
+    index: usize,
+    child: Box<dyn titik::Widget<MSG>>,
+) where
+    MSG: 'static,
+{
+    let children_len = widget.children_mut().map(|c| c.len()).unwrap_or(0);
+    let after: Vec<Box<dyn titik::Widget<MSG>>> = (index..children_len)
+        .filter_map(|_| widget.take_child(index))
+        .collect();
+    let added = convert_widget::add_child(tag, widget, child);
+    assert!(added, "{:?} must be able to contain children", tag);
+    for child in after {
+        convert_widget::add_child(tag, widget, child);
+    }
 }
 
 fn set_widget_attributes<MSG: 'static>(
@@ -59,40 +203,112 @@ fn set_widget_attributes<MSG: 'static>(
     widget: &mut dyn titik::Widget<MSG>,
     attrs: &[&Attribute<MSG>],
 ) {
-    match tag {
-        Widget::TextArea => {
-            let text_area: &mut TextArea<MSG> = widget
-                .as_any_mut()
-                .downcast_mut()
-                .expect("must be a textarea");
-            for att in attrs {
-                for value in att.get_plain() {
-                    match att.name() {
-                        AttribKey::Value => {
-                            text_area.set_value(&value.to_string());
-                        }
-                        _ => (),
-                    }
-                }
-            }
+    if let Widget::Custom(name) = tag {
+        if let Some(custom_widget) =
+            registry::lookup_custom_widget::<Rc<dyn CustomWidget<MSG>>>(name)
+        {
+            custom_widget.update_attributes(widget, attrs);
+        }
+        return;
+    }
+    for att in attrs {
+        for value in att.get_plain() {
+            set_widget_attribute(tag, widget, att.name(), value);
         }
-        Widget::Button => {
-            let btn: &mut Button<MSG> = widget
-                .as_any_mut()
-                .downcast_mut()
-                .expect("must be a button");
-
-            for att in attrs {
-                for value in att.get_plain() {
-                    match att.name() {
-                        AttribKey::Label => {
-                            btn.set_label(&value.to_string());
-                        }
-                        _ => (),
-                    }
+    }
+}
+
+/// reset the attribute of the widget to its default value
+fn remove_widget_attribute<MSG: 'static>(
+    tag: &crate::Widget,
+    widget: &mut dyn titik::Widget<MSG>,
+    attr: &Attribute<MSG>,
+) {
+    let default_value = match attr.name() {
+        AttribKey::Checked | AttribKey::Value
+            if *tag == Widget::Checkbox || *tag == Widget::Radio =>
+        {
+            Value::Bool(false)
+        }
+        AttribKey::Data => Value::Bytes(vec![]),
+        _ => Value::Str(""),
+    };
+    set_widget_attribute(tag, widget, attr.name(), &default_value);
+}
+
+fn set_widget_attribute<MSG: 'static>(
+    tag: &crate::Widget,
+    widget: &mut dyn titik::Widget<MSG>,
+    key: &AttribKey,
+    value: &Value,
+) {
+    match (tag, key) {
+        (Widget::TextArea, AttribKey::Value)
+        | (Widget::Paragraph, AttribKey::Value) => {
+            downcast_widget::<TextArea<MSG>, MSG>(widget)
+                .set_value(&value.to_string());
+        }
+        (Widget::TextInput, AttribKey::Value)
+        | (Widget::SearchInput, AttribKey::Value) => {
+            downcast_widget::<TextInput<MSG>, MSG>(widget)
+                .set_value(&value.to_string());
+        }
+        (Widget::Label, AttribKey::Value) => {
+            downcast_widget::<TextLabel<MSG>, MSG>(widget)
+                .set_value(&value.to_string());
+        }
+        (Widget::Button, AttribKey::Label) => {
+            downcast_widget::<Button<MSG>, MSG>(widget)
+                .set_label(&value.to_string());
+        }
+        (Widget::GroupBox, AttribKey::Label)
+        | (Widget::Dialog, AttribKey::Label) => {
+            downcast_widget::<GroupBox<MSG>, MSG>(widget)
+                .set_label(&value.to_string());
+        }
+        (Widget::Checkbox, AttribKey::Checked)
+        | (Widget::Checkbox, AttribKey::Value) => {
+            downcast_widget::<Checkbox<MSG>, MSG>(widget)
+                .set_checked(value.as_bool());
+        }
+        (Widget::Radio, AttribKey::Checked)
+        | (Widget::Radio, AttribKey::Value) => {
+            downcast_widget::<Radio<MSG>, MSG>(widget)
+                .set_checked(value.as_bool());
+        }
+        (Widget::Image, AttribKey::Data) => {
+            let image = downcast_widget::<Image<MSG>, MSG>(widget);
+            *image = match value.as_bytes() {
+                Some(bytes) if !bytes.is_empty() => {
+                    convert_widget::image_from_bytes(bytes)
                 }
-            }
+                _ => Image::new(vec![]),
+            };
         }
-        _ => eprintln!("todo for other widgets.. {:?}", tag),
+        (Widget::ProgressBar, AttribKey::Label) => {
+            downcast_widget::<Animation, MSG>(widget)
+                .set_label(value.to_string());
+        }
+        // the indeterminate progress bar doesn't display its fraction
+        (Widget::ProgressBar, AttribKey::Fraction) => (),
+        // the header and the page of the selected tab are patched as part of the view
+        (Widget::TabBox, AttribKey::Selected) => (),
+        // titik lays out its widgets by itself,
+        // so the style and the layout calculated for the view are not used
+        (_, AttribKey::Style) | (_, AttribKey::Layout) => (),
+        _ => log::warn!("setting {:?} is not supported in {:?}", key, tag),
     }
 }
+
+fn downcast_widget<'a, T, MSG>(
+    widget: &'a mut dyn titik::Widget<MSG>,
+) -> &'a mut T
+where
+    T: 'static,
+    MSG: 'static,
+{
+    widget
+        .as_any_mut()
+        .downcast_mut::<T>()
+        .expect("must be the widget of the tag")
+}
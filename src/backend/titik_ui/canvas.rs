@@ -0,0 +1,767 @@
+//! Titik doesn't have a canvas, so the commands are rasterized into braille characters
+//! and the canvas is displayed as a column of text labels, one for each line of characters.
+//! A character is 8 by 16 points of the canvas and has 2 by 4 dots, so a dot is 4 by 4 points.
+//!
+//! The terminal draws in a single color, so a color only decides if anything is drawn,
+//! which is not the case for a transparent color. The lines are a dot thick,
+//! the text is written in the characters where it starts at the font of the terminal,
+//! and the images are drawn as dots where they are dark.
+//!
+//! The column is a widget of the backend which keeps the mouse callbacks of the canvas.
+//! The screen finds the canvas under the mouse, which emits the mouse events at the center
+//! of the character under the mouse, in the points from the top left of the canvas.
+use super::{custom_widget::CustomWidget, screen};
+use crate::{
+    widget::{
+        attribute::{self, find_value, util::find_commands, DrawCommand},
+        custom,
+        event::MouseEvent,
+        text_label,
+    },
+    AttribKey, Attribute, Element, Event, Node,
+};
+use mt_dom::Callback;
+use std::{any::Any, f64::consts::PI, fmt};
+use titik::{
+    crossterm::event::MouseEvent as TermMouseEvent,
+    stretch::{result::Layout, style::Style},
+    Buffer, Cmd, FlexBox, Widget as Control,
+};
+
+/// the name of the custom widget of the column of the canvas
+pub(crate) const CANVAS: &str = "sauron-native:canvas";
+
+/// the mouse events which are emitted by the canvas
+const MOUSE_EVENTS: [AttribKey; 4] = [
+    AttribKey::MouseDown,
+    AttribKey::MouseUp,
+    AttribKey::ClickEvent,
+    AttribKey::MouseMove,
+];
+
+/// the points of the canvas in a character
+const CELL_WIDTH: f64 = 8.0;
+const CELL_HEIGHT: f64 = 16.0;
+
+/// the points of the canvas in a dot
+const DOT_SIZE: f64 = 4.0;
+
+/// the size of a canvas which has no width or height
+const DEFAULT_WIDTH: f64 = 320.0;
+const DEFAULT_HEIGHT: f64 = 160.0;
+
+/// the number of lines a bezier curve is drawn with
+const CURVE_SEGMENTS: usize = 16;
+
+/// the column of the lines of braille characters
+pub(crate) fn braille_lines<MSG>(element: Element<MSG>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let attrs = &element.attrs;
+    let width = find_value(AttribKey::Width, attrs)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(DEFAULT_WIDTH);
+    let height = find_value(AttribKey::Height, attrs)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(DEFAULT_HEIGHT);
+    let mut raster = Raster::new(width, height);
+    raster.draw(find_commands(attrs));
+
+    let lines = raster
+        .lines()
+        .into_iter()
+        .map(|line| text_label(vec![attribute::value(line)]))
+        .collect();
+    let canvas_attrs = element
+        .attrs
+        .into_iter()
+        .filter(|att| {
+            *att.name() == AttribKey::Key || MOUSE_EVENTS.contains(att.name())
+        })
+        .collect();
+    custom(CANVAS, canvas_attrs, lines)
+}
+
+/// emit the mouse event in the canvas under the mouse,
+/// returns no msgs if there is no canvas there
+pub(crate) fn emit_mouse_event<MSG>(
+    widget: &dyn Control<MSG>,
+    event: &titik::Event,
+) -> Vec<MSG>
+where
+    MSG: fmt::Debug + 'static,
+{
+    let (x, y) = match event {
+        titik::Event::Mouse(TermMouseEvent::Down(_, x, y, _))
+        | titik::Event::Mouse(TermMouseEvent::Up(_, x, y, _))
+        | titik::Event::Mouse(TermMouseEvent::Drag(_, x, y, _)) => {
+            (*x as f32, *y as f32)
+        }
+        _ => return vec![],
+    };
+    find_canvas_at(widget, x, y)
+        .map(|canvas| canvas.emit(event, x, y))
+        .unwrap_or_default()
+}
+
+fn find_canvas_at<MSG>(
+    widget: &dyn Control<MSG>,
+    x: f32,
+    y: f32,
+) -> Option<&BrailleCanvas<MSG>>
+where
+    MSG: 'static,
+{
+    if let Some(canvas) = widget.as_any().downcast_ref::<BrailleCanvas<MSG>>() {
+        return Some(canvas).filter(|_| screen::is_hit(widget, x, y));
+    }
+    widget
+        .children()?
+        .iter()
+        .find_map(|child| find_canvas_at(child.as_ref(), x, y))
+}
+
+/// creates the column of the canvas, registered as the custom widget `CANVAS`
+pub(crate) struct Canvases;
+
+impl<MSG> CustomWidget<MSG> for Canvases
+where
+    MSG: fmt::Debug + 'static,
+{
+    fn create(&self, attrs: &[Attribute<MSG>]) -> Box<dyn Control<MSG>> {
+        let mut flex = FlexBox::new();
+        flex.vertical();
+        let mut canvas = BrailleCanvas {
+            flex,
+            callbacks: vec![],
+        };
+        canvas.set_callbacks(attrs.iter());
+        Box::new(canvas)
+    }
+
+    fn update_attributes(
+        &self,
+        widget: &mut dyn Control<MSG>,
+        attrs: &[&Attribute<MSG>],
+    ) {
+        widget
+            .as_any_mut()
+            .downcast_mut::<BrailleCanvas<MSG>>()
+            .expect("must be a canvas")
+            .set_callbacks(attrs.iter().copied());
+    }
+}
+
+/// The column of the lines of the canvas, which keeps the mouse callbacks of the canvas
+pub(crate) struct BrailleCanvas<MSG> {
+    flex: FlexBox<MSG>,
+    callbacks: Vec<(AttribKey, Callback<Event, MSG>)>,
+}
+
+impl<MSG> BrailleCanvas<MSG>
+where
+    MSG: fmt::Debug + 'static,
+{
+    /// the callbacks of the mouse events replace the ones of the same event
+    fn set_callbacks<'a>(
+        &mut self,
+        attrs: impl Iterator<Item = &'a Attribute<MSG>>,
+    ) {
+        for att in attrs {
+            let key = att.name().clone();
+            if !MOUSE_EVENTS.contains(&key) {
+                continue;
+            }
+            self.callbacks.retain(|(cb_key, _)| *cb_key != key);
+            for cb in att.get_callback() {
+                self.callbacks.push((key.clone(), cb.clone()));
+            }
+        }
+    }
+
+    /// emit the mouse event at the location in the screen,
+    /// the click is emitted when the button is released in the canvas
+    fn emit(&self, event: &titik::Event, x: f32, y: f32) -> Vec<MSG> {
+        let location = match self.flex.layout() {
+            Some(layout) => layout.location,
+            None => return vec![],
+        };
+        let (x, y) = canvas_point(x - location.x, y - location.y);
+        let mouse_events = match event {
+            titik::Event::Mouse(TermMouseEvent::Down(..)) => {
+                vec![(AttribKey::MouseDown, MouseEvent::pressed(x, y))]
+            }
+            titik::Event::Mouse(TermMouseEvent::Up(..)) => vec![
+                (AttribKey::MouseUp, MouseEvent::release(x, y)),
+                (AttribKey::ClickEvent, MouseEvent::click(x, y)),
+            ],
+            titik::Event::Mouse(TermMouseEvent::Drag(..)) => {
+                vec![(AttribKey::MouseMove, MouseEvent::mousemove(x, y))]
+            }
+            _ => vec![],
+        };
+        mouse_events
+            .into_iter()
+            .flat_map(|(key, mouse_event)| {
+                self.callbacks
+                    .iter()
+                    .filter(move |(cb_key, _)| *cb_key == key)
+                    .map(move |(_, cb)| cb.emit(mouse_event))
+            })
+            .collect()
+    }
+}
+
+/// the center of the character at the column and line, in the points of the canvas
+fn canvas_point(column: f32, line: f32) -> (i32, i32) {
+    (
+        ((column.floor() as f64 + 0.5) * CELL_WIDTH) as i32,
+        ((line.floor() as f64 + 0.5) * CELL_HEIGHT) as i32,
+    )
+}
+
+impl<MSG> fmt::Debug for BrailleCanvas<MSG>
+where
+    MSG: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BrailleCanvas")
+            .field("flex", &self.flex)
+            .finish()
+    }
+}
+
+impl<MSG> Control<MSG> for BrailleCanvas<MSG>
+where
+    MSG: fmt::Debug + 'static,
+{
+    fn style(&self) -> Style {
+        self.flex.style()
+    }
+
+    fn layout(&self) -> Option<&Layout> {
+        self.flex.layout()
+    }
+
+    fn get_offset(&self) -> (f32, f32) {
+        self.flex.get_offset()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.flex.set_layout(layout)
+    }
+
+    fn add_child(&mut self, child: Box<dyn Control<MSG>>) -> bool {
+        self.flex.add_child(child)
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        self.flex.children()
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        self.flex.children_mut()
+    }
+
+    fn child_mut(
+        &mut self,
+        index: usize,
+    ) -> Option<&mut Box<dyn Control<MSG>>> {
+        self.flex.child_mut(index)
+    }
+
+    fn take_child(&mut self, index: usize) -> Option<Box<dyn Control<MSG>>> {
+        self.flex.take_child(index)
+    }
+
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        self.flex.draw(buf)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, width: Option<f32>, height: Option<f32>) {
+        self.flex.set_size(width, height)
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.flex.set_id(id)
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        self.flex.get_id()
+    }
+}
+
+/// a sub path of the current path, its points are in the points of the canvas
+struct SubPath {
+    points: Vec<(f64, f64)>,
+    closed: bool,
+}
+
+/// the dots and the characters of the text in the canvas
+struct Raster {
+    columns: usize,
+    lines: usize,
+    dots: Vec<bool>,
+    chars: Vec<Option<char>>,
+}
+
+impl Raster {
+    fn new(width: f64, height: f64) -> Self {
+        let columns = (width / CELL_WIDTH).ceil().max(1.0) as usize;
+        let lines = (height / CELL_HEIGHT).ceil().max(1.0) as usize;
+        Raster {
+            columns,
+            lines,
+            dots: vec![false; columns * 2 * lines * 4],
+            chars: vec![None; columns * lines],
+        }
+    }
+
+    fn dot_columns(&self) -> usize {
+        self.columns * 2
+    }
+
+    fn dot_rows(&self) -> usize {
+        self.lines * 4
+    }
+
+    /// draw the commands in order, the path is kept after it is filled or stroked
+    /// and only discarded with `BeginPath`, the same as in the other backends
+    fn draw(&mut self, commands: &[DrawCommand]) {
+        let mut path: Vec<SubPath> = vec![];
+        let mut fill_visible = true;
+        let mut stroke_visible = true;
+        for command in commands {
+            match command {
+                DrawCommand::BeginPath => path.clear(),
+                DrawCommand::MoveTo { x, y } => move_to(&mut path, (*x, *y)),
+                DrawCommand::LineTo { x, y } => line_to(&mut path, (*x, *y)),
+                DrawCommand::CurveTo {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    x,
+                    y,
+                } => {
+                    let start = current_point(&path).unwrap_or((*x1, *y1));
+                    for segment in 1..=CURVE_SEGMENTS {
+                        let t = segment as f64 / CURVE_SEGMENTS as f64;
+                        let u = 1.0 - t;
+                        let point = |p0: f64, p1: f64, p2: f64, p3: f64| {
+                            u * u * u * p0
+                                + 3.0 * u * u * t * p1
+                                + 3.0 * u * t * t * p2
+                                + t * t * t * p3
+                        };
+                        line_to(
+                            &mut path,
+                            (
+                                point(start.0, *x1, *x2, *x),
+                                point(start.1, *y1, *y2, *y),
+                            ),
+                        );
+                    }
+                }
+                DrawCommand::Arc {
+                    x,
+                    y,
+                    radius,
+                    start_angle,
+                    end_angle,
+                } => {
+                    let mut sweep = end_angle - start_angle;
+                    if sweep < 0.0 {
+                        sweep = sweep.rem_euclid(2.0 * PI);
+                    }
+                    let sweep = sweep.min(2.0 * PI);
+                    let segments =
+                        (sweep * radius / DOT_SIZE).ceil().max(4.0).min(64.0)
+                            as usize;
+                    for segment in 0..=segments {
+                        let angle = start_angle
+                            + sweep * segment as f64 / segments as f64;
+                        line_to(
+                            &mut path,
+                            (
+                                x + radius * angle.cos(),
+                                y + radius * angle.sin(),
+                            ),
+                        );
+                    }
+                }
+                DrawCommand::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                } => {
+                    move_to(&mut path, (*x, *y));
+                    line_to(&mut path, (x + width, *y));
+                    line_to(&mut path, (x + width, y + height));
+                    line_to(&mut path, (*x, y + height));
+                    close_path(&mut path);
+                }
+                DrawCommand::ClosePath => close_path(&mut path),
+                DrawCommand::FillColor(color) => fill_visible = color.a > 0.0,
+                DrawCommand::StrokeColor(color) => {
+                    stroke_visible = color.a > 0.0
+                }
+                DrawCommand::LineWidth(_) => (),
+                DrawCommand::Fill => {
+                    if fill_visible {
+                        self.fill(&path);
+                    }
+                }
+                DrawCommand::Stroke => {
+                    if stroke_visible {
+                        self.stroke(&path);
+                    }
+                }
+                DrawCommand::Text { x, y, text, .. } => {
+                    if fill_visible {
+                        self.text(*x, *y, text);
+                    }
+                }
+                DrawCommand::Image {
+                    x,
+                    y,
+                    width,
+                    height,
+                    data,
+                } => self.image((*x, *y, *width, *height), data),
+            }
+        }
+    }
+
+    fn set_dot(&mut self, column: i64, row: i64) {
+        if column >= 0
+            && row >= 0
+            && (column as usize) < self.dot_columns()
+            && (row as usize) < self.dot_rows()
+        {
+            let index = row as usize * self.dot_columns() + column as usize;
+            self.dots[index] = true;
+        }
+    }
+
+    /// set the dots along the line, from and to are in the points of the canvas
+    fn line(&mut self, from: (f64, f64), to: (f64, f64)) {
+        let (x0, y0) = (from.0 / DOT_SIZE, from.1 / DOT_SIZE);
+        let (x1, y1) = (to.0 / DOT_SIZE, to.1 / DOT_SIZE);
+        let steps = (x1 - x0).abs().max((y1 - y0).abs()).ceil().max(1.0);
+        for step in 0..=steps as usize {
+            let t = step as f64 / steps;
+            let x = x0 + (x1 - x0) * t;
+            let y = y0 + (y1 - y0) * t;
+            self.set_dot(x.floor() as i64, y.floor() as i64);
+        }
+    }
+
+    fn stroke(&mut self, path: &[SubPath]) {
+        for sub_path in path {
+            for pair in sub_path.points.windows(2) {
+                self.line(pair[0], pair[1]);
+            }
+            if sub_path.closed {
+                if let (Some(first), Some(last)) =
+                    (sub_path.points.first(), sub_path.points.last())
+                {
+                    self.line(*last, *first);
+                }
+            }
+        }
+    }
+
+    /// set the dots whose centers are inside the path, using the even-odd rule
+    fn fill(&mut self, path: &[SubPath]) {
+        for row in 0..self.dot_rows() {
+            let y = (row as f64 + 0.5) * DOT_SIZE;
+            let mut crossings = vec![];
+            for sub_path in path {
+                let points = &sub_path.points;
+                for index in 0..points.len() {
+                    let (x0, y0) = points[index];
+                    let (x1, y1) = points[(index + 1) % points.len()];
+                    if (y0 <= y && y < y1) || (y1 <= y && y < y0) {
+                        crossings.push(x0 + (y - y0) / (y1 - y0) * (x1 - x0));
+                    }
+                }
+            }
+            crossings.sort_by(|a, b| {
+                a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal)
+            });
+            for span in crossings.chunks(2) {
+                if let [start, end] = span {
+                    for column in 0..self.dot_columns() {
+                        let x = (column as f64 + 0.5) * DOT_SIZE;
+                        if *start <= x && x < *end {
+                            self.set_dot(column as i64, row as i64);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// write the text in the characters from the line of its baseline
+    fn text(&mut self, x: f64, y: f64, text: &str) {
+        let line = ((y - 1.0) / CELL_HEIGHT).floor();
+        if line < 0.0 || line as usize >= self.lines {
+            return;
+        }
+        let start = (x / CELL_WIDTH).floor().max(0.0) as usize;
+        for (offset, ch) in text.chars().enumerate() {
+            let column = start + offset;
+            if column >= self.columns {
+                break;
+            }
+            self.chars[line as usize * self.columns + column] = Some(ch);
+        }
+    }
+
+    /// set the dots where the image is dark, the image is scaled into the rectangle
+    fn image(
+        &mut self,
+        (x, y, width, height): (f64, f64, f64, f64),
+        data: &[u8],
+    ) {
+        let image = match image::load_from_memory(data) {
+            Ok(image) => image.to_rgba(),
+            Err(_) => return,
+        };
+        let (image_width, image_height) = image.dimensions();
+        if image_width == 0
+            || image_height == 0
+            || width <= 0.0
+            || height <= 0.0
+        {
+            return;
+        }
+        let first_column = (x / DOT_SIZE).floor().max(0.0) as usize;
+        let first_row = (y / DOT_SIZE).floor().max(0.0) as usize;
+        let last_column = ((x + width) / DOT_SIZE).ceil().max(0.0) as usize;
+        let last_row = ((y + height) / DOT_SIZE).ceil().max(0.0) as usize;
+        for row in first_row..last_row.min(self.dot_rows()) {
+            for column in first_column..last_column.min(self.dot_columns()) {
+                let point_x = (column as f64 + 0.5) * DOT_SIZE;
+                let point_y = (row as f64 + 0.5) * DOT_SIZE;
+                if point_x < x
+                    || point_y < y
+                    || point_x >= x + width
+                    || point_y >= y + height
+                {
+                    continue;
+                }
+                let pixel_x =
+                    ((point_x - x) / width * image_width as f64) as u32;
+                let pixel_y =
+                    ((point_y - y) / height * image_height as f64) as u32;
+                let pixel = image.get_pixel(
+                    pixel_x.min(image_width - 1),
+                    pixel_y.min(image_height - 1),
+                );
+                let [r, g, b, a] = pixel.0;
+                let luma =
+                    0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64;
+                if a >= 128 && luma < 128.0 {
+                    self.set_dot(column as i64, row as i64);
+                }
+            }
+        }
+    }
+
+    /// the lines of characters, the dots of each character are a braille character
+    fn lines(&self) -> Vec<String> {
+        // the bit of each dot in a braille character, by its column and row
+        const DOT_BITS: [[u32; 4]; 2] =
+            [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+        (0..self.lines)
+            .map(|line| {
+                (0..self.columns)
+                    .map(|column| {
+                        if let Some(ch) =
+                            self.chars[line * self.columns + column]
+                        {
+                            return ch;
+                        }
+                        let mut bits = 0;
+                        for (dot_column, column_bits) in
+                            DOT_BITS.iter().enumerate()
+                        {
+                            for (dot_row, bit) in column_bits.iter().enumerate()
+                            {
+                                let index = (line * 4 + dot_row)
+                                    * self.dot_columns()
+                                    + column * 2
+                                    + dot_column;
+                                if self.dots[index] {
+                                    bits |= bit;
+                                }
+                            }
+                        }
+                        std::char::from_u32(0x2800 + bits)
+                            .expect("must be a braille character")
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn current_point(path: &[SubPath]) -> Option<(f64, f64)> {
+    path.last()
+        .and_then(|sub_path| sub_path.points.last().cloned())
+}
+
+fn move_to(path: &mut Vec<SubPath>, point: (f64, f64)) {
+    path.push(SubPath {
+        points: vec![point],
+        closed: false,
+    });
+}
+
+/// a line without a current point starts a sub path at the point
+fn line_to(path: &mut Vec<SubPath>, point: (f64, f64)) {
+    match path.last_mut() {
+        Some(sub_path) if !sub_path.closed => sub_path.points.push(point),
+        _ => move_to(path, point),
+    }
+}
+
+/// close the sub path, the next sub path starts at the start of the closed one
+fn close_path(path: &mut Vec<SubPath>) {
+    if let Some(sub_path) = path.last_mut() {
+        if let Some(start) = sub_path.points.first().cloned() {
+            sub_path.closed = true;
+            move_to(path, start);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::attribute::Color;
+
+    /// rasterize the commands in a canvas of 2 by 1 characters
+    fn rasterize(commands: &[DrawCommand]) -> Vec<String> {
+        let mut raster = Raster::new(16.0, 16.0);
+        raster.draw(commands);
+        raster.lines()
+    }
+
+    #[test]
+    fn empty_canvas_is_blank_braille() {
+        assert_eq!(rasterize(&[]), vec!["⠀⠀"]);
+    }
+
+    #[test]
+    fn size_is_rounded_up_to_whole_characters() {
+        let raster = Raster::new(17.0, 1.0);
+        assert_eq!((raster.columns, raster.lines), (3, 1));
+        let raster = Raster::new(0.0, 0.0);
+        assert_eq!((raster.columns, raster.lines), (1, 1));
+    }
+
+    #[test]
+    fn stroke_a_line_across_the_top_dots() {
+        let lines = rasterize(&[
+            DrawCommand::MoveTo { x: 0.0, y: 0.0 },
+            DrawCommand::LineTo { x: 15.0, y: 0.0 },
+            DrawCommand::Stroke,
+        ]);
+        assert_eq!(lines, vec!["⠉⠉"]);
+    }
+
+    #[test]
+    fn stroke_a_line_down_the_left_dots() {
+        let lines = rasterize(&[
+            DrawCommand::MoveTo { x: 0.0, y: 0.0 },
+            DrawCommand::LineTo { x: 0.0, y: 15.0 },
+            DrawCommand::Stroke,
+        ]);
+        assert_eq!(lines, vec!["⡇⠀"]);
+    }
+
+    #[test]
+    fn fill_the_rectangle_of_the_first_character() {
+        let lines = rasterize(&[
+            DrawCommand::Rect {
+                x: 0.0,
+                y: 0.0,
+                width: 8.0,
+                height: 16.0,
+            },
+            DrawCommand::Fill,
+        ]);
+        assert_eq!(lines, vec!["⣿⠀"]);
+    }
+
+    #[test]
+    fn transparent_colors_draw_nothing() {
+        let lines = rasterize(&[
+            DrawCommand::FillColor(Color::rgba(0, 0, 0, 0.0)),
+            DrawCommand::StrokeColor(Color::rgba(0, 0, 0, 0.0)),
+            DrawCommand::Rect {
+                x: 0.0,
+                y: 0.0,
+                width: 16.0,
+                height: 16.0,
+            },
+            DrawCommand::Fill,
+            DrawCommand::Stroke,
+        ]);
+        assert_eq!(lines, vec!["⠀⠀"]);
+    }
+
+    #[test]
+    fn path_is_kept_until_begin_path() {
+        let path = [
+            DrawCommand::MoveTo { x: 0.0, y: 0.0 },
+            DrawCommand::LineTo { x: 15.0, y: 0.0 },
+        ];
+        let bottom_line = [
+            DrawCommand::MoveTo { x: 0.0, y: 12.0 },
+            DrawCommand::LineTo { x: 15.0, y: 12.0 },
+            DrawCommand::Stroke,
+        ];
+        let kept: Vec<DrawCommand> =
+            path.iter().chain(&bottom_line).cloned().collect();
+        assert_eq!(rasterize(&kept), vec!["⣉⣉"]);
+
+        let discarded: Vec<DrawCommand> = path
+            .iter()
+            .chain(&[DrawCommand::BeginPath])
+            .chain(&bottom_line)
+            .cloned()
+            .collect();
+        assert_eq!(rasterize(&discarded), vec!["⣀⣀"]);
+    }
+
+    #[test]
+    fn text_is_written_in_the_characters() {
+        let lines = rasterize(&[DrawCommand::Text {
+            x: 8.0,
+            y: 12.0,
+            text: "Hi".to_string(),
+            size: 12.0,
+        }]);
+        assert_eq!(lines, vec!["⠀H"]);
+    }
+
+    #[test]
+    fn mouse_is_at_the_center_of_the_character() {
+        assert_eq!(canvas_point(0.0, 0.0), (4, 8));
+        assert_eq!(canvas_point(2.0, 1.0), (20, 24));
+    }
+}
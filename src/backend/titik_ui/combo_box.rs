@@ -0,0 +1,280 @@
+//! Titik doesn't have a combo box, so the combo box is displayed as a button with the label
+//! of the chosen option. Pressing the button pops up the list of the options below it,
+//! and pressing an option chooses it and closes the list.
+//! An editable combo box is a text input followed by the button which pops up the list.
+//!
+//! Titik can not display a widget on top of the others, so the popped up list pushes
+//! the widgets after it. The view of the app doesn't know whether the list is popped up,
+//! so it is kept here. The popup buttons are widgets of the backend without any callback,
+//! these are pressed by the screen which then redraws the view, see `screen`.
+//! Only choosing an option emits the input event.
+use super::{custom_widget::CustomWidget, screen};
+use crate::{
+    widget::{
+        attribute::{self, find_callback, find_value, util::find_options},
+        button, column, custom, event,
+        event::InputEvent,
+        row, text_input,
+    },
+    AttribKey, Attribute, Element, Node,
+};
+use std::{any::Any, cell::RefCell, collections::BTreeSet, fmt::Debug};
+use titik::{
+    stretch::{result::Layout, style::Style},
+    Buffer, Button, Cmd, Widget as Control,
+};
+
+/// the name of the custom widget of the popup buttons
+pub(crate) const POPUP_BUTTON: &str = "sauron-native:popup-button";
+
+thread_local! {
+    /// the combo boxes whose list of options is popped up
+    static POPPED_UP: RefCell<BTreeSet<String>> = RefCell::new(BTreeSet::new());
+}
+
+/// the button which pops up the list, followed by the list when it is popped up.
+/// The combo box is identified by its key or id, otherwise by its `index` in the view
+pub(crate) fn combo_box_popup<MSG>(
+    element: Element<MSG>,
+    index: usize,
+) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let attrs = &element.attrs;
+    let combo_id = find_value(AttribKey::Key, attrs)
+        .or_else(|| find_value(AttribKey::Id, attrs))
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| index.to_string());
+    let combo_value = find_value(AttribKey::Value, attrs)
+        .map(|v| v.to_string())
+        .unwrap_or_default();
+    let is_editable = find_value(AttribKey::Editable, attrs)
+        .map(|v| v.as_bool())
+        .unwrap_or(false);
+    let options = find_options(attrs);
+    let callbacks =
+        find_callback(AttribKey::InputEvent, attrs).unwrap_or_default();
+    let is_popped_up =
+        POPPED_UP.with(|popped_up| popped_up.borrow().contains(&combo_id));
+
+    // the button which pops up or closes the list
+    let popup_button = |label: String, popped_up: bool| -> Node<MSG> {
+        custom(
+            POPUP_BUTTON,
+            vec![
+                attribute::label(label),
+                attribute::name(combo_id.clone()),
+                attribute::checked(popped_up),
+            ],
+            vec![],
+        )
+    };
+
+    // choosing an option closes the list, then emits the value of the option.
+    // The list is only closed when there is no callback to emit the value
+    let option_button = |label: String, new_value: String| -> Node<MSG> {
+        if callbacks.is_empty() {
+            return popup_button(label, false);
+        }
+        let mut button_attrs = vec![attribute::label(label)];
+        for cb in callbacks.iter() {
+            let cb = (*cb).clone();
+            let combo_id = combo_id.clone();
+            let new_value = new_value.clone();
+            button_attrs.push(event::on_click(move |_| {
+                set_popped_up(&combo_id, false);
+                cb.emit(InputEvent::new(new_value.clone()))
+            }));
+        }
+        button(button_attrs)
+    };
+
+    let arrow = if is_popped_up { "▴" } else { "▾" };
+    let header = if is_editable {
+        let mut input_attrs = vec![attribute::value(combo_value.clone())];
+        for cb in callbacks.iter() {
+            let cb = (*cb).clone();
+            input_attrs.push(event::on_input(move |input| cb.emit(input)));
+        }
+        row(
+            vec![],
+            vec![
+                text_input(input_attrs),
+                popup_button(arrow.to_string(), !is_popped_up),
+            ],
+        )
+    } else {
+        let chosen_label = options
+            .iter()
+            .find(|option| option.value == combo_value)
+            .map(|option| option.label.clone())
+            .unwrap_or_else(|| combo_value.clone());
+        popup_button(format!("{} {}", chosen_label, arrow), !is_popped_up)
+    };
+
+    let box_attrs = find_value(AttribKey::Key, attrs)
+        .map(|key| vec![attribute::key(key.clone())])
+        .unwrap_or_default();
+    if is_popped_up {
+        // the chosen option is enclosed in brackets
+        let list = options
+            .iter()
+            .map(|option| {
+                let label = if option.value == combo_value {
+                    format!("[{}]", option.label)
+                } else {
+                    format!(" {} ", option.label)
+                };
+                option_button(label, option.value.clone())
+            })
+            .collect();
+        column(box_attrs, vec![header, column(vec![], list)])
+    } else {
+        column(box_attrs, vec![header])
+    }
+}
+
+fn set_popped_up(combo_id: &str, popped_up: bool) {
+    POPPED_UP.with(|popped_up_ids| {
+        let mut popped_up_ids = popped_up_ids.borrow_mut();
+        if popped_up {
+            popped_up_ids.insert(combo_id.to_string());
+        } else {
+            popped_up_ids.remove(combo_id);
+        }
+    });
+}
+
+/// pop up or close the list of the popup button at the location,
+/// returns false if there is no popup button there
+pub(crate) fn press_popup_button<MSG>(
+    widget: &dyn Control<MSG>,
+    x: f32,
+    y: f32,
+) -> bool
+where
+    MSG: Debug + 'static,
+{
+    if let Some(popup_button) =
+        widget.as_any().downcast_ref::<PopupButton<MSG>>()
+    {
+        let is_hit = screen::is_hit(&popup_button.button, x, y);
+        if is_hit {
+            set_popped_up(&popup_button.combo_id, popup_button.popped_up);
+        }
+        return is_hit;
+    }
+    widget.children().map_or(false, |children| {
+        children
+            .iter()
+            .any(|child| press_popup_button(child.as_ref(), x, y))
+    })
+}
+
+/// creates the popup buttons of the combo boxes, registered as the custom widget `POPUP_BUTTON`
+pub(crate) struct PopupButtons;
+
+impl<MSG> CustomWidget<MSG> for PopupButtons
+where
+    MSG: Debug + 'static,
+{
+    fn create(&self, attrs: &[Attribute<MSG>]) -> Box<dyn Control<MSG>> {
+        let mut popup_button = PopupButton {
+            button: Button::new(""),
+            combo_id: String::new(),
+            popped_up: false,
+        };
+        popup_button.set_attributes(attrs.iter());
+        Box::new(popup_button)
+    }
+
+    fn update_attributes(
+        &self,
+        widget: &mut dyn Control<MSG>,
+        attrs: &[&Attribute<MSG>],
+    ) {
+        widget
+            .as_any_mut()
+            .downcast_mut::<PopupButton<MSG>>()
+            .expect("must be a popup button")
+            .set_attributes(attrs.iter().copied());
+    }
+}
+
+/// A button which sets whether the list of the combo box is `popped_up`
+#[derive(Debug)]
+pub(crate) struct PopupButton<MSG>
+where
+    MSG: 'static,
+{
+    button: Button<MSG>,
+    combo_id: String,
+    popped_up: bool,
+}
+
+impl<MSG> PopupButton<MSG>
+where
+    MSG: 'static,
+{
+    fn set_attributes<'a>(
+        &mut self,
+        attrs: impl Iterator<Item = &'a Attribute<MSG>>,
+    ) {
+        for att in attrs {
+            for value in att.get_plain() {
+                match att.name() {
+                    AttribKey::Label => self.button.set_label(value),
+                    AttribKey::Name => self.combo_id = value.to_string(),
+                    AttribKey::Checked => self.popped_up = value.as_bool(),
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+impl<MSG> Control<MSG> for PopupButton<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn style(&self) -> Style {
+        self.button.style()
+    }
+
+    fn layout(&self) -> Option<&Layout> {
+        self.button.layout()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.button.set_layout(layout)
+    }
+
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        self.button.draw(buf)
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.button.set_focused(focused)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, width: Option<f32>, height: Option<f32>) {
+        self.button.set_size(width, height)
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.button.set_id(id)
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        self.button.get_id()
+    }
+}
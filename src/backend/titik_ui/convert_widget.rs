@@ -1,5 +1,8 @@
 use super::convert_event;
+use super::custom_widget::CustomWidget;
+use super::progress;
 use crate::{
+    backend::registry,
     widget::{
         attribute::{find_callback, find_value},
         Widget,
@@ -8,7 +11,7 @@ use crate::{
 };
 use image::GenericImageView;
 use mt_dom::Callback;
-use std::fmt::Debug;
+use std::{fmt::Debug, rc::Rc};
 use titik::{
     renderer::Renderer, Button, Checkbox, Dispatch, FlexBox, GroupBox, Image,
     Link, Radio, TextArea, TextInput, TextLabel, Widget as Control,
@@ -21,11 +24,15 @@ where
     MSG: Debug + 'static,
 {
     match widget_node {
+        // the dialog is displayed in its own layer, see `dialog`
+        crate::Node::Element(element) if element.tag == Widget::Dialog => {
+            from_node(&element.tag, &element.attrs)
+        }
         crate::Node::Element(element) => {
             let mut control = from_node(&element.tag, &element.attrs);
             for child in element.children {
                 let child_widget = from_node_tree(child);
-                control.add_child(child_widget);
+                add_child(&element.tag, &mut *control, child_widget);
             }
             control
         }
@@ -62,6 +69,12 @@ where
             hbox.horizontal();
             Box::new(hbox)
         }
+        // the tab_box is rewritten into the header row and the page of the active tab
+        Widget::TabBox => {
+            let mut vbox = FlexBox::new();
+            vbox.vertical();
+            Box::new(vbox)
+        }
         Widget::GroupBox => {
             let mut groupbox = GroupBox::new();
             let label = find_value(AttribKey::Label, &attrs)
@@ -70,6 +83,13 @@ where
             groupbox.set_label(&label);
             Box::new(groupbox)
         }
+        // the dialog is displayed in its own layer,
+        // so it is an empty placeholder which takes no space in the view
+        Widget::Dialog => {
+            let mut placeholder = FlexBox::new();
+            placeholder.set_size(Some(0.0), Some(0.0));
+            Box::new(placeholder)
+        }
         Widget::Button => {
             let label = find_value(AttribKey::Label, &attrs)
                 .map(|v| v.to_string())
@@ -101,7 +121,19 @@ where
             let value = find_value(AttribKey::Value, &attrs)
                 .map(|v| v.to_string())
                 .unwrap_or(String::new());
-            let input = TextInput::new(value);
+            let mut input = TextInput::new(value);
+            if let Some(callbacks) =
+                find_callback(AttribKey::InputEvent, &attrs)
+            {
+                for cb in callbacks {
+                    let cb = cb.clone();
+                    input.add_input_listener(Callback::from(
+                        move |t_event: titik::Event| {
+                            cb.emit(convert_event::from_titik(t_event))
+                        },
+                    ));
+                }
+            }
             Box::new(input)
         }
         Widget::Checkbox => {
@@ -149,14 +181,7 @@ where
                 .map(|v| v.as_bytes())
                 .flatten()
                 .unwrap_or(&empty);
-            let image = image::load_from_memory(&bytes).expect("should load");
-            let mut img = Image::new(bytes.to_vec());
-            let (width, height) = image.dimensions();
-            img.set_size(
-                Some(width as f32 / 10.0),
-                Some(height as f32 / 10.0 / 2.0),
-            );
-            Box::new(img)
+            Box::new(image_from_bytes::<MSG>(bytes))
         }
         Widget::Svg => todo!(),
         Widget::TextArea => {
@@ -268,5 +293,62 @@ where
             let mut link = Link::new(uri, label);
             Box::new(link)
         }
+        // only the indeterminate progress bar is kept by the rewrite
+        Widget::ProgressBar | Widget::Spinner => {
+            Box::new(progress::Animation::new(widget, attrs))
+        }
+        // these are rewritten into the widgets of titik before they are converted
+        Widget::Slider
+        | Widget::ComboBox
+        | Widget::Table
+        | Widget::TreeView
+        | Widget::VirtualList
+        | Widget::Canvas => {
+            unreachable!("{:?} must have been rewritten", widget)
+        }
+        Widget::Custom(name) => {
+            match registry::lookup_custom_widget::<Rc<dyn CustomWidget<MSG>>>(
+                name,
+            ) {
+                Some(custom_widget) => custom_widget.create(attrs),
+                // the children are still displayed in the placeholder
+                None => {
+                    let mut vbox = FlexBox::new();
+                    vbox.vertical();
+                    Box::new(vbox)
+                }
+            }
+        }
+    }
+}
+
+/// create an image control sized according to the dimension of the image
+pub(crate) fn image_from_bytes<MSG>(bytes: &[u8]) -> Image<MSG> {
+    let image = image::load_from_memory(&bytes).expect("should load");
+    let mut img = Image::new(bytes.to_vec());
+    let (width, height) = image.dimensions();
+    img.set_size(Some(width as f32 / 10.0), Some(height as f32 / 10.0 / 2.0));
+    img
+}
+
+/// add the child control, custom widgets decide how their children are added
+pub(crate) fn add_child<MSG>(
+    widget: &Widget,
+    control: &mut dyn titik::Widget<MSG>,
+    child: Box<dyn titik::Widget<MSG>>,
+) -> bool
+where
+    MSG: 'static,
+{
+    match widget {
+        Widget::Custom(name) => {
+            match registry::lookup_custom_widget::<Rc<dyn CustomWidget<MSG>>>(
+                name,
+            ) {
+                Some(custom_widget) => custom_widget.add_child(control, child),
+                None => control.add_child(child),
+            }
+        }
+        _ => control.add_child(child),
     }
 }
@@ -0,0 +1,38 @@
+//! Custom widgets for the titik backend
+use crate::{backend::registry, Attribute};
+use std::rc::Rc;
+
+/// A widget which is not part of the `Widget` enum, created with
+/// `widget::custom(name, attrs, children)` in the view.
+pub trait CustomWidget<MSG> {
+    /// create the titik widget from the attributes,
+    /// the event callbacks are attached to the titik widget
+    fn create(&self, attrs: &[Attribute<MSG>]) -> Box<dyn titik::Widget<MSG>>;
+
+    /// set the changed attributes into the titik widget
+    fn update_attributes(
+        &self,
+        widget: &mut dyn titik::Widget<MSG>,
+        attrs: &[&Attribute<MSG>],
+    );
+
+    /// add the child widget, returns false if the child is not added
+    fn add_child(
+        &self,
+        widget: &mut dyn titik::Widget<MSG>,
+        child: Box<dyn titik::Widget<MSG>>,
+    ) -> bool {
+        widget.add_child(child)
+    }
+}
+
+/// register the custom widget which will be used to create
+/// the `Widget::Custom` of the same name in the titik backend
+pub fn register_custom_widget<MSG, CW>(name: &str, custom_widget: CW)
+where
+    MSG: 'static,
+    CW: CustomWidget<MSG> + 'static,
+{
+    let custom_widget: Rc<dyn CustomWidget<MSG>> = Rc::new(custom_widget);
+    registry::register(name, custom_widget);
+}
@@ -0,0 +1,185 @@
+//! Titik can not display a widget on top of the others, so the dialog is displayed
+//! in its own layer on top of the view and the windows, centered in the screen.
+//! Only the displayed layer receives the events, so the rest of the app
+//! doesn't take any input while the dialog is open.
+//!
+//! The dialog stays in the view of the app as an empty placeholder.
+//! The backend takes the dialog from the view after each update
+//! and patches it into its layer.
+use super::convert_widget;
+use crate::{
+    widget::{attribute::find_value, row},
+    AttribKey, Element, Node, Widget,
+};
+use std::{any::Any, fmt::Debug};
+use titik::{
+    stretch::{
+        geometry::Size,
+        result::Layout,
+        style::{AlignItems, Dimension, FlexDirection, JustifyContent, Style},
+    },
+    Buffer, Cmd, GroupBox, Widget as Control,
+};
+
+/// the id of the layer where the dialog is displayed
+pub(crate) const DIALOG_LAYER: &str = "sauron-native:dialog";
+
+/// the size of the dialog when it is not set, which is the same as in the `dialog` builder
+const DEFAULT_WIDTH: f64 = 400.0;
+const DEFAULT_HEIGHT: f64 = 200.0;
+
+/// the points of the size of the dialog which are in a cell of the terminal
+const CELL_WIDTH: f64 = 10.0;
+const CELL_HEIGHT: f64 = 20.0;
+
+/// the rows taken by each of the widgets in the dialog
+const CHILD_ROWS: usize = 3;
+
+/// the body of the dialog, followed by a row of its buttons at the bottom
+pub(crate) fn buttons_at_bottom<MSG>(mut element: Element<MSG>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let (buttons, mut body): (Vec<Node<MSG>>, Vec<Node<MSG>>) = element
+        .children
+        .into_iter()
+        .partition(|child| child.tag() == Some(&Widget::Button));
+    if !buttons.is_empty() {
+        body.push(row(vec![], buttons));
+    }
+    element.children = body;
+    Node::Element(element)
+}
+
+/// the first dialog in the view, the dialogs inside it are not displayed
+pub(crate) fn find_dialog<MSG>(node: &Node<MSG>) -> Option<&Node<MSG>> {
+    if node.tag() == Some(&Widget::Dialog) {
+        return Some(node);
+    }
+    node.get_children()
+        .and_then(|children| children.iter().find_map(find_dialog))
+}
+
+/// the bordered box with the title of the dialog, sized in the cells of the terminal.
+/// The box is made taller when its children don't fit in it
+pub(crate) fn dialog_widget<MSG>(dialog: Node<MSG>) -> Box<dyn Control<MSG>>
+where
+    MSG: Debug + 'static,
+{
+    let element = match dialog {
+        Node::Element(element) => element,
+        Node::Text(_) => unreachable!("a dialog must be an element"),
+    };
+    let attrs = &element.attrs;
+    let title = find_value(AttribKey::Label, attrs)
+        .map(|v| v.to_string())
+        .unwrap_or_default();
+    let width = find_value(AttribKey::Width, attrs)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(DEFAULT_WIDTH);
+    let height = find_value(AttribKey::Height, attrs)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(DEFAULT_HEIGHT);
+    // the border takes a row at the top and at the bottom
+    let content_rows = 2 + element.children.len() * CHILD_ROWS;
+
+    let mut frame = GroupBox::new();
+    frame.set_label(&title);
+    frame.set_size(
+        Some((width / CELL_WIDTH).round() as f32),
+        Some((height / CELL_HEIGHT).round().max(content_rows as f64) as f32),
+    );
+    for child in element.children {
+        frame.add_child(convert_widget::from_node_tree(child));
+    }
+    Box::new(frame)
+}
+
+/// The wrapper of the dialog layer, which fills the screen and centers the dialog in it
+#[derive(Debug)]
+pub(crate) struct Centered<MSG> {
+    layout: Option<Layout>,
+    children: Vec<Box<dyn Control<MSG>>>,
+    id: Option<String>,
+}
+
+impl<MSG> Centered<MSG> {
+    pub(crate) fn new() -> Self {
+        Centered {
+            layout: None,
+            children: vec![],
+            id: None,
+        }
+    }
+}
+
+impl<MSG> Control<MSG> for Centered<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn style(&self) -> Style {
+        Style {
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            size: Size {
+                width: Dimension::Percent(1.0),
+                height: Dimension::Percent(1.0),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn layout(&self) -> Option<&Layout> {
+        self.layout.as_ref()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.layout = Some(layout);
+    }
+
+    fn add_child(&mut self, child: Box<dyn Control<MSG>>) -> bool {
+        self.children.push(child);
+        true
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        Some(&self.children)
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        Some(&mut self.children)
+    }
+
+    fn take_child(&mut self, index: usize) -> Option<Box<dyn Control<MSG>>> {
+        if index < self.children.len() {
+            Some(self.children.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// only the children are drawn, the rest of the screen is left blank
+    fn draw(&self, _buf: &mut Buffer) -> Vec<Cmd> {
+        vec![]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    /// the wrapper always fills the screen
+    fn set_size(&mut self, _width: Option<f32>, _height: Option<f32>) {}
+
+    fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+}
@@ -0,0 +1,241 @@
+//! The terminal has no file chooser, so the file dialogs are shown as a file browser
+//! made out of the widgets of the view, displayed as the top most layer.
+//!
+//! The widgets of the browser can only emit the msgs of the app, so each event keeps
+//! its action in the browser and emits the msg of a cancelled dialog.
+//! The backend takes the action and ignores the msg emitted alongside it.
+use crate::{
+    widget::{
+        attribute, button, column, event, event::InputEvent, row, text_input,
+        text_label,
+    },
+    Attribute, ChosenFile, FileAction, FileDialog, Node, OnChosen,
+};
+use std::{
+    cell::RefCell,
+    env, fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+/// the id of the layer where the file browser is displayed
+pub(crate) const FILE_BROWSER_LAYER: &str = "sauron-native:file-browser";
+
+/// What the user did in the file browser
+#[derive(Debug, Clone)]
+pub(crate) enum Action {
+    /// display the files in this folder
+    OpenFolder(PathBuf),
+    /// toggle the selection of the file, in a save dialog the name of the file is used
+    Select(PathBuf),
+    /// the name of the file to save into is edited
+    SetName(String),
+    /// the chosen files are accepted
+    Accept,
+    /// the dialog is cancelled
+    Cancel,
+}
+
+/// The result of applying an action to the file browser
+pub(crate) enum Outcome<MSG> {
+    /// the view of the file browser needs to be displayed again
+    Redraw,
+    /// the change is already displayed, such as the edited name of the file
+    Unchanged,
+    /// the user is done, the msg of the chosen files is to be dispatched
+    Done(MSG),
+}
+
+/// The state of the file dialog shown in the terminal
+pub(crate) struct FileBrowser<MSG> {
+    dialog: FileDialog,
+    folder: PathBuf,
+    selected: Vec<PathBuf>,
+    name: String,
+    on_chosen: OnChosen<MSG>,
+    /// the action of the last event in the browser
+    action: Rc<RefCell<Option<Action>>>,
+}
+
+impl<MSG> FileBrowser<MSG>
+where
+    MSG: 'static,
+{
+    pub(crate) fn new(dialog: FileDialog, on_chosen: OnChosen<MSG>) -> Self {
+        let folder = dialog
+            .current_folder
+            .clone()
+            .or_else(|| env::current_dir().ok())
+            .unwrap_or_else(|| PathBuf::from("/"));
+        let name = dialog.current_name.clone().unwrap_or_default();
+        FileBrowser {
+            dialog,
+            folder,
+            selected: vec![],
+            name,
+            on_chosen,
+            action: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// the action of the last event in the browser, if the msg came from the browser
+    pub(crate) fn take_action(&self) -> Option<Action> {
+        self.action.borrow_mut().take()
+    }
+
+    pub(crate) fn apply(&mut self, action: Action) -> Outcome<MSG> {
+        match action {
+            Action::OpenFolder(folder) => {
+                self.folder = folder;
+                Outcome::Redraw
+            }
+            Action::Select(path) => {
+                match self.dialog.action {
+                    FileAction::Save => {
+                        if let Some(name) =
+                            path.file_name().and_then(|name| name.to_str())
+                        {
+                            self.name = name.to_string();
+                        }
+                    }
+                    FileAction::Open if self.dialog.multiple => {
+                        if self.selected.contains(&path) {
+                            self.selected.retain(|selected| *selected != path);
+                        } else {
+                            self.selected.push(path);
+                        }
+                    }
+                    FileAction::Open => self.selected = vec![path],
+                }
+                Outcome::Redraw
+            }
+            Action::SetName(name) => {
+                self.name = name;
+                Outcome::Unchanged
+            }
+            Action::Accept => {
+                let files = match self.dialog.action {
+                    FileAction::Open => self
+                        .selected
+                        .iter()
+                        .cloned()
+                        .map(ChosenFile::new)
+                        .collect(),
+                    FileAction::Save if !self.name.is_empty() => {
+                        vec![ChosenFile::new(self.folder.join(&self.name))]
+                    }
+                    FileAction::Save => vec![],
+                };
+                Outcome::Done((self.on_chosen)(files))
+            }
+            Action::Cancel => Outcome::Done((self.on_chosen)(vec![])),
+        }
+    }
+
+    /// the folders followed by the listed files in the current folder
+    fn entries(&self) -> Vec<(PathBuf, bool)> {
+        let mut entries: Vec<(PathBuf, bool)> = fs::read_dir(&self.folder)
+            .map(|read_dir| {
+                read_dir
+                    .filter_map(Result::ok)
+                    .map(|entry| {
+                        let path = entry.path();
+                        let is_folder = path.is_dir();
+                        (path, is_folder)
+                    })
+                    .filter(|(path, is_folder)| {
+                        *is_folder || self.dialog.is_listed(path)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        entries.sort_by(|(path1, is_folder1), (path2, is_folder2)| {
+            is_folder2.cmp(is_folder1).then_with(|| path1.cmp(path2))
+        });
+        entries
+    }
+
+    /// the attribute which keeps the action when the button is clicked
+    fn on_click(&self, action: Action) -> Attribute<MSG> {
+        let action_cell = Rc::clone(&self.action);
+        let on_chosen = Rc::clone(&self.on_chosen);
+        event::on_click(move |_| {
+            *action_cell.borrow_mut() = Some(action.clone());
+            on_chosen(vec![])
+        })
+    }
+
+    pub(crate) fn view(&self) -> Node<MSG> {
+        let mut entries = vec![];
+        if let Some(parent) = self.folder.parent() {
+            entries.push(button(vec![
+                attribute::label(".."),
+                self.on_click(Action::OpenFolder(parent.to_path_buf())),
+            ]));
+        }
+        for (path, is_folder) in self.entries() {
+            let name = file_name(&path);
+            let entry = if is_folder {
+                button(vec![
+                    attribute::label(format!("{}/", name)),
+                    self.on_click(Action::OpenFolder(path)),
+                ])
+            } else {
+                let mark = if self.selected.contains(&path) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+                button(vec![
+                    attribute::label(format!("{} {}", mark, name)),
+                    self.on_click(Action::Select(path)),
+                ])
+            };
+            entries.push(entry);
+        }
+
+        let mut children = vec![
+            text_label(vec![attribute::value(self.dialog.title.clone())]),
+            text_label(vec![attribute::value(
+                self.folder.display().to_string(),
+            )]),
+            column(vec![], entries),
+        ];
+        if self.dialog.action == FileAction::Save {
+            let action_cell = Rc::clone(&self.action);
+            let on_chosen = Rc::clone(&self.on_chosen);
+            children.push(text_input(vec![
+                attribute::value(self.name.clone()),
+                event::on_input(move |input: InputEvent| {
+                    *action_cell.borrow_mut() =
+                        Some(Action::SetName(input.value.to_string()));
+                    on_chosen(vec![])
+                }),
+            ]));
+        }
+        let accept_label = match self.dialog.action {
+            FileAction::Open => "Open",
+            FileAction::Save => "Save",
+        };
+        children.push(row(
+            vec![],
+            vec![
+                button(vec![
+                    attribute::label("Cancel"),
+                    self.on_click(Action::Cancel),
+                ]),
+                button(vec![
+                    attribute::label(accept_label),
+                    self.on_click(Action::Accept),
+                ]),
+            ],
+        ));
+        column(vec![], children)
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
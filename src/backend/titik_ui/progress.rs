@@ -0,0 +1,192 @@
+//! Titik doesn't have a progress bar and a spinner.
+//! The progress bar is displayed as a label with a bar of block characters.
+//!
+//! The indeterminate progress bar and the spinner are animations, which take their
+//! frame from the clock each time they are drawn. The event loop of the backend
+//! redraws the screen every frame interval while there is an animation in the screen.
+use crate::{
+    widget::{
+        attribute::{
+            self, find_value,
+            util::{find_fraction, is_indeterminate},
+        },
+        row, text_label,
+    },
+    AttribKey, Attribute, Element, Node, Widget,
+};
+use std::{
+    any::Any,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use titik::{
+    stretch::{result::Layout, style::Style},
+    Buffer, Cmd, TextLabel, Widget as Control,
+};
+
+/// the number of characters in the bar
+const BAR_LEN: usize = 20;
+
+/// the length of the block which moves in an indeterminate progress bar
+const PULSE_LEN: usize = 4;
+
+/// the duration of each animation frame
+pub(crate) const FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+/// the glyphs of the spinner, in the order they are displayed
+const SPINNER_GLYPHS: [char; 10] =
+    ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// the bar followed by the label, or the percentage when there is no label.
+/// The indeterminate progress bar is kept, since it is converted into an animation
+pub(crate) fn progress_bar<MSG>(element: Element<MSG>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let attrs = &element.attrs;
+    if is_indeterminate(attrs) {
+        return Node::Element(element);
+    }
+    let fraction = find_fraction(attrs);
+    let filled = (fraction * BAR_LEN as f64).round() as usize;
+    let bar = format!("{}{}", "█".repeat(filled), "░".repeat(BAR_LEN - filled));
+    let label = find_value(AttribKey::Label, attrs)
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| format!("{}%", (fraction * 100.0).round()));
+    let box_attrs = find_value(AttribKey::Key, attrs)
+        .map(|key| vec![attribute::key(key.clone())])
+        .unwrap_or_default();
+    row(
+        box_attrs,
+        vec![text_label(vec![attribute::value(format!(
+            "{} {}",
+            bar, label
+        ))])],
+    )
+}
+
+/// the frames displayed by an animation
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frames {
+    /// the rotating glyph of the spinner
+    Spinner,
+    /// the block moving across the bar of an indeterminate progress bar
+    Pulse,
+}
+
+/// A label which displays the frame of the clock each time it is drawn
+#[derive(Debug)]
+pub(crate) struct Animation {
+    layout: Option<Layout>,
+    frames: Frames,
+    /// the text displayed after the bar of the progress bar
+    label: String,
+    id: Option<String>,
+}
+
+impl Animation {
+    /// the animation of the spinner or the indeterminate progress bar
+    pub(crate) fn new<MSG>(tag: &Widget, attrs: &[Attribute<MSG>]) -> Self
+    where
+        MSG: 'static,
+    {
+        let frames = match tag {
+            Widget::Spinner => Frames::Spinner,
+            _ => Frames::Pulse,
+        };
+        let mut animation = Animation {
+            layout: None,
+            frames,
+            label: String::new(),
+            id: None,
+        };
+        if let Some(label) = find_value(AttribKey::Label, attrs) {
+            animation.set_label(label.to_string());
+        }
+        animation
+    }
+
+    pub(crate) fn set_label(&mut self, label: String) {
+        self.label = label;
+    }
+
+    /// the text of the current frame
+    fn text(&self) -> String {
+        let frame = current_frame();
+        match self.frames {
+            Frames::Spinner => {
+                SPINNER_GLYPHS[frame % SPINNER_GLYPHS.len()].to_string()
+            }
+            Frames::Pulse => {
+                let start = frame % (BAR_LEN - PULSE_LEN + 1);
+                format!(
+                    "{}{}{} {}",
+                    "░".repeat(start),
+                    "█".repeat(PULSE_LEN),
+                    "░".repeat(BAR_LEN - PULSE_LEN - start),
+                    self.label
+                )
+            }
+        }
+    }
+}
+
+impl<MSG> Control<MSG> for Animation {
+    /// the same as the style of a label
+    fn style(&self) -> Style {
+        Control::<MSG>::style(&TextLabel::new(""))
+    }
+
+    fn layout(&self) -> Option<&Layout> {
+        self.layout.as_ref()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.layout = Some(layout);
+    }
+
+    /// the current frame is drawn as a label in the layout of the animation
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        let mut label = TextLabel::new(self.text());
+        if let Some(layout) = self.layout {
+            Control::<MSG>::set_layout(&mut label, layout);
+        }
+        Control::<MSG>::draw(&label, buf)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    /// the animation is always sized as a label
+    fn set_size(&mut self, _width: Option<f32>, _height: Option<f32>) {}
+
+    fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+}
+
+/// whether there is an animation in the widget or any of its descendants
+pub(crate) fn has_animation<MSG>(widget: &dyn Control<MSG>) -> bool {
+    widget.as_any().is::<Animation>()
+        || widget.children().map_or(false, |children| {
+            children.iter().any(|child| has_animation(child.as_ref()))
+        })
+}
+
+/// the animation frame, which is the number of frame intervals since the epoch
+fn current_frame() -> usize {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| {
+            (elapsed.as_millis() / FRAME_INTERVAL.as_millis()) as usize
+        })
+        .unwrap_or(0)
+}
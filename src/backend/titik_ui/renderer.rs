@@ -0,0 +1,270 @@
+//! The event loop of the titik backend.
+//!
+//! This is the same as the loop of the titik `Renderer`, except the next event is
+//! waited with a timeout while there is an animation in the screen. When no event
+//! arrives before the next frame, the screen is redrawn so the animations advance.
+//! Without an animation, the loop blocks until the next event from the terminal.
+//! Titik keeps the layout and hit testing of its renderer private, so these are done here.
+use super::progress;
+use std::io::Write;
+use titik::{
+    crossterm::{
+        cursor,
+        event::{
+            self, DisableMouseCapture, EnableMouseCapture, KeyCode,
+            KeyModifiers, MouseEvent,
+        },
+        execute, queue, style,
+        terminal::{self, ClearType},
+        Result,
+    },
+    find_widget_mut,
+    stretch::{
+        geometry::Size, node::Node as StretchNode, number::Number, Stretch,
+    },
+    Buffer, Cmd, Dispatch, Event, Widget as Control,
+};
+
+/// run the event loop until one of CTRL-c, CTRL-q, CTRL-d or CTRL-z is pressed
+pub(crate) fn run<MSG>(
+    write: &mut dyn Write,
+    program: &dyn Dispatch<MSG>,
+    root_node: &mut dyn Control<MSG>,
+) -> Result<()> {
+    execute!(write, terminal::EnterAlternateScreen, EnableMouseCapture)?;
+    terminal::enable_raw_mode()?;
+    queue!(
+        write,
+        style::ResetColor,
+        terminal::Clear(ClearType::All),
+        cursor::Hide,
+        cursor::MoveTo(1, 1)
+    )?;
+    let mut terminal_size = terminal::size()?;
+    let mut focused_widget_idx = None;
+    compute_node_layout(root_node, terminal_size);
+
+    loop {
+        draw(write, root_node, terminal_size)?;
+
+        if progress::has_animation(root_node)
+            && !event::poll(progress::FRAME_INTERVAL)?
+        {
+            continue;
+        }
+        let event = Event::from_crossterm(event::read()?);
+        match event {
+            Event::Key(key_event) => {
+                if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                    if let KeyCode::Char('c' | 'q' | 'd' | 'z') = key_event.code
+                    {
+                        break;
+                    }
+                } else if let Some(idx) = focused_widget_idx {
+                    if let Some(focused_widget) =
+                        find_widget_mut(root_node, idx)
+                    {
+                        let msgs = focused_widget.process_event(event.clone());
+                        dispatch_msgs(program, root_node, msgs, terminal_size);
+                    }
+                }
+            }
+            // mouse clicks sets the focused the widget underneath
+            Event::Mouse(MouseEvent::Down(_btn, x, y, _modifier)) => {
+                focused_widget_idx =
+                    node_hit_at(root_node, x as f32, y as f32, &mut 0).pop();
+                if let Some(idx) = focused_widget_idx {
+                    set_focused_node(root_node, idx, &mut 0);
+                }
+            }
+            Event::Resize(width, height) => {
+                terminal_size = (width, height);
+                compute_node_layout(root_node, terminal_size);
+            }
+            _ => (),
+        }
+        // the mouse events are sent to the widgets underneath,
+        // from the deepest to the root, regardless if it is focused or not
+        if let Some((x, y)) = extract_location(&event) {
+            let hits = node_hit_at(root_node, x as f32, y as f32, &mut 0);
+            for hit in hits.iter().rev() {
+                if let Some(hit_widget) = find_widget_mut(root_node, *hit) {
+                    let msgs = hit_widget.process_event(event.clone());
+                    dispatch_msgs(program, root_node, msgs, terminal_size);
+                }
+            }
+        }
+    }
+    execute!(
+        write,
+        style::ResetColor,
+        cursor::Show,
+        terminal::LeaveAlternateScreen,
+        DisableMouseCapture,
+    )?;
+    terminal::disable_raw_mode()
+}
+
+/// the layout is recomputed even without msgs,
+/// since the widgets may have changed their own state
+fn dispatch_msgs<MSG>(
+    program: &dyn Dispatch<MSG>,
+    root_node: &mut dyn Control<MSG>,
+    msgs: Vec<MSG>,
+    terminal_size: (u16, u16),
+) {
+    for msg in msgs {
+        program.dispatch(msg, root_node);
+    }
+    compute_node_layout(root_node, terminal_size);
+}
+
+fn draw<MSG>(
+    write: &mut dyn Write,
+    root_node: &dyn Control<MSG>,
+    (width, height): (u16, u16),
+) -> Result<()> {
+    let mut buf = Buffer::new(width as usize, height as usize);
+    buf.reset();
+    let cmds = draw_widget(&mut buf, root_node);
+    buf.render(write)?;
+    for cmd in cmds {
+        cmd.execute(write)?;
+    }
+    write.flush()?;
+    Ok(())
+}
+
+fn draw_widget<MSG>(buf: &mut Buffer, widget: &dyn Control<MSG>) -> Vec<Cmd> {
+    let mut cmds = widget.draw(buf);
+    if let Some(children) = widget.children() {
+        for child in children {
+            cmds.extend(draw_widget(buf, child.as_ref()));
+        }
+    }
+    cmds
+}
+
+/// the x and y location of a mouse event
+fn extract_location(event: &Event) -> Option<(u16, u16)> {
+    match event {
+        Event::Mouse(MouseEvent::Down(_, x, y, _))
+        | Event::Mouse(MouseEvent::Up(_, x, y, _))
+        | Event::Mouse(MouseEvent::Drag(_, x, y, _))
+        | Event::Mouse(MouseEvent::ScrollDown(x, y, _))
+        | Event::Mouse(MouseEvent::ScrollUp(x, y, _)) => Some((*x, *y)),
+        _ => None,
+    }
+}
+
+/// the index of the widgets at the location, in the order they are traversed
+fn node_hit_at<MSG>(
+    node: &dyn Control<MSG>,
+    x: f32,
+    y: f32,
+    cur_node_idx: &mut usize,
+) -> Vec<usize> {
+    let mut hits = vec![];
+    if let Some(layout) = node.layout() {
+        let loc = layout.location;
+        if x >= loc.x
+            && x < loc.x + layout.size.width
+            && y >= loc.y
+            && y < loc.y + layout.size.height
+        {
+            hits.push(*cur_node_idx);
+        }
+    }
+    if let Some(children) = node.children() {
+        for child in children.iter() {
+            *cur_node_idx += 1;
+            hits.extend(node_hit_at(child.as_ref(), x, y, cur_node_idx));
+        }
+    }
+    hits
+}
+
+/// the widget at the node_idx is focused, while the rest are not
+fn set_focused_node<MSG>(
+    node: &mut dyn Control<MSG>,
+    node_idx: usize,
+    cur_node_idx: &mut usize,
+) {
+    if node_idx == *cur_node_idx {
+        node.set_focused(true);
+    } else if let Some(children) = node.children_mut() {
+        for child in children.iter_mut() {
+            *cur_node_idx += 1;
+            set_focused_node(child.as_mut(), node_idx, cur_node_idx);
+        }
+    } else {
+        node.set_focused(false);
+    }
+}
+
+/// calculate the layout of the widgets from their styles, filling the terminal
+fn compute_node_layout<MSG>(
+    root_node: &mut dyn Control<MSG>,
+    (width, height): (u16, u16),
+) {
+    let mut stretch = Stretch::new();
+    let stretch_node = build_stretch_node(&mut stretch, root_node)
+        .expect("must have built a style node");
+    stretch
+        .compute_layout(
+            stretch_node,
+            Size {
+                width: Number::Defined(width as f32),
+                height: Number::Defined(height as f32),
+            },
+        )
+        .expect("must compute the layout");
+    set_node_layout(root_node, stretch_node, &stretch, (0.0, 0.0), (0.0, 0.0));
+}
+
+fn build_stretch_node<MSG>(
+    stretch: &mut Stretch,
+    widget: &dyn Control<MSG>,
+) -> Option<StretchNode> {
+    let children: Vec<StretchNode> = widget
+        .children()
+        .unwrap_or(&[])
+        .iter()
+        .filter_map(|child| build_stretch_node(stretch, child.as_ref()))
+        .collect();
+    stretch.new_node(widget.style(), &children).ok()
+}
+
+/// the location of the layout is absolute in the screen,
+/// the offset of the parent is applied to its children
+fn set_node_layout<MSG>(
+    widget: &mut dyn Control<MSG>,
+    stretch_node: StretchNode,
+    stretch: &Stretch,
+    (parent_x, parent_y): (f32, f32),
+    (offset_x, offset_y): (f32, f32),
+) {
+    let mut layout = *stretch.layout(stretch_node).expect("must have layout");
+    layout.location.x += parent_x + offset_x;
+    layout.location.y += parent_y + offset_y;
+    layout.size.width -= offset_x;
+    layout.size.height -= offset_y;
+
+    let child_offset = widget.get_offset();
+    let stretch_children =
+        stretch.children(stretch_node).expect("must get children");
+    if let Some(children) = widget.children_mut() {
+        for (stretch_child, child) in
+            stretch_children.into_iter().zip(children.iter_mut())
+        {
+            set_node_layout(
+                child.as_mut(),
+                stretch_child,
+                stretch,
+                (layout.location.x, layout.location.y),
+                child_offset,
+            );
+        }
+    }
+    widget.set_layout(layout);
+}
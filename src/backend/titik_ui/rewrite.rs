@@ -0,0 +1,52 @@
+//! Titik doesn't have some of the widgets, so these are rewritten into
+//! the widgets that it has before the view is diffed.
+//! The rewritten widgets are then patched the same way as the other widgets.
+use super::{
+    canvas, combo_box, dialog, progress, slider, tab_box, table, tree,
+    virtual_list,
+};
+use crate::{Node, Widget};
+
+/// rewrite the widgets that titik doesn't have in the view
+pub(crate) fn rewrite_view<MSG>(node: Node<MSG>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    rewrite_node(node, &mut 0)
+}
+
+/// the widgets which keep their state here are counted in the order they are rewritten,
+/// which identifies the widgets without a key
+fn rewrite_node<MSG>(node: Node<MSG>, stateful_count: &mut usize) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    match node {
+        Node::Element(mut element) => {
+            element.children = element
+                .children
+                .into_iter()
+                .map(|child| rewrite_node(child, stateful_count))
+                .collect();
+            match element.tag {
+                Widget::TabBox => tab_box::show_active_tab(element),
+                Widget::Slider => slider::slider_bar(element),
+                Widget::ProgressBar => progress::progress_bar(element),
+                Widget::TreeView => tree::indented_list(element),
+                Widget::VirtualList => virtual_list::visible_rows(element),
+                Widget::Canvas => canvas::braille_lines(element),
+                Widget::Dialog => dialog::buttons_at_bottom(element),
+                Widget::ComboBox => {
+                    *stateful_count += 1;
+                    combo_box::combo_box_popup(element, *stateful_count)
+                }
+                Widget::Table => {
+                    *stateful_count += 1;
+                    table::table_grid(element, *stateful_count)
+                }
+                _ => Node::Element(element),
+            }
+        }
+        text => text,
+    }
+}
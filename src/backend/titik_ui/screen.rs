@@ -0,0 +1,145 @@
+//! The root widget of the screen, which contains the wrapper of the displayed layer.
+//!
+//! The event loop passes the mouse events to every widget under the mouse,
+//! the root widget being the last. The widgets which change the state kept in the backend,
+//! such as the scroll buttons of the table and the popup buttons of the combo box,
+//! can't emit the msgs of the app, so these are pressed here and the view is redrawn
+//! right away. The canvas under the mouse is also found here, which emits the mouse
+//! events of the canvas with the location in the canvas.
+use super::{canvas, combo_box, table};
+use std::{any::Any, fmt, rc::Rc};
+use titik::{
+    crossterm::event::MouseEvent,
+    stretch::{result::Layout, style::Style},
+    Buffer, Cmd, Event, FlexBox, Widget as Control,
+};
+
+/// redraw the view into the root widget
+type Redraw<MSG> = Rc<dyn Fn(&mut dyn Control<MSG>)>;
+
+/// A vertical box which fills the screen
+pub(crate) struct Screen<MSG> {
+    flex: FlexBox<MSG>,
+    redraw: Redraw<MSG>,
+}
+
+impl<MSG> Screen<MSG>
+where
+    MSG: 'static,
+{
+    pub(crate) fn new<F>(redraw: F) -> Self
+    where
+        F: Fn(&mut dyn Control<MSG>) + 'static,
+    {
+        let mut flex = FlexBox::new();
+        flex.vertical();
+        Screen {
+            flex,
+            redraw: Rc::new(redraw),
+        }
+    }
+}
+
+impl<MSG> fmt::Debug for Screen<MSG>
+where
+    MSG: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Screen").field("flex", &self.flex).finish()
+    }
+}
+
+impl<MSG> Control<MSG> for Screen<MSG>
+where
+    MSG: fmt::Debug + 'static,
+{
+    fn style(&self) -> Style {
+        self.flex.style()
+    }
+
+    fn layout(&self) -> Option<&Layout> {
+        self.flex.layout()
+    }
+
+    fn get_offset(&self) -> (f32, f32) {
+        self.flex.get_offset()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.flex.set_layout(layout)
+    }
+
+    fn add_child(&mut self, child: Box<dyn Control<MSG>>) -> bool {
+        self.flex.add_child(child)
+    }
+
+    fn children(&self) -> Option<&[Box<dyn Control<MSG>>]> {
+        self.flex.children()
+    }
+
+    fn children_mut(&mut self) -> Option<&mut [Box<dyn Control<MSG>>]> {
+        self.flex.children_mut()
+    }
+
+    fn child_mut(
+        &mut self,
+        index: usize,
+    ) -> Option<&mut Box<dyn Control<MSG>>> {
+        self.flex.child_mut(index)
+    }
+
+    fn take_child(&mut self, index: usize) -> Option<Box<dyn Control<MSG>>> {
+        self.flex.take_child(index)
+    }
+
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        self.flex.draw(buf)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, width: Option<f32>, height: Option<f32>) {
+        self.flex.set_size(width, height)
+    }
+
+    /// press the scroll button or the popup button under the mouse,
+    /// then redraw the view with the scrolled page or the popped up list.
+    /// The msgs are the mouse events emitted by the canvas under the mouse
+    fn process_event(&mut self, event: Event) -> Vec<MSG> {
+        let msgs = canvas::emit_mouse_event(self, &event);
+        if let Event::Mouse(MouseEvent::Down(_, x, y, _)) = event {
+            let (x, y) = (x as f32, y as f32);
+            if table::press_scroll_button(self, x, y)
+                || combo_box::press_popup_button(self, x, y)
+            {
+                let redraw = Rc::clone(&self.redraw);
+                redraw(self);
+            }
+        }
+        msgs
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.flex.set_id(id)
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        self.flex.get_id()
+    }
+}
+
+/// whether the location is inside the layout of the widget
+pub(crate) fn is_hit<MSG>(widget: &dyn Control<MSG>, x: f32, y: f32) -> bool {
+    widget.layout().map_or(false, |layout| {
+        x >= layout.location.x
+            && x < layout.location.x + layout.size.width
+            && y >= layout.location.y
+            && y < layout.location.y + layout.size.height
+    })
+}
@@ -0,0 +1,91 @@
+//! Titik doesn't have a slider, so the slider is displayed as a bar of block characters
+//! between the buttons which decrease and increase the value by a step.
+//! The buttons are pressed with the mouse, or focused and pressed with the keyboard.
+use crate::{
+    widget::{
+        attribute::{
+            self, find_callback, find_value,
+            util::{find_orientation, find_range},
+            Orientation,
+        },
+        button, column, event,
+        event::InputEvent,
+        row, text_label,
+    },
+    AttribKey, Element, Node,
+};
+
+/// the number of characters in the bar
+const BAR_LEN: usize = 20;
+
+/// the row of the decrease button, the bar and the increase button.
+/// A vertical slider is a column with the increase button at the top
+pub(crate) fn slider_bar<MSG>(element: Element<MSG>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let attrs = &element.attrs;
+    let (min, max, step) = find_range(attrs);
+    let value = find_value(AttribKey::Value, attrs)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(min)
+        .max(min)
+        .min(max);
+    let callbacks =
+        find_callback(AttribKey::InputEvent, attrs).unwrap_or_default();
+    let step_button = |label: &'static str, new_value: f64| {
+        let mut button_attrs = vec![attribute::label(label)];
+        for cb in callbacks.iter() {
+            let cb = (*cb).clone();
+            button_attrs.push(event::on_click(move |_| {
+                cb.emit(InputEvent::new(new_value))
+            }));
+        }
+        button(button_attrs)
+    };
+    let decrease = step_button("-", (value - step).max(min));
+    let increase = step_button("+", (value + step).min(max));
+
+    let fraction = if max > min {
+        (value - min) / (max - min)
+    } else {
+        0.0
+    };
+    let filled = (fraction * BAR_LEN as f64).round() as usize;
+    let box_attrs = find_value(AttribKey::Key, attrs)
+        .map(|key| vec![attribute::key(key.clone())])
+        .unwrap_or_default();
+    match find_orientation(attrs) {
+        Orientation::Horizontal => {
+            let bar = format!(
+                "{}{} {}",
+                "█".repeat(filled),
+                "░".repeat(BAR_LEN - filled),
+                value
+            );
+            row(
+                box_attrs,
+                vec![
+                    decrease,
+                    text_label(vec![attribute::value(bar)]),
+                    increase,
+                ],
+            )
+        }
+        Orientation::Vertical => {
+            let bar = (0..BAR_LEN)
+                .map(|i| if BAR_LEN - i <= filled { "█" } else { "░" })
+                .collect::<Vec<_>>()
+                .join("\n");
+            column(
+                box_attrs,
+                vec![
+                    increase,
+                    text_label(vec![attribute::value(bar)]),
+                    text_label(vec![attribute::value(value.to_string())]),
+                    decrease,
+                ],
+            )
+        }
+    }
+}
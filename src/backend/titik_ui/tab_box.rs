@@ -0,0 +1,68 @@
+//! Titik can not hide a widget, so the tab_box is displayed as a header row of tabs
+//! followed by the page of the active tab only.
+//!
+//! Switching to another tab replaces the page, since the view is rewritten before it is diffed.
+//! The tabs are buttons, so they are focused with the keyboard and pressed
+//! to switch to their page, the same way as the other buttons.
+use crate::{
+    widget::{
+        attribute::{self, find_callback, find_value},
+        button, event,
+        event::InputEvent,
+        row,
+    },
+    AttribKey, Element, Node,
+};
+
+/// the tab_box only contains the header row of the tabs and the page of the active tab
+pub(crate) fn show_active_tab<MSG>(mut element: Element<MSG>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let selected = find_value(AttribKey::Selected, &element.attrs)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0) as usize;
+    let header = tab_header(&element.attrs, &element.children, selected);
+    let page = element.children.drain(..).nth(selected);
+    element.children = Some(header).into_iter().chain(page).collect();
+    Node::Element(element)
+}
+
+/// the row of tabs, the active tab is enclosed in brackets.
+/// Pressing a tab emits the callbacks of the tab_box with the index of the tab
+fn tab_header<MSG>(
+    attrs: &[crate::Attribute<MSG>],
+    pages: &[Node<MSG>],
+    selected: usize,
+) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let callbacks =
+        find_callback(AttribKey::InputEvent, attrs).unwrap_or_default();
+    let tabs = pages
+        .iter()
+        .enumerate()
+        .map(|(index, page)| {
+            let label = page
+                .get_attributes()
+                .and_then(|page_attrs| find_value(AttribKey::Label, page_attrs))
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            let label = if index == selected {
+                format!("[{}]", label)
+            } else {
+                format!(" {} ", label)
+            };
+            let mut tab_attrs = vec![attribute::label(label)];
+            for cb in callbacks.iter() {
+                let cb = (*cb).clone();
+                tab_attrs.push(event::on_click(move |_| {
+                    cb.emit(InputEvent::new(index))
+                }));
+            }
+            button(tab_attrs)
+        })
+        .collect();
+    row(vec![], tabs)
+}
@@ -0,0 +1,430 @@
+//! Titik doesn't have a table, so the table is displayed as a grid of labels,
+//! where the cells of each column are padded to the same width.
+//! The headers of the sortable columns and the select button at the start of each row
+//! are pressed to sort and select the rows.
+//!
+//! Only a page of the rows is displayed, which is scrolled with the buttons below the grid.
+//! The page fits in the Height of the table, or in the terminal when it has no Height.
+//! The view of the app doesn't know about the scrolled page, so it is kept here.
+//! The scroll buttons are widgets of the backend without any callback,
+//! these are pressed by the screen which then redraws the view, see `screen`.
+use super::{custom_widget::CustomWidget, screen};
+use crate::{
+    widget::{
+        attribute::{
+            self, find_callback, find_value,
+            util::{
+                cell_text, find_columns, find_rows, find_selected, find_sort,
+            },
+            CellKind, TableColumn,
+        },
+        button, column, custom, event,
+        event::{CellEditEvent, InputEvent, SortEvent},
+        row, text_input, text_label,
+    },
+    AttribKey, Attribute, Element, Event, Node, Value,
+};
+use mt_dom::Callback;
+use std::{any::Any, cell::RefCell, collections::BTreeMap, fmt::Debug};
+use titik::{
+    crossterm::terminal,
+    stretch::{result::Layout, style::Style},
+    Buffer, Button, Cmd, Widget as Control,
+};
+
+/// the name of the custom widget of the scroll buttons
+pub(crate) const SCROLL_BUTTON: &str = "sauron-native:scroll-button";
+
+/// the points of the height of the table which are in a line of the terminal,
+/// the same as in the dialog
+const CELL_HEIGHT: f64 = 20.0;
+
+/// the lines taken by each row of the grid, since a button and a label are 3 lines tall
+const ROW_LINES: usize = 3;
+
+/// the rows in a page when the size of the terminal is not known
+const DEFAULT_PAGE_ROWS: usize = 10;
+
+/// the number of characters in the bar of a progress cell
+const PROGRESS_LEN: usize = 10;
+
+thread_local! {
+    /// the index of the first displayed row of the scrolled tables
+    static SCROLL_OFFSETS: RefCell<BTreeMap<String, usize>> = RefCell::new(BTreeMap::new());
+}
+
+/// the header row, the page of the rows, then the scroll buttons.
+/// The scrolled page is kept by the key or id of the table. A table without them
+/// is identified by its `index` in the view, which changes when the tables before it
+/// are added or removed, so a warning is logged
+pub(crate) fn table_grid<MSG>(element: Element<MSG>, index: usize) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let attrs = &element.attrs;
+    let columns = find_columns(attrs);
+    let rows = find_rows(attrs);
+    let page_rows = page_rows(attrs);
+    let table_id = find_value(AttribKey::Key, attrs)
+        .or_else(|| find_value(AttribKey::Id, attrs))
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| {
+            if rows.len() > page_rows {
+                log::warn!(
+                    "the table at index {} has no key or id, \
+                     its scrolled page may be used by another table",
+                    index
+                );
+            }
+            format!("index:{}", index)
+        });
+    let sort = find_sort(attrs);
+    let selected = find_selected(attrs);
+    let select_callbacks =
+        find_callback(AttribKey::InputEvent, attrs).unwrap_or_default();
+    let sort_callbacks =
+        find_callback(AttribKey::SortEvent, attrs).unwrap_or_default();
+    let edit_callbacks =
+        find_callback(AttribKey::CellEditEvent, attrs).unwrap_or_default();
+
+    let last_page = rows.len().saturating_sub(page_rows);
+    let offset = SCROLL_OFFSETS
+        .with(|offsets| offsets.borrow().get(&table_id).copied())
+        .unwrap_or(0)
+        .min(last_page);
+
+    // the width of each column is the widest of its title and cells
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            rows.iter()
+                .map(|row| cell_display(column, row.get(index)).chars().count())
+                .chain(Some(column.title.chars().count() + 2))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut headers = vec![text_label(vec![attribute::value("   ")])];
+    for (index, column) in columns.iter().enumerate() {
+        let indicator = match sort {
+            Some((sort_column, false)) if sort_column == index => " ▲",
+            Some((sort_column, true)) if sort_column == index => " ▼",
+            _ => "",
+        };
+        let title =
+            pad(&format!("{}{}", column.title, indicator), widths[index]);
+        if column.sortable && !sort_callbacks.is_empty() {
+            let mut button_attrs = vec![attribute::label(title)];
+            for cb in sort_callbacks.iter() {
+                let cb = (*cb).clone();
+                button_attrs.push(event::on_click(move |_| {
+                    cb.emit(SortEvent::pressed(index, sort))
+                }));
+            }
+            headers.push(button(button_attrs));
+        } else {
+            headers.push(text_label(vec![attribute::value(title)]));
+        }
+    }
+
+    // the rows are keyed with their index, so the rows of another page are replaced
+    // along with the callbacks of their row index
+    let mut grid = vec![row(vec![attribute::key("header")], headers)];
+    for (row_index, cells) in
+        rows.iter().enumerate().skip(offset).take(page_rows)
+    {
+        let mark = if selected == Some(row_index) {
+            "(•)"
+        } else {
+            "( )"
+        };
+        let mut select_attrs = vec![attribute::label(mark)];
+        for cb in select_callbacks.iter() {
+            let cb = (*cb).clone();
+            select_attrs.push(event::on_click(move |_| {
+                cb.emit(InputEvent::new(row_index))
+            }));
+        }
+        let mut grid_row = vec![button(select_attrs)];
+        for (index, column) in columns.iter().enumerate() {
+            grid_row.push(grid_cell(
+                &edit_callbacks,
+                row_index,
+                index,
+                column,
+                cells.get(index),
+                widths[index],
+            ));
+        }
+        grid.push(row(
+            vec![attribute::key(format!("row:{}", row_index))],
+            grid_row,
+        ));
+    }
+
+    if rows.len() > page_rows {
+        let scroll_button = |label: &'static str, new_offset: usize| {
+            custom(
+                SCROLL_BUTTON,
+                vec![
+                    attribute::label(label),
+                    attribute::name(table_id.clone()),
+                    attribute::value(new_offset),
+                ],
+                vec![],
+            )
+        };
+        grid.push(row(
+            vec![attribute::key("scroll")],
+            vec![
+                scroll_button("▲", offset.saturating_sub(page_rows)),
+                text_label(vec![attribute::value(format!(
+                    "{}-{} of {}",
+                    offset + 1,
+                    (offset + page_rows).min(rows.len()),
+                    rows.len()
+                ))]),
+                scroll_button("▼", (offset + page_rows).min(last_page)),
+            ],
+        ));
+    }
+
+    let box_attrs = find_value(AttribKey::Key, attrs)
+        .map(|key| vec![attribute::key(key.clone())])
+        .unwrap_or_default();
+    column(box_attrs, grid)
+}
+
+/// the rows which fit in the Height of the table below the header and above the
+/// scroll buttons, the table fills the terminal when it has no Height
+fn page_rows<MSG: 'static>(attrs: &[Attribute<MSG>]) -> usize {
+    let lines =
+        match find_value(AttribKey::Height, attrs).and_then(|v| v.as_f64()) {
+            Some(height) => (height / CELL_HEIGHT) as usize,
+            None => match terminal::size() {
+                Ok((_, lines)) => lines as usize,
+                Err(_) => return DEFAULT_PAGE_ROWS,
+            },
+        };
+    (lines / ROW_LINES).saturating_sub(2).max(1)
+}
+
+/// the cell displayed according to the kind of its column,
+/// the text cells of an editable column are text inputs
+/// and the toggle cells are buttons
+fn grid_cell<MSG>(
+    edit_callbacks: &[&Callback<Event, MSG>],
+    row_index: usize,
+    index: usize,
+    column: &TableColumn,
+    cell: Option<&Value>,
+    width: usize,
+) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let display = pad(&cell_display(column, cell), width);
+    match column.kind {
+        CellKind::Text if column.editable => {
+            let mut input_attrs = vec![attribute::value(display)];
+            for cb in edit_callbacks.iter() {
+                let cb = (*cb).clone();
+                input_attrs.push(event::on_input(move |input| {
+                    cb.emit(CellEditEvent::new(
+                        row_index,
+                        index,
+                        input.value.to_string().trim_end().to_string(),
+                    ))
+                }));
+            }
+            text_input(input_attrs)
+        }
+        CellKind::Toggle if column.editable => {
+            let checked = cell.map(|v| v.as_bool()).unwrap_or(false);
+            let mut button_attrs = vec![attribute::label(display)];
+            for cb in edit_callbacks.iter() {
+                let cb = (*cb).clone();
+                button_attrs.push(event::on_click(move |_| {
+                    cb.emit(CellEditEvent::new(row_index, index, !checked))
+                }));
+            }
+            button(button_attrs)
+        }
+        _ => text_label(vec![attribute::value(display)]),
+    }
+}
+
+/// the text of the cell in the grid
+fn cell_display(column: &TableColumn, cell: Option<&Value>) -> String {
+    match column.kind {
+        CellKind::Text => cell.map(|v| cell_text(v)).unwrap_or_default(),
+        CellKind::Toggle => {
+            if cell.map(|v| v.as_bool()).unwrap_or(false) {
+                "[x]".to_string()
+            } else {
+                "[ ]".to_string()
+            }
+        }
+        CellKind::Progress => {
+            let fraction = cell
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0)
+                .max(0.0)
+                .min(1.0);
+            let filled = (fraction * PROGRESS_LEN as f64).round() as usize;
+            format!(
+                "{}{}",
+                "█".repeat(filled),
+                "░".repeat(PROGRESS_LEN - filled)
+            )
+        }
+        CellKind::Image => "[image]".to_string(),
+    }
+}
+
+/// pad the text with spaces to the width of its column
+fn pad(text: &str, width: usize) -> String {
+    format!("{:width$}", text, width = width)
+}
+
+/// scroll the table of the scroll button at the location,
+/// returns false if there is no scroll button there
+pub(crate) fn press_scroll_button<MSG>(
+    widget: &dyn Control<MSG>,
+    x: f32,
+    y: f32,
+) -> bool
+where
+    MSG: Debug + 'static,
+{
+    if let Some(scroll_button) =
+        widget.as_any().downcast_ref::<ScrollButton<MSG>>()
+    {
+        let is_hit = screen::is_hit(&scroll_button.button, x, y);
+        if is_hit {
+            SCROLL_OFFSETS.with(|offsets| {
+                offsets.borrow_mut().insert(
+                    scroll_button.table_id.clone(),
+                    scroll_button.offset,
+                )
+            });
+        }
+        return is_hit;
+    }
+    widget.children().map_or(false, |children| {
+        children
+            .iter()
+            .any(|child| press_scroll_button(child.as_ref(), x, y))
+    })
+}
+
+/// creates the scroll buttons of the tables, registered as the custom widget `SCROLL_BUTTON`
+pub(crate) struct ScrollButtons;
+
+impl<MSG> CustomWidget<MSG> for ScrollButtons
+where
+    MSG: Debug + 'static,
+{
+    fn create(&self, attrs: &[Attribute<MSG>]) -> Box<dyn Control<MSG>> {
+        let mut scroll_button = ScrollButton {
+            button: Button::new(""),
+            table_id: String::new(),
+            offset: 0,
+        };
+        scroll_button.set_attributes(attrs.iter());
+        Box::new(scroll_button)
+    }
+
+    fn update_attributes(
+        &self,
+        widget: &mut dyn Control<MSG>,
+        attrs: &[&Attribute<MSG>],
+    ) {
+        widget
+            .as_any_mut()
+            .downcast_mut::<ScrollButton<MSG>>()
+            .expect("must be a scroll button")
+            .set_attributes(attrs.iter().copied());
+    }
+}
+
+/// A button which scrolls the table to the page starting at `offset`
+#[derive(Debug)]
+pub(crate) struct ScrollButton<MSG>
+where
+    MSG: 'static,
+{
+    button: Button<MSG>,
+    table_id: String,
+    offset: usize,
+}
+
+impl<MSG> ScrollButton<MSG>
+where
+    MSG: 'static,
+{
+    fn set_attributes<'a>(
+        &mut self,
+        attrs: impl Iterator<Item = &'a Attribute<MSG>>,
+    ) {
+        for att in attrs {
+            for value in att.get_plain() {
+                match att.name() {
+                    AttribKey::Label => self.button.set_label(value),
+                    AttribKey::Name => self.table_id = value.to_string(),
+                    AttribKey::Value => {
+                        self.offset = value.as_f64().unwrap_or(0.0) as usize
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+impl<MSG> Control<MSG> for ScrollButton<MSG>
+where
+    MSG: Debug + 'static,
+{
+    fn style(&self) -> Style {
+        self.button.style()
+    }
+
+    fn layout(&self) -> Option<&Layout> {
+        self.button.layout()
+    }
+
+    fn set_layout(&mut self, layout: Layout) {
+        self.button.set_layout(layout)
+    }
+
+    fn draw(&self, buf: &mut Buffer) -> Vec<Cmd> {
+        self.button.draw(buf)
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.button.set_focused(focused)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn set_size(&mut self, width: Option<f32>, height: Option<f32>) {
+        self.button.set_size(width, height)
+    }
+
+    fn set_id(&mut self, id: &str) {
+        self.button.set_id(id)
+    }
+
+    fn get_id(&self) -> &Option<String> {
+        self.button.get_id()
+    }
+}
@@ -0,0 +1,96 @@
+//! Titik doesn't have a tree view, so the tree view is displayed as a list of the nodes,
+//! where the children of the expanded nodes are indented below them.
+//! Each node is a ▸ or ▾ button which expands or collapses it, followed by the button
+//! of its label which selects it. The selected nodes are enclosed in brackets.
+//! The buttons are pressed with the mouse, or focused and pressed with the keyboard.
+use crate::{
+    widget::{
+        attribute::{
+            self, find_callback, find_value, selected_ids,
+            util::{find_nodes, is_multi_select},
+            TreeNode,
+        },
+        button, column, event,
+        event::{ExpandEvent, NodeSelectEvent},
+        row, text_label,
+    },
+    AttribKey, Element, Node,
+};
+
+/// the characters which indent the children of a node
+const INDENT: &str = "  ";
+
+/// the column of the rows of the displayed nodes
+pub(crate) fn indented_list<MSG>(element: Element<MSG>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let attrs = &element.attrs;
+    let nodes = find_nodes(attrs);
+    let selected = selected_ids(nodes);
+    let mut rows = vec![];
+    node_rows(attrs, nodes, &selected, 0, &mut rows);
+
+    let box_attrs = find_value(AttribKey::Key, attrs)
+        .map(|key| vec![attribute::key(key.clone())])
+        .unwrap_or_default();
+    column(box_attrs, rows)
+}
+
+/// push the rows of the nodes and the children of the expanded nodes
+fn node_rows<MSG>(
+    attrs: &[crate::Attribute<MSG>],
+    nodes: &[TreeNode],
+    selected: &[String],
+    depth: usize,
+    rows: &mut Vec<Node<MSG>>,
+) where
+    MSG: 'static,
+{
+    let multi_select = is_multi_select(attrs);
+    let expand_callbacks =
+        find_callback(AttribKey::ExpandEvent, attrs).unwrap_or_default();
+    let select_callbacks =
+        find_callback(AttribKey::NodeSelectEvent, attrs).unwrap_or_default();
+
+    for node in nodes {
+        let mut node_row =
+            vec![text_label(vec![attribute::value(INDENT.repeat(depth))])];
+        if node.has_children {
+            let marker = if node.expanded { "▾" } else { "▸" };
+            let mut toggle_attrs = vec![attribute::label(marker)];
+            for cb in expand_callbacks.iter() {
+                let cb = (*cb).clone();
+                let id = node.id.clone();
+                let expanded = !node.expanded;
+                toggle_attrs.push(event::on_click(move |_| {
+                    cb.emit(ExpandEvent::new(&id, expanded))
+                }));
+            }
+            node_row.push(button(toggle_attrs));
+        } else {
+            node_row.push(text_label(vec![attribute::value(" ")]));
+        }
+
+        let label = if node.selected {
+            format!("[{}]", node.label)
+        } else {
+            format!(" {} ", node.label)
+        };
+        let mut label_attrs = vec![attribute::label(label)];
+        for cb in select_callbacks.iter() {
+            let cb = (*cb).clone();
+            let id = node.id.clone();
+            let selected = selected.to_vec();
+            label_attrs.push(event::on_click(move |_| {
+                cb.emit(NodeSelectEvent::pressed(&id, &selected, multi_select))
+            }));
+        }
+        node_row.push(button(label_attrs));
+        rows.push(row(vec![], node_row));
+
+        if node.expanded {
+            node_rows(attrs, &node.children, selected, depth + 1, rows);
+        }
+    }
+}
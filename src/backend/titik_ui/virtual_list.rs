@@ -0,0 +1,72 @@
+//! Titik doesn't have a scrollable list, so the virtual list is displayed as a column
+//! of its visible rows, followed by the buttons which scroll it a page up or down.
+//! The overscan rows are built but not displayed.
+//! The buttons emit the scroll event, then the app sets the `scroll_top` of the list.
+use crate::{
+    widget::{
+        attribute::{
+            self, find_callback, find_value,
+            util::{
+                find_materialized_rows, find_row_geometry, find_scroll_top,
+                find_visible_rows,
+            },
+        },
+        button, column, event,
+        event::ScrollEvent,
+        row, text_label,
+    },
+    AttribKey, Element, Node,
+};
+
+/// the column of the visible rows, then the scroll buttons
+pub(crate) fn visible_rows<MSG>(element: Element<MSG>) -> Node<MSG>
+where
+    MSG: 'static,
+{
+    let attrs = &element.attrs;
+    let (row_count, row_height) = find_row_geometry(attrs);
+    let scroll_top = find_scroll_top(attrs);
+    let visible = find_visible_rows(attrs);
+    let materialized = find_materialized_rows(attrs);
+    let callbacks =
+        find_callback(AttribKey::ScrollEvent, attrs).unwrap_or_default();
+
+    let page_height = visible.len() as f64 * row_height;
+    let last_top = row_count.saturating_sub(visible.len()) as f64 * row_height;
+    let scroll_button = |label: &'static str, new_top: f64| {
+        let mut button_attrs = vec![attribute::label(label)];
+        for cb in callbacks.iter() {
+            let cb = (*cb).clone();
+            button_attrs.push(event::on_click(move |_| {
+                cb.emit(ScrollEvent::new(new_top))
+            }));
+        }
+        button(button_attrs)
+    };
+    let scroll_row = row(
+        vec![],
+        vec![
+            scroll_button("▲", (scroll_top - page_height).max(0.0)),
+            text_label(vec![attribute::value(format!(
+                "{}-{} of {}",
+                (visible.start + 1).min(visible.end),
+                visible.end,
+                row_count
+            ))]),
+            scroll_button("▼", (scroll_top + page_height).min(last_top)),
+        ],
+    );
+
+    let mut rows: Vec<Node<MSG>> = element
+        .children
+        .into_iter()
+        .skip(visible.start - materialized.start)
+        .take(visible.len())
+        .collect();
+    rows.push(scroll_row);
+
+    let box_attrs = find_value(AttribKey::Key, attrs)
+        .map(|key| vec![attribute::key(key.clone())])
+        .unwrap_or_default();
+    column(box_attrs, rows)
+}
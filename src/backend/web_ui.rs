@@ -1,12 +1,30 @@
 //! html backend where all the functionalities is offloaded into sauron
-use crate::{widget::layout::compute_node_layout, Backend, Component};
-use sauron::prelude::*;
-use std::{fmt::Debug, marker::PhantomData};
-use stretch::geometry::Size;
-use stretch::number::Number;
+use super::Dispatch;
+use crate::{
+    image_util, Backend, Cmd, Component, FileDialog, OnChosen, Program, Window,
+};
+use sauron::{
+    html::{attributes::*, button, div, text},
+    prelude::*,
+};
+use std::{fmt::Debug, rc::Rc};
 
+pub use custom_widget::{register_custom_widget, CustomWidget};
+
+mod canvas;
 mod convert_event;
 mod convert_widget;
+mod custom_widget;
+mod file_dialog;
+mod table;
+mod tree;
+mod virtual_list;
+
+/// the name of the keyframes which rotate the spinner
+const SPINNER_KEYFRAMES: &str = "sauron-native-spin";
+
+/// the animation of the spinner, which uses the keyframes added to the page
+const SPINNER_ANIMATION: &str = "sauron-native-spin 0.8s linear infinite";
 
 /// We wrap the App's Msg with this such that we can add high level behavior of the app
 /// such as automatically computing the layout when the window is resized
@@ -16,18 +34,24 @@ pub enum BackendMsg<MSG> {
     AppMsg(MSG),
     /// the app container is resized
     Resize(i32, i32),
+    /// the user closed the floating panel of the extra window with this id
+    CloseWindow(String),
 }
 
 /// holds the user application,
-/// this just wraps the app, so we can implement the Component trait for it
+/// this just wraps the app, so we can implement the Component trait for it.
+///
+/// Unlike the other backends, the msgs are not processed in the update loop of the `Program`.
+/// Sauron diffs and patches the html itself, so the msgs of the app are dispatched
+/// through the sauron program, which renders the view after each update.
+/// The `Program` still updates the app and computes the layout of the view,
+/// and the resizing of the browser is passed to it, which returns the msg of `Event::Resize`.
 pub struct HtmlApp<APP, MSG>
 where
     MSG: Clone + Debug + 'static,
     APP: Component<MSG> + 'static,
 {
-    app: APP,
-    browser_size: (i32, i32),
-    _phantom_data: PhantomData<MSG>,
+    program: Program<APP, MSG>,
 }
 
 impl<APP, MSG> HtmlApp<APP, MSG>
@@ -37,12 +61,141 @@ where
 {
     fn new(app: APP) -> Self {
         let browser_size = Browser::get_size();
-        HtmlApp {
-            app,
-            browser_size,
-            _phantom_data: PhantomData,
+        Self::set_document_title(&app.title());
+        if let Some(icon) = &app.window_config().icon {
+            Self::set_favicon(icon);
+        }
+        Self::add_animations();
+        let program =
+            Program::new(app, Some(Self::viewport_size(browser_size)));
+        program.set_title_applier(Self::set_document_title);
+        HtmlApp { program }
+    }
+
+    /// the title of the app is used as the title of the page
+    fn set_document_title(title: &str) {
+        if let Some(document) = web_sys::window().and_then(|w| w.document()) {
+            document.set_title(title);
+        }
+    }
+
+    /// the icon of the app is used as the favicon of the page
+    fn set_favicon(icon: &[u8]) {
+        let mime_type =
+            image_util::image_mime_type(icon).unwrap_or("image/png");
+        let href =
+            format!("data:{};base64,{}", mime_type, base64::encode(icon));
+        let document = web_sys::window()
+            .and_then(|w| w.document())
+            .expect("must have a document");
+        let link = document
+            .create_element("link")
+            .expect("must create a link element");
+        link.set_attribute("rel", "icon").expect("must set rel");
+        link.set_attribute("href", &href).expect("must set href");
+        if let Ok(Some(head)) = document.query_selector("head") {
+            head.append_child(&link).expect("must append the favicon");
         }
     }
+
+    /// the keyframes of the animated widgets are added to the page once,
+    /// the browser then runs the animations without redrawing the view
+    fn add_animations() {
+        let document = web_sys::window()
+            .and_then(|w| w.document())
+            .expect("must have a document");
+        let style = document
+            .create_element("style")
+            .expect("must create a style element");
+        style.set_text_content(Some(&format!(
+            "@keyframes {} {{ to {{ transform: rotate(360deg); }} }}",
+            SPINNER_KEYFRAMES
+        )));
+        if let Ok(Some(head)) = document.query_selector("head") {
+            head.append_child(&style).expect("must append the style");
+        }
+    }
+
+    /// the extra windows are displayed as floating panels on top of the view of the app,
+    /// the panels cascade from the top left when the window has no position
+    fn window_panel(
+        index: usize,
+        window: &Window<MSG>,
+        cur_node_idx: &mut usize,
+    ) -> sauron::Node<BackendMsg<MSG>> {
+        let (width, height) = window.config.size;
+        let offset = 40 * (index as i32 + 1);
+        let (x, y) = window.config.position.unwrap_or((offset, offset));
+        let id = window.id.clone();
+        let content = convert_widget::widget_tree_to_html_node(
+            &window.view,
+            cur_node_idx,
+        );
+        div(
+            vec![
+                class("window_panel"),
+                styles([
+                    ("position", "fixed".to_string()),
+                    ("left", px(x)),
+                    ("top", px(y)),
+                    ("width", px(width)),
+                    ("z-index", (100 + index).to_string()),
+                    ("background-color", "white".to_string()),
+                    ("border", "1px solid #888".to_string()),
+                    ("box-shadow", "0 4px 12px rgba(0,0,0,0.3)".to_string()),
+                ]),
+            ],
+            vec![
+                div(
+                    vec![
+                        class("window_panel_title"),
+                        styles([
+                            ("display", "flex"),
+                            ("justify-content", "space-between"),
+                            ("padding", "2px 4px"),
+                            ("background-color", "#ddd"),
+                        ]),
+                    ],
+                    vec![
+                        text(&window.title),
+                        button(
+                            vec![on_click(move |_| {
+                                BackendMsg::CloseWindow(id.clone())
+                            })],
+                            vec![text("\u{2715}")],
+                        ),
+                    ],
+                ),
+                div(
+                    vec![
+                        class("window_panel_content"),
+                        styles([
+                            ("height", px(height)),
+                            ("overflow", "auto".to_string()),
+                        ]),
+                    ],
+                    vec![content.map_msg(BackendMsg::AppMsg)],
+                ),
+            ],
+        )
+    }
+
+    /// the size used for calculating the layout of the view
+    fn viewport_size(browser_size: (i32, i32)) -> (f32, f32) {
+        let (w, h) = browser_size;
+        (w as f32 - 100.0, h as f32 - 20.0)
+    }
+
+    /// convert the Cmd of the app into a sauron Cmd,
+    /// where the sauron program is used as the dispatcher
+    fn to_sauron_cmd(
+        cmd: Cmd<Rc<dyn Dispatch<MSG>>>,
+    ) -> sauron::cmd::Cmd<sauron::Program<Self, BackendMsg<MSG>>> {
+        sauron::cmd::Cmd::new(move |program| {
+            let dispatcher: Rc<dyn Dispatch<MSG>> = Rc::new(program);
+            cmd.clone().emit(&dispatcher);
+        })
+    }
 }
 
 impl<APP, MSG> sauron::Component<BackendMsg<MSG>> for HtmlApp<APP, MSG>
@@ -52,7 +205,10 @@ where
 {
     fn init(&self) -> sauron::cmd::Cmd<sauron::Program<Self, BackendMsg<MSG>>> {
         log::debug!("init in HtmlApp..");
-        Browser::on_resize(BackendMsg::Resize)
+        sauron::cmd::Cmd::batch(vec![
+            Browser::on_resize(BackendMsg::Resize),
+            Self::to_sauron_cmd(self.program.app().init()),
+        ])
     }
 
     fn update(
@@ -61,35 +217,70 @@ where
     ) -> sauron::cmd::Cmd<sauron::Program<Self, BackendMsg<MSG>>> {
         match msg {
             BackendMsg::AppMsg(msg) => {
-                self.app.update(msg);
+                let cmd = self.program.update(msg);
+                self.program.update_title();
+                Self::to_sauron_cmd(cmd)
+            }
+            BackendMsg::CloseWindow(id) => {
+                match self.program.window_close_msg(&id) {
+                    Some(msg) => self.update(BackendMsg::AppMsg(msg)),
+                    None => sauron::cmd::Cmd::none(),
+                }
             }
             BackendMsg::Resize(w, h) => {
                 log::debug!("window is resizing..");
-                self.browser_size = (w, h);
+                let (width, height) = Self::viewport_size((w, h));
+                match self.program.resize_msg(width, height) {
+                    Some(msg) => self.update(BackendMsg::AppMsg(msg)),
+                    None => sauron::cmd::Cmd::none(),
+                }
             }
         }
-        sauron::cmd::Cmd::none()
     }
 
     fn view(&self) -> sauron::Node<BackendMsg<MSG>> {
         let t1 = sauron::now();
 
-        let mut view = self.app.view();
-        let (w, h) = self.browser_size;
-        let (adjusted_w, adjusted_h) = (w as f32 - 100.0, h as f32 - 20.0);
-        compute_node_layout(
-            &mut view,
-            Size {
-                width: Number::Defined(adjusted_w),
-                height: Number::Defined(adjusted_h),
-            },
-        );
+        let view = self.program.view();
 
         let t2 = sauron::now();
         log::warn!("layout computation took: {}ms", t2 - t1);
 
-        let html_view = convert_widget::widget_tree_to_html_node(&view, &mut 0);
-        html_view.map_msg(BackendMsg::AppMsg)
+        let mut cur_node_idx = 0;
+        let html_view =
+            convert_widget::widget_tree_to_html_node(&view, &mut cur_node_idx);
+        let html_view = html_view.map_msg(BackendMsg::AppMsg);
+
+        let windows = self.program.window_views();
+        if windows.is_empty() {
+            html_view
+        } else {
+            let panels = windows.iter().enumerate().map(|(index, window)| {
+                Self::window_panel(index, window, &mut cur_node_idx)
+            });
+            div(vec![], std::iter::once(html_view).chain(panels).collect())
+        }
+    }
+}
+
+impl<APP, MSG> Dispatch<MSG>
+    for sauron::Program<HtmlApp<APP, MSG>, BackendMsg<MSG>>
+where
+    MSG: Clone + Debug + 'static,
+    APP: Component<MSG> + 'static,
+{
+    fn dispatch(&self, msg: MSG) {
+        sauron::Dispatch::dispatch(self, BackendMsg::AppMsg(msg));
+    }
+
+    fn choose_files(&self, dialog: FileDialog, on_chosen: OnChosen<MSG>) {
+        let program = self.clone();
+        file_dialog::choose_files(dialog, move |files| {
+            sauron::Dispatch::dispatch(
+                &program,
+                BackendMsg::AppMsg(on_chosen(files)),
+            );
+        });
     }
 }
 
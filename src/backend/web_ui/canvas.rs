@@ -0,0 +1,219 @@
+//! The canvas is a `<canvas>` element, which can only be drawn once sauron has patched it
+//! into the page. The commands of each canvas in the view are kept until the next
+//! animation frame, then the canvas is looked up by its id and redrawn with its 2d context.
+//!
+//! The images are decoded by the browser, an image which is not loaded yet
+//! is drawn once it is loaded, over the commands after it.
+use super::convert_event;
+use crate::{
+    image_util,
+    widget::attribute::{
+        find_callback, util::find_commands, Color, DrawCommand,
+    },
+    AttribKey, Attribute, Value,
+};
+use sauron::{
+    html::{attributes::*, events::on},
+    prelude::*,
+};
+use std::{cell::RefCell, collections::BTreeMap, fmt::Debug};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlImageElement};
+
+/// the font of the text commands
+const FONT_FAMILY: &str = "sans-serif";
+
+thread_local! {
+    /// the commands of the canvases which are drawn in the next animation frame
+    static PENDING_CANVASES: RefCell<BTreeMap<String, Vec<DrawCommand>>> =
+        RefCell::new(BTreeMap::new());
+}
+
+/// create the canvas element with the size of its layout,
+/// its commands are drawn in the next animation frame
+pub fn canvas<MSG>(
+    attrs: &[Attribute<MSG>],
+    widget_key: Option<&Value>,
+    canvas_id: String,
+    size: (f32, f32),
+) -> sauron::Node<MSG>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let (width, height) = size;
+    let mut attributes = vec![
+        class("Canvas"),
+        id(canvas_id.clone()),
+        if let Some(widget_key) = widget_key {
+            key(widget_key.to_string())
+        } else {
+            empty_attr()
+        },
+        attr("width", width.round().to_string()),
+        attr("height", height.round().to_string()),
+        styles([("display", "block")]),
+    ];
+    let mouse_events = [
+        (AttribKey::ClickEvent, "click"),
+        (AttribKey::MouseDown, "mousedown"),
+        (AttribKey::MouseUp, "mouseup"),
+        (AttribKey::MouseMove, "mousemove"),
+    ];
+    for (attrib_key, event_name) in mouse_events.iter() {
+        for cb in find_callback(attrib_key.clone(), attrs).unwrap_or_default() {
+            let cb = cb.clone();
+            attributes.push(on(
+                *event_name,
+                move |event: sauron::web_sys::Event| {
+                    // the offset of the mouse is from the top left of the canvas
+                    let mut mouse_event =
+                        convert_event::from_mouse_event(event.unchecked_into());
+                    mouse_event.coordinate.x = mouse_event.coordinate.offset_x;
+                    mouse_event.coordinate.y = mouse_event.coordinate.offset_y;
+                    cb.emit(mouse_event)
+                },
+            ));
+        }
+    }
+
+    PENDING_CANVASES.with(|pending| {
+        let mut pending = pending.borrow_mut();
+        if pending.is_empty() {
+            request_draw();
+        }
+        pending.insert(canvas_id, find_commands(attrs).to_vec());
+    });
+
+    sauron::html::canvas(attributes, vec![])
+}
+
+/// draw the pending canvases in the next animation frame,
+/// which is after the view is patched into the page
+fn request_draw() {
+    let window = web_sys::window().expect("must have a window");
+    let draw_pending = Closure::once_into_js(draw_pending);
+    window
+        .request_animation_frame(draw_pending.unchecked_ref())
+        .expect("must request an animation frame");
+}
+
+fn draw_pending() {
+    let pending = PENDING_CANVASES
+        .with(|pending| std::mem::take(&mut *pending.borrow_mut()));
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .expect("must have a document");
+    for (canvas_id, commands) in pending {
+        let canvas = match document
+            .get_element_by_id(&canvas_id)
+            .and_then(|element| element.dyn_into::<HtmlCanvasElement>().ok())
+        {
+            Some(canvas) => canvas,
+            None => continue,
+        };
+        if let Ok(Some(context)) = canvas.get_context("2d") {
+            let context: CanvasRenderingContext2d = context.unchecked_into();
+            context.clear_rect(
+                0.0,
+                0.0,
+                canvas.width() as f64,
+                canvas.height() as f64,
+            );
+            draw(&context, &commands);
+        }
+    }
+}
+
+/// draw the commands in order, the colors and line width are reset first
+/// since the context keeps them from the previous drawing
+fn draw(context: &CanvasRenderingContext2d, commands: &[DrawCommand]) {
+    let black = Color::rgb(0, 0, 0);
+    context.set_fill_style(&JsValue::from_str(&black.to_css()));
+    context.set_stroke_style(&JsValue::from_str(&black.to_css()));
+    context.set_line_width(1.0);
+    context.begin_path();
+    for command in commands {
+        match command {
+            DrawCommand::BeginPath => context.begin_path(),
+            DrawCommand::MoveTo { x, y } => context.move_to(*x, *y),
+            DrawCommand::LineTo { x, y } => context.line_to(*x, *y),
+            DrawCommand::CurveTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+            } => context.bezier_curve_to(*x1, *y1, *x2, *y2, *x, *y),
+            DrawCommand::Arc {
+                x,
+                y,
+                radius,
+                start_angle,
+                end_angle,
+            } => {
+                // a negative radius is an error, which is not drawn
+                let _ = context.arc(*x, *y, *radius, *start_angle, *end_angle);
+            }
+            DrawCommand::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => context.rect(*x, *y, *width, *height),
+            DrawCommand::ClosePath => context.close_path(),
+            DrawCommand::FillColor(color) => {
+                context.set_fill_style(&JsValue::from_str(&color.to_css()))
+            }
+            DrawCommand::StrokeColor(color) => {
+                context.set_stroke_style(&JsValue::from_str(&color.to_css()))
+            }
+            DrawCommand::LineWidth(width) => context.set_line_width(*width),
+            DrawCommand::Fill => context.fill(),
+            DrawCommand::Stroke => context.stroke(),
+            DrawCommand::Text { x, y, text, size } => {
+                context.set_font(&format!("{}px {}", size, FONT_FAMILY));
+                let _ = context.fill_text(text, *x, *y);
+            }
+            DrawCommand::Image {
+                x,
+                y,
+                width,
+                height,
+                data,
+            } => draw_image(context, data, (*x, *y, *width, *height)),
+        }
+    }
+}
+
+fn draw_image(
+    context: &CanvasRenderingContext2d,
+    data: &[u8],
+    (x, y, width, height): (f64, f64, f64, f64),
+) {
+    let mime_type = image_util::image_mime_type(data).unwrap_or("image/png");
+    let image = HtmlImageElement::new().expect("must create an image");
+    image.set_src(&format!(
+        "data:{};base64,{}",
+        mime_type,
+        base64::encode(data)
+    ));
+    if image.complete() {
+        let _ = context.draw_image_with_html_image_element_and_dw_and_dh(
+            &image, x, y, width, height,
+        );
+    } else {
+        let context = context.clone();
+        let image_clone = image.clone();
+        let on_load = Closure::once_into_js(move || {
+            let _ = context.draw_image_with_html_image_element_and_dw_and_dh(
+                &image_clone,
+                x,
+                y,
+                width,
+                height,
+            );
+        });
+        image.set_onload(Some(on_load.unchecked_ref()));
+    }
+}
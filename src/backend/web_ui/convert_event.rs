@@ -57,6 +57,11 @@ pub fn to_input_event(input_event: sauron::InputEvent) -> InputEvent {
     InputEvent::new(input_event.value)
 }
 
+/// the value of a range input is converted into a number
+pub fn to_number_input_event(input_event: sauron::InputEvent) -> InputEvent {
+    InputEvent::new(input_event.value.parse::<f64>().unwrap_or(0.0))
+}
+
 pub fn to_key_event(keyboard_event: &sauron::KeyboardEvent) -> KeyEvent {
     KeyEvent {
         key_code: get_keycode(keyboard_event),
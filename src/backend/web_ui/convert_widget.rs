@@ -1,13 +1,20 @@
 use super::convert_event;
-use crate::image_util;
-use crate::widget::attribute::util::{get_id, get_key, get_layout};
-use crate::widget::event::KeyCode;
-use crate::{widget::attribute::find_value, AttribKey, Widget};
+use super::custom_widget::CustomWidget;
+use crate::widget::attribute::util::{
+    find_fraction, find_options, find_orientation, find_range, get_id, get_key,
+    get_layout, is_indeterminate,
+};
+use crate::widget::event::{InputEvent, KeyCode};
+use crate::{backend::registry, image_util};
+use crate::{
+    widget::attribute::{find_callback, find_value, Orientation},
+    AttribKey, Widget,
+};
 use sauron::{
     html::{attributes::*, div, img, input, text},
     prelude::*,
 };
-use std::fmt::Debug;
+use std::{fmt::Debug, rc::Rc};
 
 /// converts widget virtual node tree into an html node tree
 pub fn widget_tree_to_html_node<MSG>(
@@ -239,6 +246,111 @@ where
             ],
             html_children,
         ),
+        // all the pages are kept in the panel, so the inactive pages keep their state,
+        // only the page of the active tab is displayed
+        Widget::TabBox => {
+            let selected = find_value(AttribKey::Selected, &attrs)
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0) as usize;
+
+            let callbacks = find_callback(AttribKey::InputEvent, &attrs)
+                .unwrap_or_default();
+            let tabs = element
+                .get_children()
+                .iter()
+                .enumerate()
+                .map(|(index, widget_child)| {
+                    let label = widget_child
+                        .get_attributes()
+                        .and_then(|child_attrs| {
+                            find_value(AttribKey::Label, child_attrs)
+                        })
+                        .map(|v| v.to_string())
+                        .unwrap_or_default();
+                    let is_active = index == selected;
+                    let mut attributes = vec![
+                        class(if is_active { "Tab active" } else { "Tab" }),
+                        styles([
+                            ("padding", "4px 12px"),
+                            ("border", "1px solid #ccc"),
+                            ("border-bottom", "none"),
+                            ("margin-right", "2px"),
+                            ("cursor", "pointer"),
+                        ]),
+                        styles([(
+                            "background-color",
+                            if is_active { "#fff" } else { "#eee" },
+                        )]),
+                    ];
+                    for cb in callbacks.iter() {
+                        let cb = (*cb).clone();
+                        attributes.push(on_click(move |_| {
+                            cb.emit(InputEvent::new(index))
+                        }));
+                    }
+                    button(attributes, vec![text(label)])
+                })
+                .collect::<Vec<_>>();
+
+            let pages = html_children
+                .into_iter()
+                .enumerate()
+                .map(|(index, html_child)| {
+                    div(
+                        vec![
+                            class("TabPage"),
+                            styles([(
+                                "display",
+                                if index == selected { "flex" } else { "none" },
+                            )]),
+                            styles([
+                                ("flex-direction", "column"),
+                                ("flex-grow", "1"),
+                            ]),
+                        ],
+                        vec![html_child],
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            div(
+                vec![
+                    class("TabBox"),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                    styles([("display", "flex"), ("flex-direction", "column")]),
+                ],
+                vec![
+                    div(
+                        vec![
+                            class("TabStrip"),
+                            styles([
+                                ("display", "flex"),
+                                ("flex-direction", "row"),
+                                ("border-bottom", "1px solid #ccc"),
+                            ]),
+                        ],
+                        tabs,
+                    ),
+                    div(
+                        vec![
+                            class("TabPanel"),
+                            styles([
+                                ("display", "flex"),
+                                ("flex-direction", "column"),
+                                ("flex-grow", "1"),
+                                ("border", "1px solid #ccc"),
+                                ("border-top", "none"),
+                            ]),
+                        ],
+                        pages,
+                    ),
+                ],
+            )
+        }
         Widget::Label => {
             let value = find_value(AttribKey::Value, &attrs)
                 .map(|v| v.to_string())
@@ -428,6 +540,201 @@ where
             )
             .add_attributes(attributes)
         }
+        // an indeterminate progress bar is a progress element without a value,
+        // which is animated by the browser
+        Widget::ProgressBar => {
+            let progress_label =
+                find_value(AttribKey::Label, &attrs).map(|v| v.to_string());
+            div(
+                vec![
+                    class("ProgressBar"),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                    styles([("display", "flex"), ("align-items", "center")]),
+                ],
+                vec![
+                    progress(
+                        vec![
+                            attr("max", "1"),
+                            if is_indeterminate(&attrs) {
+                                empty_attr()
+                            } else {
+                                value(find_fraction(&attrs).to_string())
+                            },
+                            styles([("flex-grow", "1")]),
+                        ],
+                        vec![],
+                    ),
+                    if let Some(progress_label) = progress_label {
+                        span(vec![], vec![text(progress_label)])
+                    } else {
+                        span(vec![], vec![])
+                    },
+                ],
+            )
+        }
+        // the spinner is a ring rotated with the keyframes added to the page by the app
+        Widget::Spinner => div(
+            vec![
+                class("Spinner"),
+                if let Some(widget_key) = widget_key {
+                    key(widget_key.to_string())
+                } else {
+                    empty_attr()
+                },
+                styles([
+                    ("width", "1em"),
+                    ("height", "1em"),
+                    ("border", "2px solid #ccc"),
+                    ("border-top-color", "#333"),
+                    ("border-radius", "50%"),
+                    ("animation", super::SPINNER_ANIMATION),
+                ]),
+            ],
+            vec![],
+        ),
+        Widget::Table => super::table::table(&attrs, widget_key),
+        Widget::TreeView => super::tree::tree_view(&attrs, widget_key),
+        Widget::Canvas => super::canvas::canvas(
+            &attrs,
+            widget_key,
+            format!("Canvas{}", cur_node_idx),
+            (layout.size.width, layout.size.height),
+        ),
+        Widget::VirtualList => {
+            super::virtual_list::virtual_list(&attrs, widget_key, html_children)
+        }
+        // an editable combo box is a text input which suggests the options from a datalist
+        Widget::ComboBox => {
+            let combo_value = find_value(AttribKey::Value, &attrs)
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+            let is_editable = find_value(AttribKey::Editable, &attrs)
+                .map(|v| v.as_bool())
+                .unwrap_or(false);
+            let mut attributes = vec![];
+            for att in attrs {
+                match att.name() {
+                    AttribKey::InputEvent => {
+                        for cb in att.get_callback() {
+                            let cb = cb.clone();
+                            attributes.push(on_input(move |ev| {
+                                cb.emit(convert_event::to_input_event(ev))
+                            }));
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            let combo_key = if let Some(widget_key) = widget_key {
+                key(widget_key.to_string())
+            } else {
+                empty_attr()
+            };
+            if is_editable {
+                let list_id = format!("ComboBoxOptions{}", cur_node_idx);
+                let html_options = find_options(&attrs)
+                    .iter()
+                    .map(|opt| {
+                        option(
+                            vec![value(opt.value.clone())],
+                            vec![text(&opt.label)],
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                div(
+                    vec![class("ComboBox"), combo_key],
+                    vec![
+                        input(
+                            vec![
+                                r#type("text"),
+                                attr("list", list_id.clone()),
+                                value(combo_value),
+                                styles([("width", "100%")]),
+                            ],
+                            vec![],
+                        )
+                        .add_attributes(attributes),
+                        datalist(vec![id(&list_id)], html_options),
+                    ],
+                )
+            } else {
+                let html_options = find_options(&attrs)
+                    .iter()
+                    .map(|opt| {
+                        option(
+                            vec![value(opt.value.clone())],
+                            vec![text(&opt.label)],
+                        )
+                        .add_attributes(attrs_flag([
+                            ("selected", "selected", opt.value == combo_value),
+                        ]))
+                    })
+                    .collect::<Vec<_>>();
+                select(
+                    vec![
+                        class("ComboBox"),
+                        combo_key,
+                        value(combo_value.clone()),
+                        styles([("width", "100%")]),
+                    ],
+                    html_options,
+                )
+                .add_attributes(attributes)
+            }
+        }
+        Widget::Slider => {
+            let (min, max, step) = find_range(&attrs);
+            let slider_value = find_value(AttribKey::Value, &attrs)
+                .and_then(|v| v.as_f64())
+                .unwrap_or(min);
+            let mut attributes = vec![];
+            for att in attrs {
+                match att.name() {
+                    AttribKey::InputEvent => {
+                        for cb in att.get_callback() {
+                            let cb = cb.clone();
+                            attributes.push(on_input(move |ev| {
+                                cb.emit(convert_event::to_number_input_event(
+                                    ev,
+                                ))
+                            }));
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            // a vertical range input has its highest value at the top
+            let orientation_styles = match find_orientation(&attrs) {
+                Orientation::Horizontal => styles([("width", "100%")]),
+                Orientation::Vertical => styles([
+                    ("writing-mode", "vertical-lr"),
+                    ("direction", "rtl"),
+                    ("height", "100%"),
+                ]),
+            };
+            input(
+                vec![
+                    class("Slider"),
+                    r#type("range"),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                    attr("min", min.to_string()),
+                    attr("max", max.to_string()),
+                    attr("step", step.to_string()),
+                    value(slider_value.to_string()),
+                    orientation_styles,
+                ],
+                vec![],
+            )
+            .add_attributes(attributes)
+        }
         Widget::TextArea => {
             let txt_value = find_value(AttribKey::Value, &attrs)
                 .map(|v| v.to_string())
@@ -742,5 +1049,125 @@ where
             )
             .add_attributes(attributes)
         }
+        // the backdrop covers the whole page, so the widgets behind the dialog can't be clicked
+        Widget::Dialog => {
+            let title = find_value(AttribKey::Label, &attrs)
+                .map(|v| v.to_string())
+                .unwrap_or_default();
+
+            let (buttons, body): (Vec<_>, Vec<_>) = html_children
+                .into_iter()
+                .zip(element.get_children().iter())
+                .partition(|(_, widget_child)| {
+                    widget_child.tag() == Some(&Widget::Button)
+                });
+
+            div(
+                vec![
+                    class("DialogBackdrop"),
+                    if let Some(widget_key) = widget_key {
+                        key(widget_key.to_string())
+                    } else {
+                        empty_attr()
+                    },
+                    styles([
+                        ("position", "fixed"),
+                        ("top", "0"),
+                        ("left", "0"),
+                        ("width", "100%"),
+                        ("height", "100%"),
+                        ("display", "flex"),
+                        ("align-items", "center"),
+                        ("justify-content", "center"),
+                        ("background-color", "rgba(0, 0, 0, 0.4)"),
+                        ("z-index", "1000"),
+                    ]),
+                ],
+                vec![div(
+                    vec![
+                        class("Dialog"),
+                        styles([
+                            ("display", "flex"),
+                            ("flex-direction", "column"),
+                            ("background-color", "#fff"),
+                            ("border", "1px solid #888"),
+                            ("box-shadow", "0 4px 16px rgba(0, 0, 0, 0.4)"),
+                        ]),
+                        styles([
+                            ("width", px(layout.size.width)),
+                            ("min-height", px(layout.size.height)),
+                        ]),
+                    ],
+                    vec![
+                        div(
+                            vec![
+                                class("DialogTitle"),
+                                styles([
+                                    ("padding", "4px 8px"),
+                                    ("font-weight", "bold"),
+                                    ("border-bottom", "1px solid #ccc"),
+                                ]),
+                            ],
+                            vec![text(title)],
+                        ),
+                        div(
+                            vec![
+                                class("DialogBody"),
+                                styles([
+                                    ("display", "flex"),
+                                    ("flex-direction", "column"),
+                                    ("flex-grow", "1"),
+                                    ("padding", "8px"),
+                                ]),
+                            ],
+                            body.into_iter().map(|(child, _)| child).collect(),
+                        ),
+                        div(
+                            vec![
+                                class("DialogButtons"),
+                                styles([
+                                    ("display", "flex"),
+                                    ("flex-direction", "row"),
+                                    ("justify-content", "flex-end"),
+                                    ("padding", "4px 8px"),
+                                ]),
+                            ],
+                            buttons
+                                .into_iter()
+                                .map(|(child, _)| child)
+                                .collect(),
+                        ),
+                    ],
+                )],
+            )
+        }
+        Widget::Custom(name) => {
+            match registry::lookup_custom_widget::<Rc<dyn CustomWidget<MSG>>>(
+                name,
+            ) {
+                Some(custom_widget) => {
+                    let node = custom_widget.create(attrs);
+                    html_children.into_iter().fold(node, |node, child| {
+                        custom_widget.add_child(node, child)
+                    })
+                }
+                // the children are still displayed in the placeholder
+                None => div(
+                    vec![
+                        class("Custom"),
+                        if let Some(widget_key) = widget_key {
+                            key(widget_key.to_string())
+                        } else {
+                            empty_attr()
+                        },
+                        styles(vec![
+                            ("display", "flex"),
+                            ("flex-direction", "column"),
+                        ]),
+                    ],
+                    html_children,
+                ),
+            }
+        }
     }
 }
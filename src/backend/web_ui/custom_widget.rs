@@ -0,0 +1,34 @@
+//! Custom widgets for the html backend
+use crate::{backend::registry, Attribute};
+use std::rc::Rc;
+
+/// A widget which is not part of the `Widget` enum, created with
+/// `widget::custom(name, attrs, children)` in the view.
+///
+/// There is no `update_attributes` in the html backend, since the html nodes are
+/// recreated on every view and sauron patches the changes into the DOM.
+pub trait CustomWidget<MSG> {
+    /// create the html node from the attributes,
+    /// the event callbacks are converted into the html event listeners
+    fn create(&self, attrs: &[Attribute<MSG>]) -> sauron::Node<MSG>;
+
+    /// add the html child node, by default the child is appended to the node
+    fn add_child(
+        &self,
+        node: sauron::Node<MSG>,
+        child: sauron::Node<MSG>,
+    ) -> sauron::Node<MSG> {
+        node.add_children(vec![child])
+    }
+}
+
+/// register the custom widget which will be used to create
+/// the `Widget::Custom` of the same name in the html backend
+pub fn register_custom_widget<MSG, CW>(name: &str, custom_widget: CW)
+where
+    MSG: 'static,
+    CW: CustomWidget<MSG> + 'static,
+{
+    let custom_widget: Rc<dyn CustomWidget<MSG>> = Rc::new(custom_widget);
+    registry::register(name, custom_widget);
+}
@@ -0,0 +1,128 @@
+//! The browser can not access the file system, so the opened files are read
+//! from an `<input type=file>` and the saved contents are downloaded.
+use crate::{ChosenFile, FileAction, FileDialog};
+use std::{cell::RefCell, path::PathBuf, rc::Rc};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{FileReader, HtmlElement, HtmlInputElement};
+
+/// show the file dialog, `on_chosen` is called with the chosen files
+/// or with no files when the user cancelled
+pub(crate) fn choose_files<F>(dialog: FileDialog, on_chosen: F)
+where
+    F: Fn(Vec<ChosenFile>) + 'static,
+{
+    match dialog.action {
+        FileAction::Open => open_files(dialog, on_chosen),
+        FileAction::Save => save_file(dialog, on_chosen),
+    }
+}
+
+/// the files are read before `on_chosen` is called, since the browser only
+/// exposes the names of the files
+fn open_files<F>(dialog: FileDialog, on_chosen: F)
+where
+    F: Fn(Vec<ChosenFile>) + 'static,
+{
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .expect("must have a document");
+    let input: HtmlInputElement = document
+        .create_element("input")
+        .expect("must create an input element")
+        .unchecked_into();
+    input.set_type("file");
+    input.set_multiple(dialog.multiple);
+    let accept: Vec<String> = dialog
+        .filters
+        .iter()
+        .flat_map(|filter| filter.extensions.iter())
+        .map(|extension| format!(".{}", extension))
+        .collect();
+    if !accept.is_empty() {
+        input.set_accept(&accept.join(","));
+    }
+
+    let on_chosen = Rc::new(on_chosen);
+    let on_chosen_clone = Rc::clone(&on_chosen);
+    let input_clone = input.clone();
+    let on_change = Closure::once(move |_event: web_sys::Event| {
+        let file_list = match input_clone.files() {
+            Some(file_list) if file_list.length() > 0 => file_list,
+            _ => return on_chosen_clone(vec![]),
+        };
+        let files = (0..file_list.length())
+            .filter_map(|index| file_list.get(index))
+            .collect::<Vec<_>>();
+        let loaded: Rc<RefCell<Vec<Option<ChosenFile>>>> =
+            Rc::new(RefCell::new(vec![None; files.len()]));
+        for (index, file) in files.into_iter().enumerate() {
+            let reader = FileReader::new().expect("must create a file reader");
+            let reader_clone = reader.clone();
+            let loaded = Rc::clone(&loaded);
+            let on_chosen = Rc::clone(&on_chosen_clone);
+            let name = file.name();
+            let on_load = Closure::once(move |_event: web_sys::Event| {
+                let result = reader_clone.result().expect("must have a result");
+                let bytes = js_sys::Uint8Array::new(&result).to_vec();
+                loaded.borrow_mut()[index] = Some(ChosenFile {
+                    path: PathBuf::from(name),
+                    bytes: Some(bytes),
+                });
+                // the files are passed once all of them are read
+                if loaded.borrow().iter().all(Option::is_some) {
+                    let files =
+                        loaded.borrow_mut().drain(..).flatten().collect();
+                    on_chosen(files);
+                }
+            });
+            reader.set_onload(Some(on_load.as_ref().unchecked_ref()));
+            on_load.forget();
+            reader
+                .read_as_array_buffer(&file)
+                .expect("must read the file");
+        }
+    });
+    input.set_onchange(Some(on_change.as_ref().unchecked_ref()));
+    on_change.forget();
+
+    let on_cancel = Closure::once(move |_event: web_sys::Event| {
+        on_chosen(vec![]);
+    });
+    input
+        .add_event_listener_with_callback(
+            "cancel",
+            on_cancel.as_ref().unchecked_ref(),
+        )
+        .expect("must add the cancel listener");
+    on_cancel.forget();
+
+    input.click();
+}
+
+/// the contents of the dialog are downloaded with the suggested name,
+/// which is then passed as the path of the chosen file
+fn save_file<F>(dialog: FileDialog, on_chosen: F)
+where
+    F: Fn(Vec<ChosenFile>) + 'static,
+{
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .expect("must have a document");
+    let name = dialog
+        .current_name
+        .clone()
+        .unwrap_or_else(|| "download".to_string());
+    let contents = dialog.contents.clone().unwrap_or_default();
+    let href = format!(
+        "data:application/octet-stream;base64,{}",
+        base64::encode(&contents)
+    );
+    let link = document
+        .create_element("a")
+        .expect("must create a link element");
+    link.set_attribute("href", &href).expect("must set href");
+    link.set_attribute("download", &name)
+        .expect("must set download");
+    link.unchecked_into::<HtmlElement>().click();
+    on_chosen(vec![ChosenFile::new(PathBuf::from(name))]);
+}
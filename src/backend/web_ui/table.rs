@@ -0,0 +1,204 @@
+//! The table is an html table in a scrollable div.
+//! Pressing the header of a sortable column emits the sort event, pressing a row selects it.
+use crate::{
+    image_util,
+    widget::{
+        attribute::{
+            find_callback,
+            util::{
+                cell_text, find_columns, find_rows, find_selected, find_sort,
+            },
+            CellKind, TableColumn,
+        },
+        event::{CellEditEvent, InputEvent, SortEvent},
+    },
+    AttribKey, Attribute, Value,
+};
+use sauron::{
+    html::{attributes::*, div, img, input, text},
+    prelude::*,
+};
+use std::fmt::Debug;
+
+/// convert the columns and rows of the table into an html table
+pub fn table<MSG>(
+    attrs: &[Attribute<MSG>],
+    widget_key: Option<&Value>,
+) -> sauron::Node<MSG>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let columns = find_columns(attrs);
+    let sort = find_sort(attrs);
+    let selected = find_selected(attrs);
+    let select_callbacks =
+        find_callback(AttribKey::InputEvent, attrs).unwrap_or_default();
+    let sort_callbacks =
+        find_callback(AttribKey::SortEvent, attrs).unwrap_or_default();
+
+    let headers = columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            let indicator = match sort {
+                Some((sort_column, false)) if sort_column == index => " ▲",
+                Some((sort_column, true)) if sort_column == index => " ▼",
+                _ => "",
+            };
+            let mut attributes = vec![];
+            if column.sortable {
+                attributes.push(styles([("cursor", "pointer")]));
+                for cb in sort_callbacks.iter() {
+                    let cb = (*cb).clone();
+                    attributes.push(on_click(move |_| {
+                        cb.emit(SortEvent::pressed(index, sort))
+                    }));
+                }
+            }
+            if let Some(width) = column.width {
+                attributes.push(styles([("width", px(width))]));
+            }
+            th(
+                attributes,
+                vec![text(format!("{}{}", column.title, indicator))],
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let rows = find_rows(attrs)
+        .iter()
+        .enumerate()
+        .map(|(row_index, row)| {
+            let is_selected = selected == Some(row_index);
+            let mut attributes = vec![if is_selected {
+                class("selected")
+            } else {
+                empty_attr()
+            }];
+            attributes.push(styles_flag([(
+                "background-color",
+                "#cce0ff",
+                is_selected,
+            )]));
+            for cb in select_callbacks.iter() {
+                let cb = (*cb).clone();
+                attributes.push(on_click(move |_| {
+                    cb.emit(InputEvent::new(row_index))
+                }));
+            }
+            let cells = columns
+                .iter()
+                .enumerate()
+                .map(|(index, column)| {
+                    td(
+                        vec![],
+                        vec![table_cell(
+                            attrs,
+                            row_index,
+                            index,
+                            column,
+                            row.get(index),
+                        )],
+                    )
+                })
+                .collect::<Vec<_>>();
+            tr(attributes, cells)
+        })
+        .collect::<Vec<_>>();
+
+    div(
+        vec![
+            class("Table"),
+            if let Some(widget_key) = widget_key {
+                key(widget_key.to_string())
+            } else {
+                empty_attr()
+            },
+            styles([("overflow", "auto")]),
+        ],
+        vec![sauron::html::table(
+            vec![styles([("border-collapse", "collapse"), ("width", "100%")])],
+            vec![
+                thead(vec![], vec![tr(vec![], headers)]),
+                tbody(vec![], rows),
+            ],
+        )],
+    )
+}
+
+/// the content of the cell, displayed according to the kind of its column
+fn table_cell<MSG>(
+    attrs: &[Attribute<MSG>],
+    row_index: usize,
+    index: usize,
+    column: &TableColumn,
+    cell: Option<&Value>,
+) -> sauron::Node<MSG>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let edit_callbacks =
+        find_callback(AttribKey::CellEditEvent, attrs).unwrap_or_default();
+    match column.kind {
+        CellKind::Text if column.editable => {
+            let mut attributes = vec![];
+            for cb in edit_callbacks {
+                let cb = cb.clone();
+                attributes.push(on_change(move |ev| {
+                    cb.emit(CellEditEvent::new(row_index, index, ev.value))
+                }));
+            }
+            input(
+                vec![
+                    r#type("text"),
+                    value(cell.map(|v| cell_text(v)).unwrap_or_default()),
+                ],
+                vec![],
+            )
+            .add_attributes(attributes)
+        }
+        CellKind::Text => text(cell.map(|v| cell_text(v)).unwrap_or_default()),
+        CellKind::Toggle => {
+            let checked = cell.map(|v| v.as_bool()).unwrap_or(false);
+            let mut attributes = vec![];
+            if column.editable {
+                for cb in edit_callbacks {
+                    let cb = cb.clone();
+                    attributes.push(on_click(move |_| {
+                        cb.emit(CellEditEvent::new(row_index, index, !checked))
+                    }));
+                }
+            }
+            input(vec![r#type("checkbox")], vec![])
+                .add_attributes(attrs_flag([("checked", "checked", checked)]))
+                .add_attributes(attrs_flag([(
+                    "disabled",
+                    "disabled",
+                    !column.editable,
+                )]))
+                .add_attributes(attributes)
+        }
+        CellKind::Progress => {
+            let fraction = cell.and_then(|v| v.as_f64()).unwrap_or(0.0);
+            progress(
+                vec![attr("max", "1"), attr("value", fraction.to_string())],
+                vec![],
+            )
+        }
+        CellKind::Image => {
+            let bytes = cell.and_then(|v| v.as_bytes());
+            let mime_type = bytes.and_then(image_util::image_mime_type);
+            match (bytes, mime_type) {
+                (Some(bytes), Some(mime_type)) => img(
+                    vec![src(format!(
+                        "data:{};base64,{}",
+                        mime_type,
+                        base64::encode(bytes)
+                    ))],
+                    vec![],
+                ),
+                _ => text(""),
+            }
+        }
+    }
+}
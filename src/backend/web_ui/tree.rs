@@ -0,0 +1,134 @@
+//! The tree view is nested lists, the children of an expanded node are a list in its item.
+//! Pressing the toggle of a node expands or collapses it, pressing its label selects it.
+use crate::{
+    image_util,
+    widget::{
+        attribute::{
+            find_callback, selected_ids,
+            util::{find_nodes, is_multi_select},
+            TreeNode,
+        },
+        event::{ExpandEvent, NodeSelectEvent},
+    },
+    AttribKey, Attribute, Value,
+};
+use sauron::{
+    html::{attributes::*, div, img, li, span, text, ul},
+    prelude::*,
+};
+use std::fmt::Debug;
+
+/// convert the nodes of the tree view into nested lists
+pub fn tree_view<MSG>(
+    attrs: &[Attribute<MSG>],
+    widget_key: Option<&Value>,
+) -> sauron::Node<MSG>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let nodes = find_nodes(attrs);
+    let selected = selected_ids(nodes);
+    div(
+        vec![
+            class("TreeView"),
+            if let Some(widget_key) = widget_key {
+                key(widget_key.to_string())
+            } else {
+                empty_attr()
+            },
+            styles([("overflow", "auto"), ("user-select", "none")]),
+        ],
+        vec![node_list(attrs, nodes, &selected)],
+    )
+}
+
+/// the list of the nodes, with the lists of the children of the expanded nodes
+fn node_list<MSG>(
+    attrs: &[Attribute<MSG>],
+    nodes: &[TreeNode],
+    selected: &[String],
+) -> sauron::Node<MSG>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let multi_select = is_multi_select(attrs);
+    let expand_callbacks =
+        find_callback(AttribKey::ExpandEvent, attrs).unwrap_or_default();
+    let select_callbacks =
+        find_callback(AttribKey::NodeSelectEvent, attrs).unwrap_or_default();
+
+    let items = nodes
+        .iter()
+        .map(|node| {
+            let mut toggle_attrs = vec![styles([
+                ("display", "inline-block"),
+                ("width", "1em"),
+                ("cursor", "pointer"),
+            ])];
+            if node.has_children {
+                for cb in expand_callbacks.iter() {
+                    let cb = (*cb).clone();
+                    let id = node.id.clone();
+                    let expanded = !node.expanded;
+                    toggle_attrs.push(on_click(move |_| {
+                        cb.emit(ExpandEvent::new(&id, expanded))
+                    }));
+                }
+            }
+            let toggle = match (node.has_children, node.expanded) {
+                (true, true) => "▾",
+                (true, false) => "▸",
+                (false, _) => "",
+            };
+
+            let mut label_attrs = vec![styles_flag([(
+                "background-color",
+                "#cce0ff",
+                node.selected,
+            )])];
+            for cb in select_callbacks.iter() {
+                let cb = (*cb).clone();
+                let id = node.id.clone();
+                let selected = selected.to_vec();
+                label_attrs.push(on_click(move |_| {
+                    cb.emit(NodeSelectEvent::pressed(
+                        &id,
+                        &selected,
+                        multi_select,
+                    ))
+                }));
+            }
+
+            let mut content = vec![span(toggle_attrs, vec![text(toggle)])];
+            if let Some(icon) = &node.icon {
+                let mime_type =
+                    image_util::image_mime_type(icon).unwrap_or("image/png");
+                content.push(img(
+                    vec![
+                        src(format!(
+                            "data:{};base64,{}",
+                            mime_type,
+                            base64::encode(icon)
+                        )),
+                        styles([("height", "1em"), ("margin-right", "4px")]),
+                    ],
+                    vec![],
+                ));
+            }
+            content.push(span(label_attrs, vec![text(&node.label)]));
+            if node.expanded {
+                content.push(node_list(attrs, &node.children, selected));
+            }
+            li(vec![], content)
+        })
+        .collect::<Vec<_>>();
+
+    ul(
+        vec![styles([
+            ("list-style", "none"),
+            ("margin", "0"),
+            ("padding-left", "1em"),
+        ])],
+        items,
+    )
+}
@@ -0,0 +1,72 @@
+//! The virtual list is a scrollable div with a content div as tall as all the rows.
+//! The built rows are pushed down by the padding of the content, to the offset of
+//! the first built row.
+//!
+//! The scroll offset of a div is not an attribute, so the list is only scrolled by the user
+//! and the `scroll_top` of the app only decides which rows are built.
+use crate::{
+    widget::{
+        attribute::{find_callback, find_value},
+        event::ScrollEvent,
+    },
+    AttribKey, Attribute, Value,
+};
+use sauron::{
+    html::{attributes::*, div, events::on},
+    prelude::*,
+};
+use std::fmt::Debug;
+use wasm_bindgen::JsCast;
+
+/// wrap the html of the built rows in the scrollable div
+pub fn virtual_list<MSG>(
+    attrs: &[Attribute<MSG>],
+    widget_key: Option<&Value>,
+    html_children: Vec<sauron::Node<MSG>>,
+) -> sauron::Node<MSG>
+where
+    MSG: Clone + Debug + 'static,
+{
+    let find_f64 = |key| {
+        find_value(key, attrs)
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0)
+    };
+    let height = find_value(AttribKey::Height, attrs)
+        .and_then(|v| v.as_f64())
+        .map(px)
+        .unwrap_or_else(|| "100%".to_string());
+
+    let mut attributes = vec![
+        class("VirtualList"),
+        if let Some(widget_key) = widget_key {
+            key(widget_key.to_string())
+        } else {
+            empty_attr()
+        },
+        styles([("overflow-y", "auto".to_string()), ("height", height)]),
+    ];
+    for cb in find_callback(AttribKey::ScrollEvent, attrs).unwrap_or_default() {
+        let cb = cb.clone();
+        attributes.push(on("scroll", move |event: sauron::web_sys::Event| {
+            let scroll_top = event
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+                .map(|element| element.scroll_top())
+                .unwrap_or(0);
+            cb.emit(ScrollEvent::new(scroll_top as f64))
+        }));
+    }
+
+    div(
+        attributes,
+        vec![div(
+            vec![styles([
+                ("box-sizing", "border-box".to_string()),
+                ("height", px(find_f64(AttribKey::ContentHeight))),
+                ("padding-top", px(find_f64(AttribKey::RowsOffset))),
+            ])],
+            html_children,
+        )],
+    )
+}
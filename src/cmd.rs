@@ -1,6 +1,7 @@
 //! provides functionalities for commands to be executed by the system, such as
 //! when the application starts or after the application updates.
 //!
+use crate::{ChosenFile, Dispatch, FileAction, FileDialog, OnChosen};
 use std::rc::Rc;
 
 /// Cmd is a command to be executed by the system.
@@ -11,6 +12,12 @@ use std::rc::Rc;
 /// The callback is supplied with the program an is then executed/emitted.
 pub struct Cmd<DSP>(pub Vec<Rc<dyn Fn(DSP)>>);
 
+impl<DSP> Clone for Cmd<DSP> {
+    fn clone(&self) -> Self {
+        Cmd(self.0.iter().map(Rc::clone).collect())
+    }
+}
+
 impl<DSP> Cmd<DSP>
 where
     DSP: Clone + 'static,
@@ -45,3 +52,42 @@ where
         }
     }
 }
+
+impl<MSG> Cmd<Rc<dyn Dispatch<MSG>>>
+where
+    MSG: 'static,
+{
+    /// show a file dialog for choosing the files to open,
+    /// `on_chosen` is called with the chosen files which is empty when the user cancelled
+    pub fn open_file<F>(dialog: FileDialog, on_chosen: F) -> Self
+    where
+        F: Fn(Vec<ChosenFile>) -> MSG + 'static,
+    {
+        let on_chosen: OnChosen<MSG> = Rc::new(on_chosen);
+        Cmd::new(move |program: Rc<dyn Dispatch<MSG>>| {
+            let dialog = FileDialog {
+                action: FileAction::Open,
+                ..dialog.clone()
+            };
+            program.choose_files(dialog, Rc::clone(&on_chosen));
+        })
+    }
+
+    /// show a file dialog for choosing the path to save into,
+    /// `on_chosen` is called with `None` when the user cancelled
+    pub fn save_file<F>(dialog: FileDialog, on_chosen: F) -> Self
+    where
+        F: Fn(Option<ChosenFile>) -> MSG + 'static,
+    {
+        let on_chosen: OnChosen<MSG> =
+            Rc::new(move |files| on_chosen(files.into_iter().next()));
+        Cmd::new(move |program: Rc<dyn Dispatch<MSG>>| {
+            let dialog = FileDialog {
+                action: FileAction::Save,
+                multiple: false,
+                ..dialog.clone()
+            };
+            program.choose_files(dialog, Rc::clone(&on_chosen));
+        })
+    }
+}
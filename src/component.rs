@@ -1,15 +1,46 @@
+use crate::{backend::Dispatch, Cmd, Event, Window, WindowConfig};
 use sauron_widget::Node;
+use std::rc::Rc;
 
 /// Applications must implement Component
-pub trait Component<MSG> {
-    /// returns a string that sets the title of the app
+pub trait Component<MSG>
+where
+    MSG: 'static,
+{
+    /// returns a string that sets the title of the app,
+    /// the title is updated when it changes after an update
     fn title(&self) -> String {
         std::any::type_name::<Self>().to_string()
     }
 
-    /// updates the state of the Application based on the supplied msg
-    fn update(&mut self, msg: MSG);
+    /// returns the configuration of the window where the app is displayed,
+    /// this is read once when the backend starts
+    fn window_config(&self) -> WindowConfig {
+        WindowConfig::default()
+    }
+
+    /// called once when the backend has mounted the app,
+    /// the returned Cmd is executed right after the initial view is rendered
+    fn init(&self) -> Cmd<Rc<dyn Dispatch<MSG>>> {
+        Cmd::none()
+    }
+
+    /// updates the state of the Application based on the supplied msg,
+    /// the returned Cmd is executed after the view is updated
+    fn update(&mut self, msg: MSG) -> Cmd<Rc<dyn Dispatch<MSG>>>;
 
     /// returns a Node tree for rendering the view
     fn view(&self) -> Node<MSG>;
+
+    /// returns the extra windows of the app, each with its own view.
+    /// A window is opened when its id is first returned and closed when it is no longer returned
+    fn windows(&self) -> Vec<Window<MSG>> {
+        vec![]
+    }
+
+    /// called when an event happened to the window of the app, such as `Event::Resize`.
+    /// Apps subscribe to the event by returning a msg, which is then dispatched
+    fn window_event(&self, _event: Event) -> Option<MSG> {
+        None
+    }
 }
@@ -0,0 +1,137 @@
+//! The file dialogs for choosing the files to open or the path to save into.
+//!
+//! The dialogs are shown with `Cmd::open_file` and `Cmd::save_file`, then the files
+//! chosen by the user are passed back to the app as a msg.
+//! The browser has no access to the file system, so it reads the bytes of the opened files
+//! and downloads the `contents` of the dialog when saving.
+use std::{
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+/// Called with the files chosen by the user, which is empty when the user cancelled.
+/// The returned msg is dispatched to the app
+pub type OnChosen<MSG> = Rc<dyn Fn(Vec<ChosenFile>) -> MSG>;
+
+/// Whether the dialog chooses the files to open or the path to save into
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileAction {
+    /// choose existing files to open
+    Open,
+    /// choose a path to save into, the file doesn't need to exist
+    Save,
+}
+
+/// Only the files with these extensions are listed in the dialog
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileFilter {
+    /// the name of the filter, such as "Images"
+    pub name: String,
+    /// the extensions of the files without the dot, such as "png"
+    pub extensions: Vec<String>,
+}
+
+impl FileFilter {
+    /// whether the file has one of the extensions of this filter
+    pub fn matches(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| {
+                self.extensions
+                    .iter()
+                    .any(|filter_ext| filter_ext.eq_ignore_ascii_case(ext))
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// Describes the file dialog to be shown
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileDialog {
+    /// whether to open or save, this is set by `Cmd::open_file` and `Cmd::save_file`
+    pub action: FileAction,
+    /// the title of the dialog
+    pub title: String,
+    /// the filters of the files listed in the dialog, all files are listed when empty
+    pub filters: Vec<FileFilter>,
+    /// whether the user can choose more than one file to open
+    pub multiple: bool,
+    /// the folder which is displayed when the dialog is shown
+    pub current_folder: Option<PathBuf>,
+    /// the suggested name of the file to save into
+    pub current_name: Option<String>,
+    /// the bytes which the browser downloads when saving,
+    /// since the browser can not write into a path
+    pub contents: Option<Vec<u8>>,
+}
+
+impl FileDialog {
+    /// create a dialog with this title, which lists all the files
+    pub fn new(title: &str) -> Self {
+        FileDialog {
+            action: FileAction::Open,
+            title: title.to_string(),
+            filters: vec![],
+            multiple: false,
+            current_folder: None,
+            current_name: None,
+            contents: None,
+        }
+    }
+
+    /// add a filter which lists only the files with these extensions
+    pub fn filter(mut self, name: &str, extensions: &[&str]) -> Self {
+        self.filters.push(FileFilter {
+            name: name.to_string(),
+            extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+        });
+        self
+    }
+
+    /// set whether the user can choose more than one file to open
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    /// set the folder which is displayed when the dialog is shown
+    pub fn current_folder<P: AsRef<Path>>(mut self, folder: P) -> Self {
+        self.current_folder = Some(folder.as_ref().to_path_buf());
+        self
+    }
+
+    /// set the suggested name of the file to save into
+    pub fn current_name(mut self, name: &str) -> Self {
+        self.current_name = Some(name.to_string());
+        self
+    }
+
+    /// set the bytes which the browser downloads when saving
+    pub fn contents(mut self, bytes: Vec<u8>) -> Self {
+        self.contents = Some(bytes);
+        self
+    }
+
+    /// whether the file is listed by any of the filters of the dialog
+    pub fn is_listed(&self, path: &Path) -> bool {
+        self.filters.is_empty()
+            || self.filters.iter().any(|filter| filter.matches(path))
+    }
+}
+
+/// A file chosen by the user in the file dialog
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChosenFile {
+    /// the path of the file, in the browser this is only the name of the file
+    pub path: PathBuf,
+    /// the content of the opened file, this is only read in the browser
+    /// since it has no access to the path
+    pub bytes: Option<Vec<u8>>,
+}
+
+impl ChosenFile {
+    /// a file chosen by its path
+    pub fn new(path: PathBuf) -> Self {
+        ChosenFile { path, bytes: None }
+    }
+}
@@ -4,16 +4,29 @@
 //#![deny(warnings, missing_docs)]
 #![deny(clippy::all)]
 
-pub use backend::Backend;
+pub use backend::{Backend, Dispatch};
+pub use cmd::Cmd;
 pub use component::Component;
+pub use file_dialog::{
+    ChosenFile, FileAction, FileDialog, FileFilter, OnChosen,
+};
 pub use mt_dom;
+pub use program::Program;
+pub use sauron_native_macro::view;
 pub use sauron_widget::{
     attribute,
     attribute::{AttribKey, Value},
     widget, Attribute, Element, Event, Node, Patch, Widget,
 };
 pub use stretch;
+pub use window::{Window, WindowChange};
+pub use window_config::WindowConfig;
 
 pub mod backend;
+pub mod cmd;
 mod component;
+mod file_dialog;
 pub(crate) mod image_util;
+mod program;
+mod window;
+mod window_config;
@@ -0,0 +1,478 @@
+//! Program is the backend independent runtime of an application.
+//! It owns the app, the current view and the queue of msgs to be processed,
+//! so the backends only need to supply a patch applier.
+//!
+use crate::{
+    backend::Dispatch, widget::layout::compute_node_layout, AttribKey, Cmd,
+    Component, Event, FileDialog, Node, OnChosen, Patch, Window, WindowChange,
+};
+use std::{
+    cell::{Cell, Ref, RefCell},
+    collections::{BTreeMap, VecDeque},
+    marker::PhantomData,
+    rc::Rc,
+};
+use stretch::geometry::Size;
+use stretch::number::Number;
+
+/// A patch applier applies the patches to the actual widgets of the backend.
+/// The first argument is the view where the patches are computed from.
+type PatchApplier<MSG> = Rc<dyn Fn(&Node<MSG>, &[Patch<MSG>])>;
+
+/// A title applier sets the title of the window in the backend
+type TitleApplier = Rc<dyn Fn(&str)>;
+
+/// A window applier applies the changes in the extra windows of the app
+type WindowApplier<MSG> = Rc<dyn Fn(WindowChange<MSG>)>;
+
+/// A file dialog applier shows the file dialog in the backend
+type FileDialogApplier<MSG> = Rc<dyn Fn(FileDialog, OnChosen<MSG>)>;
+
+/// A view filter rewrites the view before it is diffed, such as displaying a widget
+/// which the backend doesn't have with the widgets that it has
+type ViewFilter<MSG> = fn(Node<MSG>) -> Node<MSG>;
+
+/// The title and the current view of the extra windows, keyed by their id
+type OpenWindows<MSG> = BTreeMap<String, (String, Node<MSG>)>;
+
+/// Holds the app, its current view and the msgs that are yet to be processed
+pub struct Program<APP, MSG>
+where
+    MSG: 'static,
+{
+    app: Rc<RefCell<APP>>,
+    current_vdom: Rc<RefCell<Node<MSG>>>,
+    pending_msgs: Rc<RefCell<VecDeque<MSG>>>,
+    /// whether the program is processing the pending msgs or patching the view,
+    /// msgs dispatched in the meantime are processed in the same loop.
+    is_updating: Rc<Cell<bool>>,
+    /// a redraw requested while the view is patched, which is done once the patching is done
+    redraw_requested: Rc<Cell<bool>>,
+    /// the size used to calculate the layout of the view,
+    /// no layout is calculated when this is not set
+    viewport_size: Rc<Cell<Option<(f32, f32)>>>,
+    patch_applier: Rc<RefCell<Option<PatchApplier<MSG>>>>,
+    /// the title that was last set in the window
+    title: Rc<RefCell<String>>,
+    title_applier: Rc<RefCell<Option<TitleApplier>>>,
+    /// the title and the current view of the extra windows, keyed by their id
+    windows: Rc<RefCell<OpenWindows<MSG>>>,
+    window_applier: Rc<RefCell<Option<WindowApplier<MSG>>>>,
+    file_dialog_applier: Rc<RefCell<Option<FileDialogApplier<MSG>>>>,
+    view_filter: Rc<Cell<Option<ViewFilter<MSG>>>>,
+    _phantom_msg: PhantomData<MSG>,
+}
+
+impl<APP, MSG> Clone for Program<APP, MSG> {
+    fn clone(&self) -> Self {
+        Program {
+            app: Rc::clone(&self.app),
+            current_vdom: Rc::clone(&self.current_vdom),
+            pending_msgs: Rc::clone(&self.pending_msgs),
+            is_updating: Rc::clone(&self.is_updating),
+            redraw_requested: Rc::clone(&self.redraw_requested),
+            viewport_size: Rc::clone(&self.viewport_size),
+            patch_applier: Rc::clone(&self.patch_applier),
+            title: Rc::clone(&self.title),
+            title_applier: Rc::clone(&self.title_applier),
+            windows: Rc::clone(&self.windows),
+            window_applier: Rc::clone(&self.window_applier),
+            file_dialog_applier: Rc::clone(&self.file_dialog_applier),
+            view_filter: Rc::clone(&self.view_filter),
+            _phantom_msg: PhantomData,
+        }
+    }
+}
+
+impl<APP, MSG> Program<APP, MSG>
+where
+    MSG: 'static,
+    APP: Component<MSG> + 'static,
+{
+    /// create a program from the app, the initial view is calculated
+    /// using the viewport size if there is any.
+    pub fn new(app: APP, viewport_size: Option<(f32, f32)>) -> Self {
+        let current_vdom = Self::calculate_view(&app, viewport_size);
+        let title = app.title();
+        Program {
+            app: Rc::new(RefCell::new(app)),
+            current_vdom: Rc::new(RefCell::new(current_vdom)),
+            pending_msgs: Rc::new(RefCell::new(VecDeque::new())),
+            is_updating: Rc::new(Cell::new(false)),
+            redraw_requested: Rc::new(Cell::new(false)),
+            viewport_size: Rc::new(Cell::new(viewport_size)),
+            patch_applier: Rc::new(RefCell::new(None)),
+            title: Rc::new(RefCell::new(title)),
+            title_applier: Rc::new(RefCell::new(None)),
+            windows: Rc::new(RefCell::new(BTreeMap::new())),
+            window_applier: Rc::new(RefCell::new(None)),
+            file_dialog_applier: Rc::new(RefCell::new(None)),
+            view_filter: Rc::new(Cell::new(None)),
+            _phantom_msg: PhantomData,
+        }
+    }
+
+    /// set the patch applier that is used when a msg is dispatched
+    pub fn set_patch_applier<F>(&self, patch_applier: F)
+    where
+        F: Fn(&Node<MSG>, &[Patch<MSG>]) + 'static,
+    {
+        *self.patch_applier.borrow_mut() = Some(Rc::new(patch_applier));
+    }
+
+    /// set the title applier that is used when the title of the app changed
+    pub fn set_title_applier<F>(&self, title_applier: F)
+    where
+        F: Fn(&str) + 'static,
+    {
+        *self.title_applier.borrow_mut() = Some(Rc::new(title_applier));
+    }
+
+    /// returns the title which was last set in the window
+    pub fn title(&self) -> String {
+        self.title.borrow().clone()
+    }
+
+    /// set the title of the window using the title applier,
+    /// if the title of the app changed since it was last set
+    pub fn update_title(&self) {
+        let title = self.app.borrow().title();
+        if *self.title.borrow() != title {
+            let title_applier = self.title_applier.borrow().clone();
+            if let Some(title_applier) = title_applier {
+                title_applier(&title);
+            }
+            *self.title.borrow_mut() = title;
+        }
+    }
+
+    /// set the window applier that is used when the extra windows of the app changed
+    pub fn set_window_applier<F>(&self, window_applier: F)
+    where
+        F: Fn(WindowChange<MSG>) + 'static,
+    {
+        *self.window_applier.borrow_mut() = Some(Rc::new(window_applier));
+    }
+
+    /// set the file dialog applier that is used when a command of the app
+    /// shows a file dialog
+    pub fn set_file_dialog_applier<F>(&self, file_dialog_applier: F)
+    where
+        F: Fn(FileDialog, OnChosen<MSG>) + 'static,
+    {
+        *self.file_dialog_applier.borrow_mut() =
+            Some(Rc::new(file_dialog_applier));
+    }
+
+    /// set the view filter which rewrites the views of the app before they are diffed,
+    /// the current view is rewritten with it as well
+    pub fn set_view_filter(&self, view_filter: ViewFilter<MSG>) {
+        self.view_filter.set(Some(view_filter));
+        let current_vdom = self.view();
+        *self.current_vdom.borrow_mut() = current_vdom;
+    }
+
+    /// rewrite the view with the view filter, if there is any
+    fn filter_view(&self, view: Node<MSG>) -> Node<MSG> {
+        match self.view_filter.get() {
+            Some(view_filter) => view_filter(view),
+            None => view,
+        }
+    }
+
+    /// the extra windows of the app, with the layout of their view computed
+    /// using the size in their config
+    pub fn window_views(&self) -> Vec<Window<MSG>> {
+        let windows = self.app.borrow().windows();
+        windows
+            .into_iter()
+            .map(|mut window| {
+                let (width, height) = window.config.size;
+                compute_node_layout(
+                    &mut window.view,
+                    Size {
+                        width: Number::Defined(width as f32),
+                        height: Number::Defined(height as f32),
+                    },
+                );
+                window.view = self.filter_view(window.view);
+                window
+            })
+            .collect()
+    }
+
+    /// the msg to be dispatched when the user closes the window with this id
+    pub fn window_close_msg(&self, id: &str) -> Option<MSG> {
+        self.app
+            .borrow()
+            .windows()
+            .into_iter()
+            .find(|window| window.id == id)
+            .and_then(|window| window.on_close)
+    }
+
+    /// update the extra windows using the set window applier
+    pub fn update_windows(&self) {
+        let window_applier = self.window_applier.borrow().clone();
+        if let Some(window_applier) = window_applier {
+            self.update_windows_with(|change| window_applier(change));
+        }
+    }
+
+    /// compare the extra windows of the app against the windows that are open,
+    /// then open, patch or close the windows using `apply_change`
+    pub fn update_windows_with<F>(&self, mut apply_change: F)
+    where
+        F: FnMut(WindowChange<MSG>),
+    {
+        let new_windows = self.window_views();
+        let closed: Vec<String> = self
+            .windows
+            .borrow()
+            .keys()
+            .filter(|id| !new_windows.iter().any(|window| window.id == **id))
+            .cloned()
+            .collect();
+        for id in closed {
+            apply_change(WindowChange::Close(&id));
+            self.windows.borrow_mut().remove(&id);
+        }
+        for window in new_windows {
+            {
+                let windows = self.windows.borrow();
+                match windows.get(&window.id) {
+                    Some((title, current_vdom)) => {
+                        let patches = mt_dom::diff_with_key(
+                            current_vdom,
+                            &window.view,
+                            &AttribKey::Key,
+                        );
+                        if !patches.is_empty() {
+                            apply_change(WindowChange::Patch {
+                                id: &window.id,
+                                current_vdom,
+                                patches: &patches,
+                            });
+                        }
+                        if *title != window.title {
+                            apply_change(WindowChange::SetTitle {
+                                id: &window.id,
+                                title: &window.title,
+                            });
+                        }
+                    }
+                    None => apply_change(WindowChange::Open(&window)),
+                }
+            }
+            self.windows
+                .borrow_mut()
+                .insert(window.id, (window.title, window.view));
+        }
+    }
+
+    /// execute the init Cmd of the app
+    pub fn init(&self) {
+        let cmd = self.app.borrow().init();
+        cmd.emit(&self.dispatcher());
+    }
+
+    /// the dispatcher which is passed to the commands of the app
+    pub fn dispatcher(&self) -> Rc<dyn Dispatch<MSG>> {
+        Rc::new(self.clone())
+    }
+
+    /// returns a reference to the app
+    pub fn app(&self) -> Ref<APP> {
+        self.app.borrow()
+    }
+
+    /// returns the view which was last rendered by the backend
+    pub fn current_vdom(&self) -> Ref<Node<MSG>> {
+        self.current_vdom.borrow()
+    }
+
+    /// returns the size used in calculating the layout of the view
+    pub fn viewport_size(&self) -> Option<(f32, f32)> {
+        self.viewport_size.get()
+    }
+
+    /// set the size used in calculating the layout of the view,
+    /// the view is redrawn if the size changed
+    pub fn set_viewport_size(&self, width: f32, height: f32) {
+        if self.viewport_size.get() != Some((width, height)) {
+            self.viewport_size.set(Some((width, height)));
+            self.redraw();
+        }
+    }
+
+    /// the window of the app is resized, the layout of the view is recalculated
+    /// and the app is notified with `Event::Resize`
+    pub fn resize(&self, width: f32, height: f32) {
+        if let Some(msg) = self.resize_msg(width, height) {
+            self.dispatch(msg);
+        }
+    }
+
+    /// set the viewport size of the resized window and return the msg of the app
+    /// for `Event::Resize`, if the size changed.
+    /// This is used by backends which dispatch the msgs themselves such as the web backend.
+    pub fn resize_msg(&self, width: f32, height: f32) -> Option<MSG> {
+        if self.viewport_size.get() == Some((width, height)) {
+            return None;
+        }
+        self.set_viewport_size(width, height);
+        self.app.borrow().window_event(Event::Resize(width, height))
+    }
+
+    /// calculate the view of the app, with the layout computed
+    /// when there is a viewport size
+    pub fn view(&self) -> Node<MSG> {
+        let view =
+            Self::calculate_view(&self.app.borrow(), self.viewport_size.get());
+        self.filter_view(view)
+    }
+
+    fn calculate_view(
+        app: &APP,
+        viewport_size: Option<(f32, f32)>,
+    ) -> Node<MSG> {
+        let mut view = app.view();
+        if let Some((width, height)) = viewport_size {
+            compute_node_layout(
+                &mut view,
+                Size {
+                    width: Number::Defined(width),
+                    height: Number::Defined(height),
+                },
+            );
+        }
+        view
+    }
+
+    /// update the app with the msg without patching the view.
+    /// This is used by backends which do their own diffing such as the web backend.
+    pub fn update(&self, msg: MSG) -> Cmd<Rc<dyn Dispatch<MSG>>> {
+        self.app.borrow_mut().update(msg)
+    }
+
+    /// redraw the view using the set patch applier
+    pub fn redraw(&self) {
+        let patch_applier = self.patch_applier.borrow().clone();
+        if let Some(patch_applier) = patch_applier {
+            self.redraw_with(|current_vdom, patches| {
+                patch_applier(current_vdom, patches)
+            });
+        }
+    }
+
+    /// diff the new view against the current view,
+    /// then apply the patches using `apply_patches`.
+    /// A redraw requested while the view is patched, such as from a signal which the backend
+    /// emits while applying the patches, is done after the patching.
+    /// The msgs dispatched while patching are processed afterwards as well.
+    pub fn redraw_with<F>(&self, mut apply_patches: F)
+    where
+        F: FnMut(&Node<MSG>, &[Patch<MSG>]),
+    {
+        if self.is_updating.get() {
+            self.redraw_requested.set(true);
+            return;
+        }
+        self.is_updating.set(true);
+        self.patch_view(&mut apply_patches);
+        self.is_updating.set(false);
+        self.process_pending_msgs_with(apply_patches);
+    }
+
+    /// diff and patch the view, the current view is only borrowed while the patches
+    /// are applied and is replaced once the borrow is released
+    fn patch_view<F>(&self, apply_patches: &mut F)
+    where
+        F: FnMut(&Node<MSG>, &[Patch<MSG>]),
+    {
+        self.redraw_requested.set(false);
+        let new_vdom = self.view();
+        {
+            let current_vdom = self.current_vdom.borrow();
+            let patches = mt_dom::diff_with_key(
+                &current_vdom,
+                &new_vdom,
+                &AttribKey::Key,
+            );
+            if !patches.is_empty() {
+                apply_patches(&current_vdom, &patches);
+            }
+        }
+        *self.current_vdom.borrow_mut() = new_vdom;
+    }
+
+    /// process all the pending msgs, the view is calculated and patched once
+    /// for all the msgs in the queue, then the returned commands are executed.
+    /// Msgs dispatched by the commands are processed in the same loop.
+    pub fn process_pending_msgs_with<F>(&self, mut apply_patches: F)
+    where
+        F: FnMut(&Node<MSG>, &[Patch<MSG>]),
+    {
+        if self.is_updating.get() {
+            return;
+        }
+        self.is_updating.set(true);
+        loop {
+            let mut cmds = vec![];
+            loop {
+                let msg = self.pending_msgs.borrow_mut().pop_front();
+                match msg {
+                    Some(msg) => cmds.push(self.update(msg)),
+                    None => break,
+                }
+            }
+            if cmds.is_empty() && !self.redraw_requested.get() {
+                break;
+            }
+            self.patch_view(&mut apply_patches);
+            self.update_title();
+            self.update_windows();
+            let dispatcher = self.dispatcher();
+            for cmd in cmds {
+                cmd.emit(&dispatcher);
+            }
+        }
+        self.is_updating.set(false);
+    }
+
+    /// queue the msg, it will be processed on the next call to
+    /// `process_pending_msgs_with`
+    pub fn queue_msg(&self, msg: MSG) {
+        self.pending_msgs.borrow_mut().push_back(msg);
+    }
+}
+
+impl<APP, MSG> Dispatch<MSG> for Program<APP, MSG>
+where
+    MSG: 'static,
+    APP: Component<MSG> + 'static,
+{
+    /// the msg is queued and processed right away if there is a patch applier,
+    /// otherwise the backend has to call `process_pending_msgs_with`
+    fn dispatch(&self, msg: MSG) {
+        self.queue_msg(msg);
+        let patch_applier = self.patch_applier.borrow().clone();
+        if let Some(patch_applier) = patch_applier {
+            self.process_pending_msgs_with(|current_vdom, patches| {
+                patch_applier(current_vdom, patches)
+            });
+        }
+    }
+
+    /// the file dialog is shown using the file dialog applier of the backend
+    fn choose_files(&self, dialog: FileDialog, on_chosen: OnChosen<MSG>) {
+        let file_dialog_applier = self.file_dialog_applier.borrow().clone();
+        match file_dialog_applier {
+            Some(file_dialog_applier) => file_dialog_applier(dialog, on_chosen),
+            None => {
+                log::warn!("file dialog is not supported: {:?}", dialog.title);
+                self.dispatch(on_chosen(vec![]));
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Extra windows of the app, such as preferences or a detached inspector.
+//!
+//! The app returns its extra windows in `Component::windows`, each with its own view.
+//! A window is opened when its id is first returned and closed when it is no longer returned.
+//! The windows share the model and the msg type of the app.
+use crate::{Node, Patch, WindowConfig};
+
+/// An extra window of the app
+pub struct Window<MSG> {
+    /// the unique id of the window, used to address the window
+    pub id: String,
+    /// the title of the window
+    pub title: String,
+    /// the configuration of the window, the size is used in calculating the layout of its view
+    pub config: WindowConfig,
+    /// the view of the window
+    pub view: Node<MSG>,
+    /// the msg dispatched when the user closes the window.
+    /// The window stays open until the app stops returning it
+    pub on_close: Option<MSG>,
+}
+
+impl<MSG> Window<MSG> {
+    /// create a window with the default configuration
+    pub fn new(id: &str, title: &str, view: Node<MSG>) -> Self {
+        Window {
+            id: id.to_string(),
+            title: title.to_string(),
+            config: WindowConfig::default(),
+            view,
+            on_close: None,
+        }
+    }
+
+    /// set the configuration of the window
+    pub fn config(mut self, config: WindowConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// set the msg dispatched when the user closes the window
+    pub fn on_close(mut self, msg: MSG) -> Self {
+        self.on_close = Some(msg);
+        self
+    }
+}
+
+/// The changes in the extra windows, which the backend applies
+pub enum WindowChange<'a, MSG> {
+    /// open a new window
+    Open(&'a Window<MSG>),
+    /// patch the view of the window
+    Patch {
+        /// the id of the window
+        id: &'a str,
+        /// the view where the patches are computed from
+        current_vdom: &'a Node<MSG>,
+        /// the patches to be applied to the view of the window
+        patches: &'a [Patch<'a, MSG>],
+    },
+    /// the title of the window changed
+    SetTitle {
+        /// the id of the window
+        id: &'a str,
+        /// the new title of the window
+        title: &'a str,
+    },
+    /// close the window with this id
+    Close(&'a str),
+}
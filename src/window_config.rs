@@ -0,0 +1,99 @@
+//! The configuration of the window where the app is displayed.
+//! Each backend applies what is applicable to it, such as the browser only
+//! uses the title, while gtk uses all of them.
+
+/// Configures the window of the app, returned by `Component::window_config`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowConfig {
+    /// the application id, such as "com.example.MyApp"
+    pub app_id: Option<String>,
+    /// the initial width and height of the window
+    pub size: (i32, i32),
+    /// the minimum width and height of the window
+    pub min_size: Option<(i32, i32)>,
+    /// the maximum width and height of the window
+    pub max_size: Option<(i32, i32)>,
+    /// the initial x and y position of the window in the screen,
+    /// `None` lets the window manager place the window
+    pub position: Option<(i32, i32)>,
+    /// whether the window can be resized by the user
+    pub resizable: bool,
+    /// whether the window has a title bar and borders
+    pub decorated: bool,
+    /// the bytes of the image used as the icon of the window
+    pub icon: Option<Vec<u8>>,
+    /// whether the window is displayed in full screen
+    pub fullscreen: bool,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        WindowConfig {
+            app_id: None,
+            size: (800, 600),
+            min_size: None,
+            max_size: None,
+            position: None,
+            resizable: true,
+            decorated: true,
+            icon: None,
+            fullscreen: false,
+        }
+    }
+}
+
+impl WindowConfig {
+    /// set the application id
+    pub fn app_id(mut self, app_id: &str) -> Self {
+        self.app_id = Some(app_id.to_string());
+        self
+    }
+
+    /// set the initial size of the window
+    pub fn size(mut self, width: i32, height: i32) -> Self {
+        self.size = (width, height);
+        self
+    }
+
+    /// set the minimum size of the window
+    pub fn min_size(mut self, width: i32, height: i32) -> Self {
+        self.min_size = Some((width, height));
+        self
+    }
+
+    /// set the maximum size of the window
+    pub fn max_size(mut self, width: i32, height: i32) -> Self {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    /// set the initial position of the window
+    pub fn position(mut self, x: i32, y: i32) -> Self {
+        self.position = Some((x, y));
+        self
+    }
+
+    /// set whether the window can be resized
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// set whether the window has a title bar and borders
+    pub fn decorated(mut self, decorated: bool) -> Self {
+        self.decorated = decorated;
+        self
+    }
+
+    /// set the icon of the window from the bytes of an image
+    pub fn icon(mut self, bytes: Vec<u8>) -> Self {
+        self.icon = Some(bytes);
+        self
+    }
+
+    /// set whether the window is displayed in full screen
+    pub fn fullscreen(mut self, fullscreen: bool) -> Self {
+        self.fullscreen = fullscreen;
+        self
+    }
+}
@@ -0,0 +1,186 @@
+//! Drives a small counter component through the `TestBackend`
+use sauron_native::{
+    backend::{
+        test_ui::{Selector, TestError},
+        TestBackend,
+    },
+    widget::{attribute::*, event::*, *},
+    AttribKey, Cmd, Component, Dispatch, Node,
+};
+use std::rc::Rc;
+
+struct Counter {
+    count: i32,
+    step: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    Increment,
+    Decrement,
+    ChangeStep(String),
+    Reset,
+}
+
+impl Component<Msg> for Counter {
+    fn update(&mut self, msg: Msg) -> Cmd<Rc<dyn Dispatch<Msg>>> {
+        let step = self.step.parse::<i32>().unwrap_or(1);
+        match msg {
+            Msg::Increment => self.count += step,
+            Msg::Decrement => self.count -= step,
+            Msg::ChangeStep(step) => self.step = step,
+            Msg::Reset => self.count = 0,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        column(
+            vec![name("counter")],
+            vec![
+                text_label(vec![id("count"), value(self.count.to_string())]),
+                row(
+                    vec![],
+                    vec![
+                        button(vec![
+                            key("dec"),
+                            label("-"),
+                            on_click(|_| Msg::Decrement),
+                        ]),
+                        button(vec![
+                            key("inc"),
+                            label("+"),
+                            on_click(|_| Msg::Increment),
+                        ]),
+                    ],
+                ),
+                text_input(vec![
+                    id("step"),
+                    value(self.step.clone()),
+                    on_input(|input: InputEvent| {
+                        Msg::ChangeStep(input.value.to_string())
+                    }),
+                    on_enter(|_| Msg::Reset),
+                ]),
+            ],
+        )
+    }
+}
+
+fn counter() -> TestBackend<Counter, Msg> {
+    TestBackend::new(Counter {
+        count: 0,
+        step: "1".to_string(),
+    })
+}
+
+fn count(backend: &TestBackend<Counter, Msg>) -> Option<String> {
+    backend
+        .value(&Selector::Id("count".to_string()), AttribKey::Value)
+        .map(|v| v.to_string())
+}
+
+#[test]
+fn find_widgets_by_id_key_and_path() {
+    let backend = counter();
+    assert_eq!(
+        backend.find(&Selector::Id("count".to_string())),
+        Some(vec![0])
+    );
+    assert_eq!(
+        backend.find(&Selector::Key("inc".to_string())),
+        Some(vec![1, 1])
+    );
+    assert_eq!(backend.find(&Selector::Path(vec![2])), Some(vec![2]));
+    assert_eq!(backend.find(&Selector::Path(vec![])), Some(vec![]));
+    assert_eq!(backend.find(&Selector::Path(vec![1, 2])), None);
+    assert_eq!(backend.find(&Selector::Key("missing".to_string())), None);
+}
+
+#[test]
+fn click_updates_the_view() {
+    let backend = counter();
+    let inc = Selector::Key("inc".to_string());
+    backend
+        .click(&inc, MouseEvent::click(0, 0))
+        .expect("must click");
+    backend
+        .click(&inc, MouseEvent::click(0, 0))
+        .expect("must click");
+    assert_eq!(count(&backend), Some("2".to_string()));
+    assert_eq!(backend.app().count, 2);
+
+    backend
+        .click(&Selector::Path(vec![1, 0]), MouseEvent::click(0, 0))
+        .expect("must click");
+    assert_eq!(count(&backend), Some("1".to_string()));
+}
+
+#[test]
+fn input_and_enter_update_the_view() {
+    let backend = counter();
+    let step = Selector::Id("step".to_string());
+    backend
+        .input(&step, InputEvent::new("5"))
+        .expect("must input");
+    assert_eq!(
+        backend
+            .value(&step, AttribKey::Value)
+            .map(|v| v.to_string()),
+        Some("5".to_string())
+    );
+    backend
+        .click(&Selector::Key("inc".to_string()), MouseEvent::click(0, 0))
+        .expect("must click");
+    assert_eq!(count(&backend), Some("5".to_string()));
+
+    backend.enter(&step, KeyEvent::enter()).expect("must enter");
+    assert_eq!(count(&backend), Some("0".to_string()));
+}
+
+#[test]
+fn snapshot_of_the_view() {
+    let backend = counter();
+    backend
+        .click(&Selector::Key("inc".to_string()), MouseEvent::click(0, 0))
+        .expect("must click");
+    let expected = r#"Vbox name="counter"
+  Label value="1" id="count"
+  Hbox
+    Button label="-" key="dec" events=[click_event]
+    Button label="+" key="inc" events=[click_event]
+  TextInput value="1" id="step" events=[input_event, activate]
+"#;
+    assert_eq!(backend.snapshot(), expected);
+}
+
+#[test]
+fn firing_on_a_missing_widget_is_not_found() {
+    let backend = counter();
+    let missing = Selector::Id("missing".to_string());
+    assert_eq!(
+        backend.click(&missing, MouseEvent::click(0, 0)),
+        Err(TestError::NotFound(missing))
+    );
+    let out_of_bounds = Selector::Path(vec![5]);
+    assert_eq!(
+        backend.click(&out_of_bounds, MouseEvent::click(0, 0)),
+        Err(TestError::NotFound(out_of_bounds))
+    );
+}
+
+#[test]
+fn firing_without_a_listener_is_no_listener() {
+    let backend = counter();
+    let label = Selector::Id("count".to_string());
+    assert_eq!(
+        backend.click(&label, MouseEvent::click(0, 0)),
+        Err(TestError::NoListener(label, AttribKey::ClickEvent))
+    );
+    let button = Selector::Key("inc".to_string());
+    assert_eq!(
+        backend.input(&button, InputEvent::new("x")),
+        Err(TestError::NoListener(button, AttribKey::InputEvent))
+    );
+    assert_eq!(count(&backend), Some("0".to_string()));
+}
@@ -0,0 +1,44 @@
+//! The `view!` macro expands into the same tree as the builder functions
+use sauron_native::{
+    view,
+    widget::{attribute::*, snapshot, *},
+    Node,
+};
+
+#[test]
+fn same_as_the_builder_functions() {
+    let from_macro: Node<()> = view! {
+        <column name="list">
+            <button label="Hi"/>
+            <checkbox label="done" checked=true/>
+        </column>
+    };
+    let from_builder: Node<()> = column(
+        vec![name("list")],
+        vec![
+            button(vec![label("Hi")]),
+            checkbox(vec![label("done"), checked(true)]),
+        ],
+    );
+    assert_eq!(
+        snapshot::render(&from_macro),
+        snapshot::render(&from_builder)
+    );
+}
+
+#[test]
+fn spliced_children_can_use_a_children_variable() {
+    let children = vec!["a", "b"];
+    let view: Node<()> = view! {
+        <column>
+            <button label="first"/>
+            { for children.iter().map(|txt| button(vec![label(*txt)])) }
+        </column>
+    };
+    let expected = r#"Vbox
+  Button label="first"
+  Button label="a"
+  Button label="b"
+"#;
+    assert_eq!(snapshot::render(&view), expected);
+}